@@ -12,10 +12,14 @@ use crate::{
     ui::{
         components::{colors::*, *},
         panels::{
+            asn1_panel::Asn1Panel, certificate_panel::CertificatePanel,
             color_picker_panel::ColorPickerPanel, cryptography_panel::CryptographyPanel,
             datetime_panel::DateTimePanel, encoding_panel::EncodingPanel,
             error_display::ErrorDisplay, generators_panel::GeneratorsPanel, jwt_panel::JwtPanel,
-            regex_panel::RegexPanel, top_panel::TopPanel,
+            paseto_panel::PasetoPanel, protobuf_panel::ProtobufPanel, qr_panel::QrPanel,
+            recipe_panel::RecipePanel, regex_panel::RegexPanel,
+            serialization_panel::SerializationPanel, top_panel::TopPanel,
+            unicode_panel::UnicodePanel, url_panel::UrlPanel,
         },
         traits::{UiComponent, UiPanel, UiWindow},
         windows::settings::SettingsWindow,
@@ -38,12 +42,21 @@ pub const CURRENT_COLOR_BOX_SIZE: f32 = 40.0;
 #[derive(Clone, Debug)]
 pub enum CentralPanelTab {
     Jwt,
+    Paseto,
     Encoding,
+    Url,
+    Protobuf,
+    Serialization,
+    Recipe,
     Regex,
+    Unicode,
     ColorPicker,
     Generators,
+    Qr,
     DateTime,
     Cryptography,
+    Asn1,
+    Certificates,
 }
 
 #[derive(Default)]
@@ -56,12 +69,21 @@ pub struct App {
     pub windows: Windows,
     pub top_panel: TopPanel,
     pub jwt_panel: JwtPanel,
+    pub paseto_panel: PasetoPanel,
     pub encoding_panel: EncodingPanel,
+    pub url_panel: UrlPanel,
+    pub protobuf_panel: ProtobufPanel,
+    pub serialization_panel: SerializationPanel,
+    pub recipe_panel: RecipePanel,
     pub regex_panel: RegexPanel,
+    pub unicode_panel: UnicodePanel,
     pub color_picker_panel: ColorPickerPanel,
     pub generators_panel: GeneratorsPanel,
+    pub qr_panel: QrPanel,
     pub datetime_panel: DateTimePanel,
     pub cryptography_panel: CryptographyPanel,
+    pub asn1_panel: Asn1Panel,
+    pub certificate_panel: CertificatePanel,
     pub error_display: ErrorDisplay,
 }
 
@@ -131,12 +153,21 @@ impl App {
             windows: Windows::default(),
             top_panel: TopPanel::new(),
             jwt_panel: JwtPanel::new(),
+            paseto_panel: PasetoPanel::new(),
             encoding_panel: EncodingPanel::new(),
+            url_panel: UrlPanel::new(),
+            protobuf_panel: ProtobufPanel::new(),
+            serialization_panel: SerializationPanel::new(),
+            recipe_panel: RecipePanel::new(),
             regex_panel: RegexPanel::new(),
+            unicode_panel: UnicodePanel::new(),
             color_picker_panel: ColorPickerPanel::new(),
             generators_panel: GeneratorsPanel::new(),
+            qr_panel: QrPanel::new(),
             datetime_panel: DateTimePanel::new(),
             cryptography_panel: CryptographyPanel::new(),
+            asn1_panel: Asn1Panel::new(),
+            certificate_panel: CertificatePanel::new(),
             error_display: ErrorDisplay::new(),
         });
 
@@ -161,6 +192,8 @@ impl App {
             }
 
             ctx.app.load_palettes(context.storage);
+            ctx.app.load_recipes();
+            ctx.app.load_jwt_templates();
         }
 
         let mut fonts = egui::FontDefinitions::default();
@@ -228,12 +261,21 @@ impl App {
             .frame(_frame)
             .show(ctx.egui, |ui| match ctx.app.central_panel_tab {
                 CentralPanelTab::Jwt => self.jwt_ui(ctx, ui),
+                CentralPanelTab::Paseto => self.paseto_panel_ui(ctx, ui),
                 CentralPanelTab::ColorPicker => self.color_picker_ui(ctx, ui),
                 CentralPanelTab::Encoding => self.encoding_panel_ui(ctx, ui),
+                CentralPanelTab::Url => self.url_panel_ui(ctx, ui),
+                CentralPanelTab::Protobuf => self.protobuf_panel_ui(ctx, ui),
+                CentralPanelTab::Serialization => self.serialization_panel_ui(ctx, ui),
+                CentralPanelTab::Recipe => self.recipe_panel_ui(ctx, ui),
                 CentralPanelTab::Regex => self.regex_panel_ui(ctx, ui),
+                CentralPanelTab::Unicode => self.unicode_panel_ui(ctx, ui),
                 CentralPanelTab::Generators => self.generators_panel_ui(ctx, ui),
+                CentralPanelTab::Qr => self.qr_panel_ui(ctx, ui),
                 CentralPanelTab::DateTime => self.datetime_panel_ui(ctx, ui),
                 CentralPanelTab::Cryptography => self.cryptography_panel_ui(ctx, ui),
+                CentralPanelTab::Asn1 => self.asn1_panel_ui(ctx, ui),
+                CentralPanelTab::Certificates => self.certificate_panel_ui(ctx, ui),
             });
     }
 
@@ -242,6 +284,11 @@ impl App {
         self.jwt_panel.display(ctx, ui);
     }
 
+    fn paseto_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.paseto_panel.display(ctx, ui);
+    }
+
     fn color_picker_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
         self.error_display.render(ctx, ui);
         self.color_picker_panel.display(ctx, ui);
@@ -252,16 +299,46 @@ impl App {
         self.encoding_panel.display(ctx, ui);
     }
 
+    fn url_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.url_panel.display(ctx, ui);
+    }
+
+    fn protobuf_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.protobuf_panel.display(ctx, ui);
+    }
+
+    fn serialization_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.serialization_panel.display(ctx, ui);
+    }
+
+    fn recipe_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.recipe_panel.display(ctx, ui);
+    }
+
     fn regex_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
         self.error_display.render(ctx, ui);
         self.regex_panel.display(ctx, ui);
     }
 
+    fn unicode_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.unicode_panel.display(ctx, ui);
+    }
+
     fn generators_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
         self.error_display.render(ctx, ui);
         self.generators_panel.display(ctx, ui);
     }
 
+    fn qr_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.qr_panel.display(ctx, ui);
+    }
+
     fn datetime_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
         self.error_display.render(ctx, ui);
         self.datetime_panel.display(ctx, ui);
@@ -272,6 +349,16 @@ impl App {
         self.cryptography_panel.display(ctx, ui);
     }
 
+    fn asn1_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.asn1_panel.display(ctx, ui);
+    }
+
+    fn certificate_panel_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut egui::Ui) {
+        self.error_display.render(ctx, ui);
+        self.certificate_panel.display(ctx, ui);
+    }
+
     fn check_backtick_key_pressed(&mut self, ctx: &mut FrameCtx<'_>) {
         //TODO: Capture mouse clicked event
 
@@ -7,13 +7,21 @@ use serde::{Deserialize, Serialize};
 use crate::{
     app::{CentralPanelTab, DARK_VISUALS, LIGHT_VISUALS},
     core::{
+        asn1::Asn1Viewer,
         color::{palettes::Palettes, Color, ColorFormat},
-        crypto::CryptographyProcessor,
+        crypto::{x509::CertificateInspector, CryptographyProcessor},
         datetime::DateTimeProcessor,
         encoding::EncodingProcessor,
         generators::GeneratorProcessor,
-        jwt::JwtEncoderDecoder,
+        jwt::{templates::SavedTemplates, JwtEncoderDecoder},
+        paseto::PasetoTool,
+        protobuf::ProtobufDecoder,
+        qr::QrTool,
+        recipe::{RecipeProcessor, SavedRecipes},
         regex::RegexProcessor,
+        serialization::SerializationConverter,
+        unicode::UnicodeInspector,
+        url::UrlProcessor,
     },
     settings::{ColorDisplayFmtEnum, Settings},
     types::error::append_global_error,
@@ -35,11 +43,20 @@ pub struct AppCtx {
     pub sidepanel: SidePanelData,
 
     pub jwt: JwtEncoderDecoder,
+    pub paseto: PasetoTool,
     pub encoding: EncodingProcessor,
+    pub protobuf: ProtobufDecoder,
+    pub serialization: SerializationConverter,
+    pub recipe: RecipeProcessor,
     pub regex: RegexProcessor,
+    pub unicode: UnicodeInspector,
+    pub url: UrlProcessor,
     pub generator: GeneratorProcessor,
     pub datetime: DateTimeProcessor,
     pub crypto: CryptographyProcessor,
+    pub asn1: Asn1Viewer,
+    pub certificates: CertificateInspector,
+    pub qr: QrTool,
     pub picker: ColorPicker,
     pub palettes: Palettes,
 
@@ -76,11 +93,20 @@ impl Default for AppCtx {
                 response_size: (0., 0.).into(),
             },
             jwt: JwtEncoderDecoder::default(),
+            paseto: PasetoTool::default(),
             encoding: EncodingProcessor::default(),
+            protobuf: ProtobufDecoder::default(),
+            serialization: SerializationConverter::default(),
+            recipe: RecipeProcessor::default(),
             regex: RegexProcessor::default(),
+            unicode: UnicodeInspector::default(),
+            url: UrlProcessor::default(),
             generator: GeneratorProcessor::default(),
             datetime: DateTimeProcessor::default(),
             crypto: CryptographyProcessor::default(),
+            asn1: Asn1Viewer::default(),
+            certificates: CertificateInspector::default(),
+            qr: QrTool::default(),
             picker: ColorPicker::default(),
             palettes: Palettes::default(),
             cursor_pick_color: Color::black(),
@@ -181,6 +207,38 @@ impl AppCtx {
         }
     }
 
+    /// Path of the file holding saved transformation recipes
+    pub fn recipes_path(&self) -> Option<std::path::PathBuf> {
+        SavedRecipes::dir(APP_NAME).map(|dir| dir.join(SavedRecipes::FILE_NAME))
+    }
+
+    /// Load saved transformation recipes from disk
+    pub fn load_recipes(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = self.recipes_path() {
+            match SavedRecipes::load_or_default(path) {
+                Ok(saved) => self.recipe.saved = saved,
+                Err(e) => append_global_error(format!("failed to load recipes, {e:?}")),
+            }
+        }
+    }
+
+    /// Path of the file holding saved JWT claim templates
+    pub fn jwt_templates_path(&self) -> Option<std::path::PathBuf> {
+        SavedTemplates::dir(APP_NAME).map(|dir| dir.join(SavedTemplates::FILE_NAME))
+    }
+
+    /// Load saved JWT claim templates from disk
+    pub fn load_jwt_templates(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = self.jwt_templates_path() {
+            match SavedTemplates::load_or_default(path) {
+                Ok(saved) => self.jwt.templates = saved,
+                Err(e) => append_global_error(format!("failed to load JWT templates, {e:?}")),
+            }
+        }
+    }
+
     /// Adds a color to the currently selected palette
     pub fn add_color(&mut self, color: Color) {
         if !self.palettes.current_mut().palette.add(color) {
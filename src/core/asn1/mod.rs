@@ -0,0 +1,638 @@
+pub mod oid;
+pub mod pem;
+
+use anyhow::{anyhow, Result};
+use std::{fmt, fs, path::Path};
+
+use crate::core::encoding::input::BinaryInput;
+use oid::oid_name;
+use pem::{decode_pem, is_pem};
+
+const MAX_DEPTH: usize = 64;
+/// Longer byte values are truncated in the tree.
+const MAX_PREVIEW_BYTES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagClass {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tag {
+    pub class: TagClass,
+    pub constructed: bool,
+    pub number: u32,
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.class {
+            TagClass::Universal => match universal_name(self.number) {
+                Some(name) => write!(f, "{}", name),
+                None => write!(f, "UNIVERSAL {}", self.number),
+            },
+            TagClass::Application => write!(f, "[APPLICATION {}]", self.number),
+            TagClass::ContextSpecific => write!(f, "[{}]", self.number),
+            TagClass::Private => write!(f, "[PRIVATE {}]", self.number),
+        }
+    }
+}
+
+impl Tag {
+    fn is_universal(&self, number: u32) -> bool {
+        self.class == TagClass::Universal && self.number == number
+    }
+}
+
+fn universal_name(number: u32) -> Option<&'static str> {
+    Some(match number {
+        0 => "END-OF-CONTENTS",
+        1 => "BOOLEAN",
+        2 => "INTEGER",
+        3 => "BIT STRING",
+        4 => "OCTET STRING",
+        5 => "NULL",
+        6 => "OBJECT IDENTIFIER",
+        10 => "ENUMERATED",
+        12 => "UTF8String",
+        16 => "SEQUENCE",
+        17 => "SET",
+        18 => "NumericString",
+        19 => "PrintableString",
+        20 => "T61String",
+        22 => "IA5String",
+        23 => "UTCTime",
+        24 => "GeneralizedTime",
+        26 => "VisibleString",
+        28 => "UniversalString",
+        30 => "BMPString",
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asn1Node {
+    pub tag: Tag,
+    /// Byte offset of the identifier octet within the decoded input
+    pub offset: usize,
+    pub header_length: usize,
+    /// Length of the contents, excluding the end-of-contents octets of indefinite lengths
+    pub length: usize,
+    pub indefinite: bool,
+    pub value: String,
+    pub children: Vec<Asn1Node>,
+    /// Children were decoded from the contents of a primitive BIT STRING or OCTET STRING
+    pub encapsulated: bool,
+}
+
+/// Parses a sequence of BER/DER elements. Errors carry the byte offset of the malformed
+/// element.
+pub fn parse_der(bytes: &[u8]) -> Result<Vec<Asn1Node>> {
+    if bytes.is_empty() {
+        return Err(anyhow!("Input is empty"));
+    }
+    parse_elements(bytes, 0, bytes.len(), 0)
+}
+
+fn parse_elements(bytes: &[u8], start: usize, end: usize, depth: usize) -> Result<Vec<Asn1Node>> {
+    let mut nodes = Vec::new();
+    let mut pos = start;
+    while pos < end {
+        let (node, next) = parse_element(bytes, pos, end, depth)?;
+        nodes.push(node);
+        pos = next;
+    }
+    Ok(nodes)
+}
+
+fn parse_element(
+    bytes: &[u8],
+    offset: usize,
+    end: usize,
+    depth: usize,
+) -> Result<(Asn1Node, usize)> {
+    if depth > MAX_DEPTH {
+        return Err(anyhow!("Elements nested too deeply at byte {}", offset));
+    }
+
+    let truncated = || anyhow!("Truncated element header at byte {}", offset);
+    let mut pos = offset;
+    let identifier = *bytes.get(pos).filter(|_| pos < end).ok_or_else(truncated)?;
+    pos += 1;
+
+    let mut number = u32::from(identifier & 0x1f);
+    if number == 0x1f {
+        number = 0;
+        loop {
+            let byte = *bytes.get(pos).filter(|_| pos < end).ok_or_else(truncated)?;
+            pos += 1;
+            number = number
+                .checked_mul(128)
+                .map(|n| n | u32::from(byte & 0x7f))
+                .ok_or_else(|| anyhow!("Tag number too large at byte {}", offset))?;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+    let tag = Tag {
+        class: match identifier >> 6 {
+            0 => TagClass::Universal,
+            1 => TagClass::Application,
+            2 => TagClass::ContextSpecific,
+            _ => TagClass::Private,
+        },
+        constructed: identifier & 0x20 != 0,
+        number,
+    };
+
+    let first = *bytes.get(pos).filter(|_| pos < end).ok_or_else(truncated)?;
+    pos += 1;
+    let length = match first {
+        0x80 if tag.constructed => None,
+        0x80 => {
+            return Err(anyhow!(
+                "Indefinite length on primitive {} at byte {}",
+                tag,
+                offset
+            ))
+        }
+        0xff => return Err(anyhow!("Reserved length octet at byte {}", offset)),
+        short if short < 0x80 => Some(usize::from(short)),
+        long => {
+            let count = usize::from(long & 0x7f);
+            if count > std::mem::size_of::<usize>() || pos + count > end {
+                return Err(anyhow!("Invalid length encoding at byte {}", offset));
+            }
+            let value = bytes[pos..pos + count]
+                .iter()
+                .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+            pos += count;
+            Some(value)
+        }
+    };
+    let header_length = pos - offset;
+
+    let Some(length) = length else {
+        // Indefinite length: children until the end-of-contents octets
+        let mut children = Vec::new();
+        let content_start = pos;
+        loop {
+            if pos + 2 <= end && bytes[pos] == 0 && bytes[pos + 1] == 0 {
+                break;
+            }
+            if pos >= end {
+                return Err(anyhow!(
+                    "Missing end-of-contents for {} at byte {}",
+                    tag,
+                    offset
+                ));
+            }
+            let (child, next) = parse_element(bytes, pos, end, depth + 1)?;
+            children.push(child);
+            pos = next;
+        }
+        let node = Asn1Node {
+            tag,
+            offset,
+            header_length,
+            length: pos - content_start,
+            indefinite: true,
+            value: format!("{} elements", children.len()),
+            children,
+            encapsulated: false,
+        };
+        return Ok((node, pos + 2));
+    };
+
+    if length > end - pos {
+        return Err(anyhow!(
+            "Length {} of {} at byte {} exceeds the remaining {} bytes",
+            length,
+            tag,
+            offset,
+            end - pos
+        ));
+    }
+    let content = &bytes[pos..pos + length];
+
+    let (value, children, encapsulated) = if tag.constructed {
+        let children = parse_elements(bytes, pos, pos + length, depth + 1)?;
+        (format!("{} elements", children.len()), children, false)
+    } else {
+        let value = describe_primitive(&tag, content)
+            .map_err(|e| anyhow!("Invalid {} at byte {}: {}", tag, offset, e))?;
+        let children = encapsulated_children(&tag, bytes, pos, length, depth);
+        let encapsulated = !children.is_empty();
+        (value, children, encapsulated)
+    };
+
+    let node = Asn1Node {
+        tag,
+        offset,
+        header_length,
+        length,
+        indefinite: false,
+        value,
+        children,
+        encapsulated,
+    };
+    Ok((node, pos + length))
+}
+
+/// BIT STRING and OCTET STRING often wrap DER, e.g. public keys and certificate extension
+/// values. The contents are shown as a subtree when they parse completely, starting with a
+/// constructed element or forming a single well-known primitive.
+fn encapsulated_children(
+    tag: &Tag,
+    bytes: &[u8],
+    pos: usize,
+    length: usize,
+    depth: usize,
+) -> Vec<Asn1Node> {
+    let start = if tag.is_universal(3) {
+        // Only whole-byte bit strings can hold DER
+        if bytes.get(pos) != Some(&0) {
+            return Vec::new();
+        }
+        pos + 1
+    } else if tag.is_universal(4) {
+        pos
+    } else {
+        return Vec::new();
+    };
+    let end = pos + length;
+    if start >= end {
+        return Vec::new();
+    }
+
+    match parse_elements(bytes, start, end, depth + 1) {
+        Ok(children) => {
+            let plausible = match children.as_slice() {
+                [first, ..] if first.tag.constructed => true,
+                [only] => {
+                    tag.is_universal(4)
+                        && only.tag.class == TagClass::Universal
+                        && matches!(only.tag.number, 1..=4 | 6)
+                }
+                _ => false,
+            };
+            if plausible {
+                children
+            } else {
+                Vec::new()
+            }
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+fn describe_primitive(tag: &Tag, content: &[u8]) -> Result<String> {
+    if tag.class != TagClass::Universal {
+        return Ok(preview_hex(content));
+    }
+
+    Ok(match tag.number {
+        1 => match content {
+            [0] => "false".to_string(),
+            [_] => "true".to_string(),
+            _ => return Err(anyhow!("BOOLEAN must be 1 byte")),
+        },
+        2 | 10 => describe_integer(content)?,
+        3 => {
+            let (&unused, data) = content
+                .split_first()
+                .ok_or_else(|| anyhow!("missing unused bits octet"))?;
+            if unused > 7 || (data.is_empty() && unused != 0) {
+                return Err(anyhow!("{} unused bits", unused));
+            }
+            format!(
+                "{} bits: {}",
+                data.len() * 8 - usize::from(unused),
+                preview_hex(data)
+            )
+        }
+        4 => preview_hex(content),
+        5 => {
+            if !content.is_empty() {
+                return Err(anyhow!("NULL must be empty"));
+            }
+            String::new()
+        }
+        6 => {
+            let oid = decode_oid(content)?;
+            match oid_name(&oid) {
+                Some(name) => format!("{} ({})", oid, name),
+                None => oid,
+            }
+        }
+        12 | 18 | 19 | 22 | 26 => format!("{:?}", std::str::from_utf8(content)?),
+        20 => format!(
+            "{:?}",
+            content.iter().map(|&b| b as char).collect::<String>()
+        ),
+        23 | 24 => {
+            let text = std::str::from_utf8(content)?;
+            format_time(text, tag.number == 23).unwrap_or_else(|| text.to_string())
+        }
+        28 => {
+            if !content.len().is_multiple_of(4) {
+                return Err(anyhow!("length is not a multiple of 4"));
+            }
+            let text: Option<String> = content
+                .chunks(4)
+                .map(|c| char::from_u32(u32::from_be_bytes([c[0], c[1], c[2], c[3]])))
+                .collect();
+            format!("{:?}", text.ok_or_else(|| anyhow!("invalid code point"))?)
+        }
+        30 => {
+            if !content.len().is_multiple_of(2) {
+                return Err(anyhow!("length is not a multiple of 2"));
+            }
+            let units: Vec<u16> = content
+                .chunks(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            format!("{:?}", String::from_utf16(&units)?)
+        }
+        _ => preview_hex(content),
+    })
+}
+
+fn describe_integer(content: &[u8]) -> Result<String> {
+    if content.is_empty() {
+        return Err(anyhow!("integer has no content"));
+    }
+    if content.len() <= 16 {
+        let fill = if content[0] & 0x80 != 0 { 0xff } else { 0 };
+        let mut buffer = [fill; 16];
+        buffer[16 - content.len()..].copy_from_slice(content);
+        return Ok(i128::from_be_bytes(buffer).to_string());
+    }
+
+    let magnitude = match content {
+        [0, rest @ ..] => rest,
+        _ => content,
+    };
+    let bits = magnitude.len() * 8 - magnitude[0].leading_zeros() as usize;
+    Ok(format!("{} bits: {}", bits, preview_hex(magnitude)))
+}
+
+pub fn decode_oid(content: &[u8]) -> Result<String> {
+    if content.is_empty() || content.last().is_some_and(|b| b & 0x80 != 0) {
+        return Err(anyhow!("truncated object identifier"));
+    }
+
+    let mut arcs: Vec<u128> = Vec::new();
+    let mut value: u128 = 0;
+    for &byte in content {
+        value = value
+            .checked_mul(128)
+            .ok_or_else(|| anyhow!("object identifier arc too large"))?
+            | u128::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+
+    Ok(arcs
+        .iter()
+        .map(|arc| arc.to_string())
+        .collect::<Vec<_>>()
+        .join("."))
+}
+
+/// Formats `YYMMDDHHMMSSZ` and `YYYYMMDDHHMMSSZ` times, leaving other forms as-is.
+fn format_time(text: &str, utc_time: bool) -> Option<String> {
+    let digits = text.strip_suffix('Z')?;
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (year, rest) = if utc_time {
+        let yy: u32 = digits.get(..2)?.parse().ok()?;
+        (if yy >= 50 { 1900 + yy } else { 2000 + yy }, &digits[2..])
+    } else {
+        (digits.get(..4)?.parse().ok()?, &digits[4..])
+    };
+    if rest.len() != 10 {
+        return None;
+    }
+    Some(format!(
+        "{}-{}-{} {}:{}:{} UTC",
+        year,
+        &rest[0..2],
+        &rest[2..4],
+        &rest[4..6],
+        &rest[6..8],
+        &rest[8..10]
+    ))
+}
+
+fn preview_hex(bytes: &[u8]) -> String {
+    if bytes.len() > MAX_PREVIEW_BYTES {
+        format!(
+            "{}… ({} bytes)",
+            hex::encode(&bytes[..MAX_PREVIEW_BYTES]),
+            bytes.len()
+        )
+    } else {
+        hex::encode(bytes)
+    }
+}
+
+/// One decoded input, or one block of a PEM bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asn1Document {
+    pub label: Option<String>,
+    pub size: usize,
+    pub nodes: Vec<Asn1Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Asn1Viewer {
+    pub input: String,
+    pub input_format: BinaryInput,
+    pub documents: Vec<Asn1Document>,
+    pub error: Option<String>,
+}
+
+impl Asn1Viewer {
+    /// Decodes PEM blocks when present, otherwise hex or Base64 DER.
+    pub fn decode(&mut self) -> Result<()> {
+        let result = self.parse_input();
+        self.documents.clear();
+        match result {
+            Ok(documents) => {
+                self.documents = documents;
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    fn parse_input(&self) -> Result<Vec<Asn1Document>> {
+        if is_pem(&self.input) {
+            return decode_pem(&self.input)?
+                .into_iter()
+                .map(|block| {
+                    let nodes =
+                        parse_der(&block.der).map_err(|e| anyhow!("{}: {}", block.label, e))?;
+                    Ok(Asn1Document {
+                        label: Some(block.label),
+                        size: block.der.len(),
+                        nodes,
+                    })
+                })
+                .collect();
+        }
+
+        let bytes = self.input_format.decode(&self.input)?;
+        Ok(vec![Asn1Document {
+            label: None,
+            size: bytes.len(),
+            nodes: parse_der(&bytes)?,
+        }])
+    }
+
+    /// Loads a PEM or DER file.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        match String::from_utf8(bytes) {
+            Ok(text) if is_pem(&text) => self.input = text,
+            Ok(text) => {
+                self.input = hex::encode(text.as_bytes());
+                self.input_format = BinaryInput::Hex;
+            }
+            Err(e) => {
+                self.input = hex::encode(e.as_bytes());
+                self.input_format = BinaryInput::Hex;
+            }
+        }
+        self.decode()
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.documents.clear();
+        self.error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::crypto::asymmetric::rsa::generate_rsa_keypair;
+
+    const CSR: &str = "-----BEGIN CERTIFICATE REQUEST-----
+MIIBFjCBvAIBADAaMRgwFgYDVQQDDA93d3cuZXhhbXBsZS5jb20wWTATBgcqhkjO
+PQIBBggqhkjOPQMBBwNCAARNTYyZ3tNkLFHYdL3EpjdhkwfTPlxQZWyUjjTlJCWC
+AFdMjeHuxxVDP6ZSEEtm+jrumYgT35PJzMXhih8ANJ1toEAwPgYJKoZIhvcNAQkO
+MTEwLzAtBgNVHREEJjAkgg93d3cuZXhhbXBsZS5jb22CC2V4YW1wbGUuY29thwTA
+AAIBMAoGCCqGSM49BAMCA0kAMEYCIQCBeTuZvi5HbdL8Crbfg6ffdAYq3vRCA4Kf
+xEsu0XM4sQIhAMmIAZsCVJZnfdgIV1cwOlhihlRXRvwshBRvSJEuDP0g
+-----END CERTIFICATE REQUEST-----
+";
+
+    fn find<'a>(nodes: &'a [Asn1Node], value: &str) -> Option<&'a Asn1Node> {
+        nodes.iter().find_map(|node| {
+            (node.value == value)
+                .then_some(node)
+                .or_else(|| find(&node.children, value))
+        })
+    }
+
+    #[test]
+    fn parses_generated_rsa_keys() {
+        let (public_pem, private_pem) = generate_rsa_keypair(512).unwrap();
+        let mut viewer = Asn1Viewer {
+            input: format!("{}\n{}", public_pem, private_pem),
+            ..Default::default()
+        };
+        viewer.decode().unwrap();
+        assert_eq!(viewer.documents.len(), 2);
+        assert_eq!(viewer.documents[0].label.as_deref(), Some("PUBLIC KEY"));
+
+        for document in &viewer.documents {
+            let oid = find(&document.nodes, "1.2.840.113549.1.1.1 (rsaEncryption)");
+            assert!(oid.is_some());
+        }
+
+        // The public key BIT STRING wraps the RSAPublicKey sequence
+        let bit_string = &viewer.documents[0].nodes[0].children[1];
+        assert!(bit_string.encapsulated);
+        let rsa_public_key = &bit_string.children[0];
+        assert!(rsa_public_key.children[0].value.starts_with("512 bits: "));
+        assert_eq!(rsa_public_key.children[1].value, "65537");
+    }
+
+    #[test]
+    fn parses_csr_with_extensions() {
+        let documents = decode_pem(CSR).unwrap();
+        let nodes = parse_der(&documents[0].der).unwrap();
+        assert_eq!(nodes[0].tag.to_string(), "SEQUENCE");
+        assert!(find(&nodes, "2.5.4.3 (commonName)").is_some());
+        assert!(find(&nodes, "\"www.example.com\"").is_some());
+        assert!(find(&nodes, "1.2.840.113549.1.9.14 (extensionRequest)").is_some());
+        assert!(find(&nodes, "1.2.840.10045.4.3.2 (ecdsaWithSHA256)").is_some());
+
+        let request_info = &nodes[0].children[0];
+        assert_eq!(request_info.children[3].tag.to_string(), "[0]");
+        assert_eq!(request_info.offset, 4);
+        assert_eq!(request_info.header_length, 3);
+    }
+
+    #[test]
+    fn reports_malformed_element_offsets() {
+        let error = |input: &str| {
+            parse_der(&hex::decode(input).unwrap())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("3006020101020501"),
+            "Length 5 of INTEGER at byte 5 exceeds the remaining 1 bytes"
+        );
+        assert_eq!(
+            error("30030101"),
+            "Length 3 of SEQUENCE at byte 0 exceeds the remaining 2 bytes"
+        );
+        assert_eq!(
+            error("300401020000"),
+            "Invalid BOOLEAN at byte 2: BOOLEAN must be 1 byte"
+        );
+        assert_eq!(
+            error("3080020100"),
+            "Missing end-of-contents for SEQUENCE at byte 0"
+        );
+        assert_eq!(
+            error("3003060188"),
+            "Invalid OBJECT IDENTIFIER at byte 2: truncated object identifier"
+        );
+    }
+
+    #[test]
+    fn decodes_indefinite_lengths_and_primitives() {
+        // SEQUENCE (indefinite) { UTCTime, BMPString "hi", INTEGER -1 }
+        let bytes =
+            hex::decode("3080170d3234303130323033303430355a1e04006800690201ff0000").unwrap();
+        let nodes = parse_der(&bytes).unwrap();
+        assert!(nodes[0].indefinite);
+        let values: Vec<&str> = nodes[0].children.iter().map(|n| n.value.as_str()).collect();
+        assert_eq!(values, ["2024-01-02 03:04:05 UTC", "\"hi\"", "-1"]);
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine};
+
+/// A decoded `-----BEGIN <label>-----` block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PemBlock {
+    pub label: String,
+    pub der: Vec<u8>,
+}
+
+pub fn is_pem(input: &str) -> bool {
+    input.contains("-----BEGIN ")
+}
+
+/// Decodes every PEM block in `input`, ignoring text between blocks and RFC 1421 headers
+/// such as `Proc-Type`.
+pub fn decode_pem(input: &str) -> Result<Vec<PemBlock>> {
+    let mut blocks = Vec::new();
+    let mut lines = input.lines().enumerate();
+
+    while let Some((_, line)) = lines.next() {
+        let Some(label) = boundary_label(line, "BEGIN") else {
+            continue;
+        };
+
+        let mut body = String::new();
+        let mut closed = false;
+        for (number, line) in lines.by_ref() {
+            if let Some(end) = boundary_label(line, "END") {
+                if end != label {
+                    return Err(anyhow!(
+                        "Line {}: END {} does not match BEGIN {}",
+                        number + 1,
+                        end,
+                        label
+                    ));
+                }
+                closed = true;
+                break;
+            }
+            if !line.contains(':') {
+                body.push_str(line.trim());
+            }
+        }
+        if !closed {
+            return Err(anyhow!("PEM block {} is missing its END line", label));
+        }
+
+        let der = general_purpose::STANDARD
+            .decode(&body)
+            .map_err(|e| anyhow!("Invalid Base64 in PEM block {}: {}", label, e))?;
+        blocks.push(PemBlock {
+            label: label.to_string(),
+            der,
+        });
+    }
+
+    if blocks.is_empty() {
+        return Err(anyhow!("No PEM blocks found"));
+    }
+    Ok(blocks)
+}
+
+fn boundary_label<'a>(line: &'a str, kind: &str) -> Option<&'a str> {
+    line.trim()
+        .strip_prefix("-----")?
+        .strip_prefix(kind)?
+        .strip_prefix(' ')?
+        .strip_suffix("-----")
+}
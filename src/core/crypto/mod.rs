@@ -1,5 +1,6 @@
 pub mod asymmetric;
 pub mod symmetric;
+pub mod x509;
 
 use anyhow::{anyhow, Result};
 use asymmetric::{
@@ -0,0 +1,594 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt, fs,
+    net::{Ipv4Addr, Ipv6Addr},
+    path::Path,
+};
+use x509_parser::{
+    certificate::X509Certificate,
+    extensions::{DistributionPointName, GeneralName, ParsedExtension},
+    oid_registry::Oid,
+    prelude::FromDer,
+    public_key::PublicKey,
+};
+
+use crate::core::{
+    asn1::{
+        oid::oid_name,
+        pem::{decode_pem, is_pem},
+    },
+    encoding::input::BinaryInput,
+};
+
+const CERTIFICATE_LABEL: &str = "CERTIFICATE";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionInfo {
+    pub name: String,
+    pub critical: bool,
+    pub value: String,
+}
+
+/// An owned summary of a parsed certificate. The DER is kept for signature checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    pub version: u32,
+    pub serial: String,
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    pub signature_algorithm: String,
+    pub key_algorithm: String,
+    pub key_size: usize,
+    pub subject_alt_names: Vec<String>,
+    pub extensions: Vec<ExtensionInfo>,
+    pub is_ca: bool,
+    pub sha1_fingerprint: String,
+    pub sha256_fingerprint: String,
+    subject_raw: Vec<u8>,
+    issuer_raw: Vec<u8>,
+    der: Vec<u8>,
+}
+
+impl CertificateInfo {
+    pub fn from_der(der: &[u8]) -> Result<Self> {
+        let (rest, cert) =
+            X509Certificate::from_der(der).map_err(|e| anyhow!("Invalid certificate: {}", e))?;
+        if !rest.is_empty() {
+            return Err(anyhow!(
+                "Unexpected {} trailing bytes after the certificate",
+                rest.len()
+            ));
+        }
+
+        let spki = cert.public_key();
+        let mut key_algorithm = oid_label(&spki.algorithm.algorithm);
+        if let Some(curve) = spki
+            .algorithm
+            .parameters
+            .as_ref()
+            .and_then(|params| params.as_oid().ok())
+        {
+            key_algorithm = format!("{} ({})", key_algorithm, oid_label(&curve));
+        }
+        let key_size = match spki.parsed() {
+            Ok(PublicKey::RSA(rsa)) => significant_bits(rsa.modulus),
+            Ok(key) => key.key_size(),
+            Err(_) => 0,
+        };
+
+        let mut subject_alt_names = Vec::new();
+        let extensions = cert
+            .extensions()
+            .iter()
+            .map(|extension| {
+                if let ParsedExtension::SubjectAlternativeName(san) = extension.parsed_extension() {
+                    subject_alt_names = san.general_names.iter().map(general_name).collect();
+                }
+                ExtensionInfo {
+                    name: oid_label(&extension.oid),
+                    critical: extension.critical,
+                    value: describe_extension(extension.parsed_extension(), extension.value),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            version: cert.version().0 + 1,
+            serial: colon_hex(cert.raw_serial()),
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            not_before: to_datetime(cert.validity().not_before.timestamp())?,
+            not_after: to_datetime(cert.validity().not_after.timestamp())?,
+            signature_algorithm: oid_label(&cert.signature_algorithm.algorithm),
+            key_algorithm,
+            key_size,
+            subject_alt_names,
+            extensions,
+            is_ca: cert.is_ca(),
+            sha1_fingerprint: colon_hex(&Sha1::digest(der)),
+            sha256_fingerprint: colon_hex(&Sha256::digest(der)),
+            subject_raw: cert.subject().as_raw().to_vec(),
+            issuer_raw: cert.issuer().as_raw().to_vec(),
+            der: der.to_vec(),
+        })
+    }
+
+    pub fn is_self_issued(&self) -> bool {
+        self.subject_raw == self.issuer_raw
+    }
+
+    /// Describes the validity period relative to `now`, e.g. "Valid, expires in 89 days".
+    pub fn validity_status(&self, now: DateTime<Utc>) -> String {
+        if now < self.not_before {
+            format!(
+                "Not yet valid, starts in {}",
+                format_duration(self.not_before - now)
+            )
+        } else if now > self.not_after {
+            format!("Expired {} ago", format_duration(now - self.not_after))
+        } else {
+            format!(
+                "Valid, expires in {}",
+                format_duration(self.not_after - now)
+            )
+        }
+    }
+
+    /// Checks that this certificate was signed by `issuer`.
+    pub fn verify_issued_by(&self, issuer: &CertificateInfo) -> Result<()> {
+        if self.issuer_raw != issuer.subject_raw {
+            return Err(anyhow!(
+                "Issuer name does not match the subject of {}",
+                issuer.subject
+            ));
+        }
+        let (_, cert) = X509Certificate::from_der(&self.der)
+            .map_err(|e| anyhow!("Invalid certificate: {}", e))?;
+        let (_, issuer_cert) = X509Certificate::from_der(&issuer.der)
+            .map_err(|e| anyhow!("Invalid certificate: {}", e))?;
+        cert.verify_signature(Some(issuer_cert.public_key()))
+            .map_err(|e| anyhow!("Signature verification failed: {}", e))?;
+        if !issuer.is_ca {
+            return Err(anyhow!("{} is not a CA certificate", issuer.subject));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkStatus {
+    Verified,
+    SelfSigned,
+    Failed(String),
+    IssuerMissing,
+}
+
+impl fmt::Display for LinkStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkStatus::Verified => write!(f, "Signed by issuer"),
+            LinkStatus::SelfSigned => write!(f, "Self-signed root"),
+            LinkStatus::Failed(reason) => write!(f, "{}", reason),
+            LinkStatus::IssuerMissing => write!(f, "Issuer not found"),
+        }
+    }
+}
+
+impl LinkStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, LinkStatus::Verified | LinkStatus::SelfSigned)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainLink {
+    pub subject: String,
+    pub issuer: String,
+    pub sha256_fingerprint: String,
+    pub status: LinkStatus,
+}
+
+/// Links each certificate to its issuer, trying `root` before the rest of the bundle. The chain
+/// ends at `root` itself or, failing that, at the first self-issued certificate.
+pub fn verify_chain(
+    certificates: &[CertificateInfo],
+    root: Option<&CertificateInfo>,
+) -> Vec<ChainLink> {
+    let mut links = Vec::new();
+    let mut current = certificates.first();
+
+    while let Some(cert) = current {
+        if links.len() > certificates.len() {
+            break;
+        }
+        let link = |status| ChainLink {
+            subject: cert.subject.clone(),
+            issuer: cert.issuer.clone(),
+            sha256_fingerprint: cert.sha256_fingerprint.clone(),
+            status,
+        };
+
+        let is_root = root.is_some_and(|root| root.der == cert.der);
+        let root_issuer = root.filter(|root| !is_root && root.subject_raw == cert.issuer_raw);
+        if is_root || (cert.is_self_issued() && root_issuer.is_none()) {
+            let status = match cert.verify_issued_by(cert) {
+                Ok(()) => LinkStatus::SelfSigned,
+                Err(e) => LinkStatus::Failed(e.to_string()),
+            };
+            links.push(link(status));
+            break;
+        }
+
+        let issuer = root_issuer.or_else(|| {
+            certificates
+                .iter()
+                .find(|candidate| candidate.subject_raw == cert.issuer_raw && *candidate != cert)
+        });
+        match issuer {
+            Some(issuer) => {
+                let status = match cert.verify_issued_by(issuer) {
+                    Ok(()) => LinkStatus::Verified,
+                    Err(e) => LinkStatus::Failed(e.to_string()),
+                };
+                links.push(link(status));
+                current = Some(issuer);
+            }
+            None => {
+                links.push(link(LinkStatus::IssuerMissing));
+                break;
+            }
+        }
+    }
+
+    links
+}
+
+fn describe_extension(extension: &ParsedExtension<'_>, raw: &[u8]) -> String {
+    match extension {
+        ParsedExtension::KeyUsage(usage) => usage.to_string(),
+        ParsedExtension::ExtendedKeyUsage(eku) => {
+            let flags = [
+                (eku.any, "anyExtendedKeyUsage"),
+                (eku.server_auth, "serverAuth"),
+                (eku.client_auth, "clientAuth"),
+                (eku.code_signing, "codeSigning"),
+                (eku.email_protection, "emailProtection"),
+                (eku.time_stamping, "timeStamping"),
+                (eku.ocsp_signing, "OCSPSigning"),
+            ];
+            flags
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, name)| name.to_string())
+                .chain(eku.other.iter().map(oid_label))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        ParsedExtension::BasicConstraints(constraints) => match constraints.path_len_constraint {
+            Some(len) => format!("CA: {}, path length: {}", constraints.ca, len),
+            None => format!("CA: {}", constraints.ca),
+        },
+        ParsedExtension::SubjectAlternativeName(san) => join_names(&san.general_names),
+        ParsedExtension::IssuerAlternativeName(ian) => join_names(&ian.general_names),
+        ParsedExtension::AuthorityInfoAccess(aia) => aia
+            .accessdescs
+            .iter()
+            .map(|desc| {
+                format!(
+                    "{}: {}",
+                    oid_label(&desc.access_method),
+                    general_name(&desc.access_location)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        ParsedExtension::SubjectKeyIdentifier(id) => colon_hex(id.0),
+        ParsedExtension::AuthorityKeyIdentifier(aki) => aki
+            .key_identifier
+            .as_ref()
+            .map(|id| colon_hex(id.0))
+            .unwrap_or_default(),
+        ParsedExtension::CertificatePolicies(policies) => policies
+            .iter()
+            .map(|policy| oid_label(&policy.policy_id))
+            .collect::<Vec<_>>()
+            .join(", "),
+        ParsedExtension::CRLDistributionPoints(points) => points
+            .points
+            .iter()
+            .filter_map(|point| point.distribution_point.as_ref())
+            .map(|name| match name {
+                DistributionPointName::FullName(names) => join_names(names),
+                DistributionPointName::NameRelativeToCRLIssuer(_) => {
+                    "name relative to CRL issuer".to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        ParsedExtension::SCT(timestamps) => timestamps
+            .iter()
+            .map(|sct| {
+                let time = DateTime::from_timestamp_millis(sct.timestamp as i64)
+                    .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                    .unwrap_or_default();
+                format!("log {} at {}", hex::encode(&sct.id.key_id[..8]), time)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => hex::encode(raw),
+    }
+}
+
+fn join_names(names: &[GeneralName<'_>]) -> String {
+    names
+        .iter()
+        .map(general_name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn general_name(name: &GeneralName<'_>) -> String {
+    match name {
+        GeneralName::DNSName(dns) => format!("DNS:{}", dns),
+        GeneralName::RFC822Name(email) => format!("email:{}", email),
+        GeneralName::URI(uri) => format!("URI:{}", uri),
+        GeneralName::IPAddress(bytes) => {
+            if let Ok(octets) = <[u8; 4]>::try_from(*bytes) {
+                format!("IP:{}", Ipv4Addr::from(octets))
+            } else if let Ok(octets) = <[u8; 16]>::try_from(*bytes) {
+                format!("IP:{}", Ipv6Addr::from(octets))
+            } else {
+                format!("IP:{}", hex::encode(bytes))
+            }
+        }
+        GeneralName::DirectoryName(dn) => format!("DirName:{}", dn),
+        other => other.to_string(),
+    }
+}
+
+fn oid_label(oid: &Oid<'_>) -> String {
+    let dotted = oid.to_id_string();
+    match oid_name(&dotted) {
+        Some(name) => name.to_string(),
+        None => dotted,
+    }
+}
+
+fn significant_bits(bytes: &[u8]) -> usize {
+    match bytes.iter().position(|&b| b != 0) {
+        Some(start) => (bytes.len() - start) * 8 - bytes[start].leading_zeros() as usize,
+        None => 0,
+    }
+}
+
+fn colon_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn to_datetime(timestamp: i64) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp(timestamp, 0).ok_or_else(|| anyhow!("Invalid certificate time"))
+}
+
+fn format_duration(duration: chrono::Duration) -> String {
+    let days = duration.num_days();
+    if days >= 1 {
+        format!("{} days", days)
+    } else {
+        format!("{} hours", duration.num_hours())
+    }
+}
+
+/// Parses a PEM bundle, or a single DER certificate in hex or Base64.
+pub fn parse_certificates(input: &str, input_format: BinaryInput) -> Result<Vec<CertificateInfo>> {
+    if is_pem(input) {
+        let certificates = decode_pem(input)?
+            .into_iter()
+            .filter(|block| block.label == CERTIFICATE_LABEL)
+            .enumerate()
+            .map(|(index, block)| {
+                CertificateInfo::from_der(&block.der)
+                    .map_err(|e| anyhow!("Certificate {}: {}", index + 1, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if certificates.is_empty() {
+            return Err(anyhow!("No CERTIFICATE blocks found"));
+        }
+        return Ok(certificates);
+    }
+
+    let der = input_format.decode(input)?;
+    Ok(vec![CertificateInfo::from_der(&der)?])
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CertificateInspector {
+    pub input: String,
+    pub input_format: BinaryInput,
+    /// Trusted root used to terminate the chain
+    pub root_input: String,
+    pub certificates: Vec<CertificateInfo>,
+    pub root: Option<CertificateInfo>,
+    pub chain: Vec<ChainLink>,
+    pub error: Option<String>,
+}
+
+impl CertificateInspector {
+    pub fn inspect(&mut self) -> Result<()> {
+        self.certificates.clear();
+        self.root = None;
+        self.chain.clear();
+
+        let result = self.parse_inputs();
+        match result {
+            Ok((certificates, root)) => {
+                if certificates.len() > 1 || root.is_some() {
+                    self.chain = verify_chain(&certificates, root.as_ref());
+                }
+                self.certificates = certificates;
+                self.root = root;
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    fn parse_inputs(&self) -> Result<(Vec<CertificateInfo>, Option<CertificateInfo>)> {
+        let certificates = parse_certificates(&self.input, self.input_format)?;
+        let root = if self.root_input.trim().is_empty() {
+            None
+        } else {
+            let mut roots = parse_certificates(&self.root_input, self.input_format)
+                .map_err(|e| anyhow!("Root: {}", e))?;
+            Some(roots.remove(0))
+        };
+        Ok((certificates, root))
+    }
+
+    /// Whether the chain links all the way up to the provided root.
+    pub fn chain_reaches_root(&self) -> bool {
+        let Some(root) = &self.root else {
+            return false;
+        };
+        self.chain.iter().all(|link| link.status.is_ok())
+            && self
+                .chain
+                .last()
+                .is_some_and(|link| link.sha256_fingerprint == root.sha256_fingerprint)
+    }
+
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        match String::from_utf8(bytes) {
+            Ok(text) if is_pem(&text) => self.input = text,
+            Ok(text) => {
+                self.input = hex::encode(text.as_bytes());
+                self.input_format = BinaryInput::Hex;
+            }
+            Err(e) => {
+                self.input = hex::encode(e.as_bytes());
+                self.input_format = BinaryInput::Hex;
+            }
+        }
+        self.inspect()
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.root_input.clear();
+        self.certificates.clear();
+        self.root = None;
+        self.chain.clear();
+        self.error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT: &str = include_str!("testdata/root.pem");
+    const INTERMEDIATE: &str = include_str!("testdata/int.pem");
+    const LEAF: &str = include_str!("testdata/leaf.pem");
+    const OTHER_ROOT: &str = include_str!("testdata/other_root.pem");
+
+    fn certificate(pem: &str) -> CertificateInfo {
+        parse_certificates(pem, BinaryInput::Auto)
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn summarizes_leaf_certificate() {
+        let leaf = certificate(LEAF);
+        assert_eq!(leaf.version, 3);
+        assert_eq!(leaf.serial, "12:34");
+        assert_eq!(leaf.subject, "CN=www.example.com");
+        assert_eq!(leaf.issuer, "C=US, O=Example, CN=Example Intermediate CA");
+        assert_eq!(leaf.key_algorithm, "ecPublicKey (prime256v1)");
+        assert_eq!(leaf.key_size, 256);
+        assert_eq!(leaf.signature_algorithm, "ecdsaWithSHA256");
+        assert_eq!(
+            leaf.subject_alt_names,
+            ["DNS:www.example.com", "DNS:example.com", "IP:192.0.2.1"]
+        );
+        assert_eq!(
+            leaf.sha256_fingerprint,
+            "74:3B:1E:8A:01:35:2F:A5:E6:F5:E3:98:85:56:F3:CB:6D:C3:4C:93:47:44:51:1F:4B:6E:97:0E:E7:AA:0E:0A"
+        );
+        assert!(!leaf.is_ca);
+
+        let extension = |name: &str| leaf.extensions.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(extension("extKeyUsage").value, "serverAuth, clientAuth");
+        assert_eq!(extension("basicConstraints").value, "CA: false");
+        assert!(extension("keyUsage").critical);
+        assert_eq!(
+            extension("authorityInfoAccess").value,
+            "ocsp: URI:http://ocsp.example.com, caIssuers: URI:http://ca.example.com/int.pem"
+        );
+    }
+
+    #[test]
+    fn reports_time_remaining() {
+        let leaf = certificate(LEAF);
+        let now = leaf.not_after - chrono::Duration::days(30);
+        assert_eq!(leaf.validity_status(now), "Valid, expires in 30 days");
+        let now = leaf.not_after + chrono::Duration::hours(5);
+        assert_eq!(leaf.validity_status(now), "Expired 5 hours ago");
+    }
+
+    #[test]
+    fn verifies_chain_up_to_root() {
+        let mut inspector = CertificateInspector {
+            input: format!("{}{}", LEAF, INTERMEDIATE),
+            root_input: ROOT.to_string(),
+            ..Default::default()
+        };
+        inspector.inspect().unwrap();
+        let statuses: Vec<_> = inspector.chain.iter().map(|l| l.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            [
+                LinkStatus::Verified,
+                LinkStatus::Verified,
+                LinkStatus::SelfSigned
+            ]
+        );
+        assert!(inspector.chain_reaches_root());
+
+        // Same root name, different key
+        inspector.root_input = OTHER_ROOT.to_string();
+        inspector.inspect().unwrap();
+        assert!(matches!(inspector.chain[1].status, LinkStatus::Failed(_)));
+        assert!(!inspector.chain_reaches_root());
+
+        // A self-signed certificate with the root's name but its own key
+        inspector.input = OTHER_ROOT.to_string();
+        inspector.root_input = ROOT.to_string();
+        inspector.inspect().unwrap();
+        assert!(matches!(inspector.chain[0].status, LinkStatus::Failed(_)));
+        assert!(!inspector.chain_reaches_root());
+
+        inspector.input = LEAF.to_string();
+        inspector.root_input.clear();
+        inspector.inspect().unwrap();
+        assert!(inspector.chain.is_empty());
+        assert_eq!(
+            verify_chain(&inspector.certificates, None)[0].status,
+            LinkStatus::IssuerMissing
+        );
+    }
+}
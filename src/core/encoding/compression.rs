@@ -0,0 +1,214 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW: u32 = 22;
+const ZSTD_LEVEL: i32 = 3;
+/// Upper bound on decompressed output, so a few KB of input can't expand into gigabytes.
+const MAX_DECOMPRESSED_LEN: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    Gzip,
+    Zlib,
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::Gzip => write!(f, "Gzip"),
+            Compression::Zlib => write!(f, "Zlib"),
+            Compression::Deflate => write!(f, "Raw Deflate"),
+            Compression::Brotli => write!(f, "Brotli"),
+            Compression::Zstd => write!(f, "Zstd"),
+        }
+    }
+}
+
+impl Compression {
+    pub fn variants() -> &'static [Compression] {
+        &[
+            Compression::Gzip,
+            Compression::Zlib,
+            Compression::Deflate,
+            Compression::Brotli,
+            Compression::Zstd,
+        ]
+    }
+
+    pub fn compress(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let level = flate2::Compression::default();
+        let compressed = match self {
+            Compression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), level);
+                encoder.write_all(input)?;
+                encoder.finish()?
+            }
+            Compression::Zlib => {
+                let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), level);
+                encoder.write_all(input)?;
+                encoder.finish()?
+            }
+            Compression::Deflate => {
+                let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), level);
+                encoder.write_all(input)?;
+                encoder.finish()?
+            }
+            Compression::Brotli => {
+                let mut output = Vec::new();
+                {
+                    let mut encoder = brotli::CompressorWriter::new(
+                        &mut output,
+                        BROTLI_BUFFER_SIZE,
+                        BROTLI_QUALITY,
+                        BROTLI_WINDOW,
+                    );
+                    encoder.write_all(input)?;
+                }
+                output
+            }
+            Compression::Zstd => zstd::encode_all(input, ZSTD_LEVEL)?,
+        };
+
+        Ok(compressed)
+    }
+
+    pub fn decompress(&self, input: &[u8]) -> Result<Vec<u8>> {
+        fn read_capped(reader: impl Read, output: &mut Vec<u8>) -> std::io::Result<usize> {
+            reader.take(MAX_DECOMPRESSED_LEN + 1).read_to_end(output)
+        }
+
+        let mut output = Vec::new();
+        let result = match self {
+            Compression::Gzip => read_capped(flate2::read::MultiGzDecoder::new(input), &mut output),
+            Compression::Zlib => read_capped(flate2::read::ZlibDecoder::new(input), &mut output),
+            Compression::Deflate => {
+                read_capped(flate2::read::DeflateDecoder::new(input), &mut output)
+            }
+            Compression::Brotli => read_capped(
+                brotli::Decompressor::new(input, BROTLI_BUFFER_SIZE),
+                &mut output,
+            ),
+            Compression::Zstd => zstd::stream::read::Decoder::new(input)
+                .and_then(|decoder| read_capped(decoder, &mut output)),
+        };
+
+        result.map_err(|e| anyhow!("{} decompression failed: {}", self, e))?;
+        if output.len() as u64 > MAX_DECOMPRESSED_LEN {
+            return Err(anyhow!(
+                "{} output is larger than {} MiB",
+                self,
+                MAX_DECOMPRESSED_LEN / (1024 * 1024)
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Detects the compression format from the leading magic bytes. Raw deflate and brotli
+    /// streams have no header and are never detected here.
+    pub fn detect(input: &[u8]) -> Option<Compression> {
+        if input.starts_with(&GZIP_MAGIC) {
+            return Some(Compression::Gzip);
+        }
+        if input.starts_with(&ZSTD_MAGIC) {
+            return Some(Compression::Zstd);
+        }
+        if let [cmf, flg, ..] = input {
+            // CM = 8 (deflate), CINFO <= 7 and the header checksum must be a multiple of 31
+            if cmf & 0x0f == 8
+                && cmf >> 4 <= 7
+                && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0
+            {
+                return Some(Compression::Zlib);
+            }
+        }
+        None
+    }
+
+    /// Decompresses `input` using the format detected from its magic bytes. When no header is
+    /// found and the input is binary, raw deflate and then brotli are attempted. Returns the
+    /// format that succeeded.
+    pub fn decompress_detected(input: &[u8]) -> Result<(Compression, Vec<u8>)> {
+        if let Some(compression) = Self::detect(input) {
+            return compression
+                .decompress(input)
+                .map(|output| (compression, output));
+        }
+
+        if std::str::from_utf8(input).is_ok() {
+            return Err(anyhow!("Input is plain text, not compressed data"));
+        }
+
+        for compression in [Compression::Deflate, Compression::Brotli] {
+            if let Ok(output) = compression.decompress(input) {
+                if !output.is_empty() {
+                    return Ok((compression, output));
+                }
+            }
+        }
+
+        Err(anyhow!("Unable to detect compression format"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &[u8] = b"<samlp:AuthnRequest ID=\"_1\" Version=\"2.0\"></samlp:AuthnRequest>";
+
+    #[test]
+    fn round_trip_all_formats() {
+        for compression in Compression::variants() {
+            let compressed = compression.compress(SAMPLE).unwrap();
+            assert_eq!(compression.decompress(&compressed).unwrap(), SAMPLE);
+        }
+    }
+
+    #[test]
+    fn detects_formats() {
+        for compression in Compression::variants() {
+            let compressed = compression.compress(SAMPLE).unwrap();
+            let (detected, output) = Compression::decompress_detected(&compressed).unwrap();
+            assert_eq!(&detected, compression);
+            assert_eq!(output, SAMPLE);
+        }
+    }
+
+    #[test]
+    fn plain_text_is_not_detected() {
+        assert!(Compression::detect(b"hello world").is_none());
+        assert!(Compression::decompress_detected(b"hello world").is_err());
+    }
+
+    #[test]
+    fn caps_decompressed_size() {
+        let chunk = vec![0u8; 1024 * 1024];
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        let mut zstd = zstd::stream::write::Encoder::new(Vec::new(), ZSTD_LEVEL).unwrap();
+        for _ in 0..MAX_DECOMPRESSED_LEN / chunk.len() as u64 {
+            gzip.write_all(&chunk).unwrap();
+            zstd.write_all(&chunk).unwrap();
+        }
+        gzip.write_all(&[0]).unwrap();
+        zstd.write_all(&[0]).unwrap();
+
+        for (compression, bomb) in [
+            (Compression::Gzip, gzip.finish().unwrap()),
+            (Compression::Zstd, zstd.finish().unwrap()),
+        ] {
+            assert!(bomb.len() < 1024 * 1024);
+            let err = compression.decompress(&bomb).unwrap_err();
+            assert!(err.to_string().contains("larger than"), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::{fmt, fs, path::Path};
+
+const DEFAULT_MIME: &str = "text/plain";
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Characters that must be escaped in a non-base64 data URI payload.
+const PAYLOAD_ESCAPE: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`');
+
+/// Magic byte signatures, checked in order. `None` bytes are wildcards.
+const SIGNATURES: &[(&[Option<u8>], &str)] = &[
+    (
+        &[Some(0x89), Some(b'P'), Some(b'N'), Some(b'G')],
+        "image/png",
+    ),
+    (&[Some(0xff), Some(0xd8), Some(0xff)], "image/jpeg"),
+    (
+        &[Some(b'G'), Some(b'I'), Some(b'F'), Some(b'8')],
+        "image/gif",
+    ),
+    (
+        &[
+            Some(b'R'),
+            Some(b'I'),
+            Some(b'F'),
+            Some(b'F'),
+            None,
+            None,
+            None,
+            None,
+            Some(b'W'),
+            Some(b'E'),
+            Some(b'B'),
+            Some(b'P'),
+        ],
+        "image/webp",
+    ),
+    (&[Some(b'B'), Some(b'M')], "image/bmp"),
+    (&[Some(0), Some(0), Some(1), Some(0)], "image/x-icon"),
+    (
+        &[Some(b'%'), Some(b'P'), Some(b'D'), Some(b'F')],
+        "application/pdf",
+    ),
+    (
+        &[Some(b'w'), Some(b'O'), Some(b'F'), Some(b'F')],
+        "font/woff",
+    ),
+    (
+        &[Some(b'w'), Some(b'O'), Some(b'F'), Some(b'2')],
+        "font/woff2",
+    ),
+    (&[Some(0), Some(1), Some(0), Some(0), Some(0)], "font/ttf"),
+    (
+        &[Some(b'O'), Some(b'T'), Some(b'T'), Some(b'O')],
+        "font/otf",
+    ),
+    (&[Some(0x1f), Some(0x8b)], "application/gzip"),
+    (
+        &[Some(b'P'), Some(b'K'), Some(3), Some(4)],
+        "application/zip",
+    ),
+    (
+        &[Some(0), Some(b'a'), Some(b's'), Some(b'm')],
+        "application/wasm",
+    ),
+];
+
+/// Detects the MIME type of `bytes` from magic numbers, falling back to sniffing SVG and plain
+/// text.
+pub fn detect_mime(bytes: &[u8]) -> &'static str {
+    let matched = SIGNATURES.iter().find(|(signature, _)| {
+        bytes.len() >= signature.len()
+            && signature
+                .iter()
+                .zip(bytes)
+                .all(|(expected, actual)| expected.is_none_or(|b| b == *actual))
+    });
+    if let Some((_, mime)) = matched {
+        return mime;
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => {
+            let head = text.trim_start();
+            if head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg")) {
+                "image/svg+xml"
+            } else {
+                DEFAULT_MIME
+            }
+        }
+        Err(_) => OCTET_STREAM,
+    }
+}
+
+/// A parsed `data:` URI (RFC 2397).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUri {
+    pub mime: String,
+    pub params: Vec<(String, String)>,
+    pub base64: bool,
+    pub payload: Vec<u8>,
+}
+
+impl fmt::Display for DataUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "data:{}", self.mime)?;
+        for (key, value) in &self.params {
+            write!(f, ";{}={}", key, value)?;
+        }
+        if self.base64 {
+            write!(
+                f,
+                ";base64,{}",
+                general_purpose::STANDARD.encode(&self.payload)
+            )
+        } else {
+            let text = String::from_utf8_lossy(&self.payload);
+            write!(f, ",{}", utf8_percent_encode(&text, PAYLOAD_ESCAPE))
+        }
+    }
+}
+
+impl DataUri {
+    /// Wraps raw bytes in a base64 data URI with the MIME type detected from the content.
+    pub fn from_bytes(payload: Vec<u8>) -> Self {
+        Self {
+            mime: detect_mime(&payload).to_string(),
+            params: Vec::new(),
+            base64: true,
+            payload,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let rest = input
+            .get(..5)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+            .map(|_| &input[5..])
+            .ok_or_else(|| anyhow!("Data URI must start with 'data:'"))?;
+        let (header, data) = rest
+            .split_once(',')
+            .ok_or_else(|| anyhow!("Data URI is missing the ',' before the payload"))?;
+
+        let mut segments = header.split(';');
+        let mime = segments
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let mime = if mime.is_empty() {
+            DEFAULT_MIME.to_string()
+        } else {
+            let valid = !mime.contains(char::is_whitespace)
+                && mime.split_once('/').is_some_and(|(kind, subtype)| {
+                    !kind.is_empty() && !subtype.is_empty() && !subtype.contains('/')
+                });
+            if !valid {
+                return Err(anyhow!("Invalid MIME type '{}'", mime));
+            }
+            mime
+        };
+
+        let mut params = Vec::new();
+        let mut base64 = false;
+        for segment in segments {
+            if segment.eq_ignore_ascii_case("base64") {
+                base64 = true;
+            } else if base64 {
+                return Err(anyhow!("';base64' must be the last parameter"));
+            } else {
+                let (key, value) = segment
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("Invalid parameter '{}'", segment))?;
+                params.push((key.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        let payload = if base64 {
+            let decoded: Vec<u8> = percent_decode_str(data).collect();
+            let cleaned: Vec<u8> = decoded
+                .into_iter()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect();
+            general_purpose::STANDARD
+                .decode(cleaned)
+                .map_err(|e| anyhow!("Invalid Base64 payload: {}", e))?
+        } else {
+            percent_decode_str(data).collect()
+        };
+
+        Ok(Self {
+            mime,
+            params,
+            base64,
+            payload,
+        })
+    }
+
+    /// MIME type detected from the payload, when it differs from the declared one.
+    pub fn mime_mismatch(&self) -> Option<&'static str> {
+        let detected = detect_mime(&self.payload);
+        let generic = detected == DEFAULT_MIME || detected == OCTET_STREAM;
+        (!generic && detected != self.mime).then_some(detected)
+    }
+
+    /// Whether the payload is a raster image the `image` crate can decode.
+    pub fn is_image(&self) -> bool {
+        self.mime.starts_with("image/") && self.mime != "image/svg+xml"
+    }
+
+    pub fn decode_image(&self) -> Result<image::RgbaImage> {
+        let image = image::load_from_memory(&self.payload)
+            .map_err(|e| anyhow!("Unable to decode {} payload: {}", self.mime, e))?;
+        Ok(image.to_rgba8())
+    }
+
+    /// File extension for saving the payload, derived from the MIME subtype.
+    pub fn extension(&self) -> &str {
+        match self.mime.as_str() {
+            "image/jpeg" => "jpg",
+            "image/svg+xml" => "svg",
+            "image/x-icon" => "ico",
+            "text/plain" => "txt",
+            OCTET_STREAM => "bin",
+            mime => mime.rsplit('/').next().unwrap_or("bin"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DataUriConverter {
+    pub uri: String,
+    pub parsed: Option<DataUri>,
+}
+
+impl DataUriConverter {
+    /// Reads a file and emits it as a base64 data URI.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        let data_uri = DataUri::from_bytes(bytes);
+        self.uri = data_uri.to_string();
+        self.parsed = Some(data_uri);
+        Ok(())
+    }
+
+    pub fn parse(&mut self) -> Result<()> {
+        self.parsed = None;
+        self.parsed = Some(DataUri::parse(&self.uri)?);
+        Ok(())
+    }
+
+    pub fn save_payload<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let data_uri = self
+            .parsed
+            .as_ref()
+            .ok_or_else(|| anyhow!("No data URI has been parsed"))?;
+        fs::write(path.as_ref(), &data_uri.payload)
+            .map_err(|e| anyhow!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    pub fn clear(&mut self) {
+        self.uri.clear();
+        self.parsed = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_1X1: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8z8DwHwAFBQIAX8jx0gAAAABJRU5ErkJggg==";
+
+    #[test]
+    fn detects_mime_from_magic_bytes() {
+        let png = general_purpose::STANDARD.decode(PNG_1X1).unwrap();
+        assert_eq!(detect_mime(&png), "image/png");
+        assert_eq!(detect_mime(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(detect_mime(b"wOF2rest"), "font/woff2");
+        assert_eq!(
+            detect_mime(b"<?xml version=\"1.0\"?><svg/>"),
+            "image/svg+xml"
+        );
+        assert_eq!(detect_mime(b"hello"), "text/plain");
+        assert_eq!(detect_mime(&[0xfe, 0xfe]), "application/octet-stream");
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("icon.png");
+        fs::write(&source, general_purpose::STANDARD.decode(PNG_1X1).unwrap()).unwrap();
+
+        let mut converter = DataUriConverter::default();
+        converter.load_file(&source).unwrap();
+        assert_eq!(converter.uri, format!("data:image/png;base64,{}", PNG_1X1));
+
+        converter.parse().unwrap();
+        let data_uri = converter.parsed.as_ref().unwrap();
+        assert!(data_uri.is_image());
+        assert_eq!(data_uri.decode_image().unwrap().dimensions(), (1, 1));
+
+        let target = dir.path().join("copy.png");
+        converter.save_payload(&target).unwrap();
+        assert_eq!(fs::read(target).unwrap(), fs::read(source).unwrap());
+    }
+
+    #[test]
+    fn parses_plain_and_base64_forms() {
+        let uri = DataUri::parse("data:,Hello%2C%20World%21").unwrap();
+        assert_eq!(uri.mime, "text/plain");
+        assert_eq!(uri.payload, b"Hello, World!");
+
+        let uri = DataUri::parse("data:text/html;charset=utf-8;base64,PGI+aGk8L2I+").unwrap();
+        assert_eq!(
+            uri.params,
+            vec![("charset".to_string(), "utf-8".to_string())]
+        );
+        assert_eq!(uri.payload, b"<b>hi</b>");
+        assert_eq!(
+            uri.to_string(),
+            "data:text/html;charset=utf-8;base64,PGI+aGk8L2I+"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_uris() {
+        let error = |input| DataUri::parse(input).unwrap_err().to_string();
+        assert_eq!(error("http://x"), "Data URI must start with 'data:'");
+        assert_eq!(
+            error("data:text/plain"),
+            "Data URI is missing the ',' before the payload"
+        );
+        assert_eq!(error("data:png;base64,AAAA"), "Invalid MIME type 'png'");
+        assert!(error("data:image/png;base64,***").starts_with("Invalid Base64 payload"));
+
+        let mismatched = DataUri::parse(&format!("data:image/jpeg;base64,{}", PNG_1X1)).unwrap();
+        assert_eq!(mismatched.mime_mismatch(), Some("image/png"));
+    }
+}
@@ -0,0 +1,906 @@
+//! Text escaping for markup, string literals and shell/CSV quoting.
+//!
+//! Escaping functions never fail. Unescaping functions report the byte offset of the first
+//! malformed sequence.
+
+use anyhow::{anyhow, bail, Result};
+use std::fmt::Write as _;
+
+/// Named HTML entities recognised when unescaping, besides numeric references.
+static HTML_ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("iexcl", '¡'),
+    ("cent", '¢'),
+    ("pound", '£'),
+    ("curren", '¤'),
+    ("yen", '¥'),
+    ("brvbar", '¦'),
+    ("sect", '§'),
+    ("uml", '¨'),
+    ("copy", '©'),
+    ("ordf", 'ª'),
+    ("laquo", '«'),
+    ("not", '¬'),
+    ("shy", '\u{ad}'),
+    ("reg", '®'),
+    ("macr", '¯'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("sup2", '²'),
+    ("sup3", '³'),
+    ("acute", '´'),
+    ("micro", 'µ'),
+    ("para", '¶'),
+    ("middot", '·'),
+    ("cedil", '¸'),
+    ("sup1", '¹'),
+    ("ordm", 'º'),
+    ("raquo", '»'),
+    ("frac14", '¼'),
+    ("frac12", '½'),
+    ("frac34", '¾'),
+    ("iquest", '¿'),
+    ("Agrave", 'À'),
+    ("Aacute", 'Á'),
+    ("Acirc", 'Â'),
+    ("Atilde", 'Ã'),
+    ("Auml", 'Ä'),
+    ("Aring", 'Å'),
+    ("AElig", 'Æ'),
+    ("Ccedil", 'Ç'),
+    ("Egrave", 'È'),
+    ("Eacute", 'É'),
+    ("Ecirc", 'Ê'),
+    ("Euml", 'Ë'),
+    ("Igrave", 'Ì'),
+    ("Iacute", 'Í'),
+    ("Icirc", 'Î'),
+    ("Iuml", 'Ï'),
+    ("ETH", 'Ð'),
+    ("Ntilde", 'Ñ'),
+    ("Ograve", 'Ò'),
+    ("Oacute", 'Ó'),
+    ("Ocirc", 'Ô'),
+    ("Otilde", 'Õ'),
+    ("Ouml", 'Ö'),
+    ("times", '×'),
+    ("Oslash", 'Ø'),
+    ("Ugrave", 'Ù'),
+    ("Uacute", 'Ú'),
+    ("Ucirc", 'Û'),
+    ("Uuml", 'Ü'),
+    ("Yacute", 'Ý'),
+    ("THORN", 'Þ'),
+    ("szlig", 'ß'),
+    ("agrave", 'à'),
+    ("aacute", 'á'),
+    ("acirc", 'â'),
+    ("atilde", 'ã'),
+    ("auml", 'ä'),
+    ("aring", 'å'),
+    ("aelig", 'æ'),
+    ("ccedil", 'ç'),
+    ("egrave", 'è'),
+    ("eacute", 'é'),
+    ("ecirc", 'ê'),
+    ("euml", 'ë'),
+    ("igrave", 'ì'),
+    ("iacute", 'í'),
+    ("icirc", 'î'),
+    ("iuml", 'ï'),
+    ("eth", 'ð'),
+    ("ntilde", 'ñ'),
+    ("ograve", 'ò'),
+    ("oacute", 'ó'),
+    ("ocirc", 'ô'),
+    ("otilde", 'õ'),
+    ("ouml", 'ö'),
+    ("divide", '÷'),
+    ("oslash", 'ø'),
+    ("ugrave", 'ù'),
+    ("uacute", 'ú'),
+    ("ucirc", 'û'),
+    ("uuml", 'ü'),
+    ("yacute", 'ý'),
+    ("thorn", 'þ'),
+    ("yuml", 'ÿ'),
+    ("OElig", 'Œ'),
+    ("oelig", 'œ'),
+    ("Scaron", 'Š'),
+    ("scaron", 'š'),
+    ("Yuml", 'Ÿ'),
+    ("fnof", 'ƒ'),
+    ("circ", 'ˆ'),
+    ("tilde", '˜'),
+    ("Alpha", 'Α'),
+    ("Beta", 'Β'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Omega", 'Ω'),
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ε'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("pi", 'π'),
+    ("sigma", 'σ'),
+    ("omega", 'ω'),
+    ("ensp", '\u{2002}'),
+    ("emsp", '\u{2003}'),
+    ("thinsp", '\u{2009}'),
+    ("zwnj", '\u{200c}'),
+    ("zwj", '\u{200d}'),
+    ("lrm", '\u{200e}'),
+    ("rlm", '\u{200f}'),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("sbquo", '‚'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("bdquo", '„'),
+    ("dagger", '†'),
+    ("Dagger", '‡'),
+    ("bull", '•'),
+    ("hellip", '…'),
+    ("permil", '‰'),
+    ("prime", '′'),
+    ("Prime", '″'),
+    ("lsaquo", '‹'),
+    ("rsaquo", '›'),
+    ("euro", '€'),
+    ("trade", '™'),
+    ("larr", '←'),
+    ("uarr", '↑'),
+    ("rarr", '→'),
+    ("darr", '↓'),
+    ("harr", '↔'),
+    ("forall", '∀'),
+    ("part", '∂'),
+    ("exist", '∃'),
+    ("empty", '∅'),
+    ("nabla", '∇'),
+    ("isin", '∈'),
+    ("notin", '∉'),
+    ("sum", '∑'),
+    ("minus", '−'),
+    ("radic", '√'),
+    ("infin", '∞'),
+    ("and", '∧'),
+    ("or", '∨'),
+    ("cap", '∩'),
+    ("cup", '∪'),
+    ("int", '∫'),
+    ("asymp", '≈'),
+    ("ne", '≠'),
+    ("equiv", '≡'),
+    ("le", '≤'),
+    ("ge", '≥'),
+    ("loz", '◊'),
+    ("spades", '♠'),
+    ("clubs", '♣'),
+    ("hearts", '♥'),
+    ("diams", '♦'),
+];
+
+/// Control characters other than whitespace become U+FFFD: HTML5 treats references to them as
+/// parse errors and remaps C1 references to Windows-1252.
+pub fn escape_html(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            '\n' | '\t' | '\r' => output.push(c),
+            c if c.is_control() => output.push(char::REPLACEMENT_CHARACTER),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// Like HTML5, keeps a bare `&` or an unknown entity such as `AT&T` as literal text.
+pub fn unescape_html(input: &str) -> Result<String> {
+    unescape_entities(input, false, |name| {
+        HTML_ENTITIES
+            .iter()
+            .find(|(entity, _)| *entity == name)
+            .map(|(_, c)| *c)
+    })
+}
+
+/// C0 control characters other than tab, line feed and carriage return are not allowed in
+/// XML 1.0, not even as references, so they become U+FFFD.
+pub fn escape_xml(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            '\n' | '\t' => output.push(c),
+            c if c.is_ascii_control() && c != '\r' && c != '\u{7f}' => {
+                output.push(char::REPLACEMENT_CHARACTER)
+            }
+            c if c.is_control() => {
+                let _ = write!(output, "&#x{:X};", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+pub fn unescape_xml(input: &str) -> Result<String> {
+    unescape_entities(input, true, |name| match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => None,
+    })
+}
+
+/// Replaces `&name;`, `&#123;` and `&#x7B;` references. When `strict`, unterminated and unknown
+/// entities are an error so that typos don't silently survive a round trip; otherwise they are
+/// kept as they are.
+fn unescape_entities(
+    input: &str,
+    strict: bool,
+    named: impl Fn(&str) -> Option<char>,
+) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(amp) = rest.find('&') {
+        output.push_str(&rest[..amp]);
+        let offset = input.len() - rest.len() + amp;
+        let after = &rest[amp + 1..];
+
+        match decode_entity(after, offset, &named) {
+            Ok((c, len)) => {
+                output.push(c);
+                rest = &after[len..];
+            }
+            Err(e) if strict => return Err(e),
+            Err(_) => {
+                output.push('&');
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Decodes the entity following a `&`, returning its character and length including the `;`.
+fn decode_entity(
+    after: &str,
+    offset: usize,
+    named: impl Fn(&str) -> Option<char>,
+) -> Result<(char, usize)> {
+    let semi = after
+        .find(';')
+        .filter(|&i| i <= 32)
+        .ok_or_else(|| anyhow!("Unterminated entity at byte {}", offset))?;
+    let entity = &after[..semi];
+
+    let c = if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => number.parse::<u32>(),
+        }
+        .map_err(|_| anyhow!("Invalid numeric entity '&{};' at byte {}", entity, offset))?;
+        char::from_u32(code)
+            .ok_or_else(|| anyhow!("Invalid code point '&{};' at byte {}", entity, offset))?
+    } else {
+        named(entity).ok_or_else(|| anyhow!("Unknown entity '&{};' at byte {}", entity, offset))?
+    };
+    Ok((c, semi + 1))
+}
+
+fn push_utf16_escape(output: &mut String, c: char) {
+    let mut units = [0u16; 2];
+    for unit in c.encode_utf16(&mut units) {
+        let _ = write!(output, "\\u{:04X}", unit);
+    }
+}
+
+pub fn escape_json(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\u{08}' => output.push_str("\\b"),
+            '\u{0c}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => push_utf16_escape(&mut output, c),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+pub fn unescape_json(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = Scanner::new(strip_quotes(input, '"'));
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let offset = chars.offset - 1;
+        match chars.next() {
+            Some('"') => output.push('"'),
+            Some('\\') => output.push('\\'),
+            Some('/') => output.push('/'),
+            Some('b') => output.push('\u{08}'),
+            Some('f') => output.push('\u{0c}'),
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some('t') => output.push('\t'),
+            Some('u') => output.push(chars.utf16_escape(offset)?),
+            _ => bail!("Invalid escape sequence at byte {}", offset),
+        }
+    }
+
+    Ok(output)
+}
+
+pub fn escape_javascript(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\'' => output.push_str("\\'"),
+            '`' => output.push_str("\\`"),
+            '\\' => output.push_str("\\\\"),
+            '\u{08}' => output.push_str("\\b"),
+            '\u{0b}' => output.push_str("\\v"),
+            '\u{0c}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            // Line terminators inside string literals are syntax errors before ES2019
+            '\u{2028}' | '\u{2029}' => push_utf16_escape(&mut output, c),
+            c if (c as u32) < 0x100 && c.is_control() => {
+                let _ = write!(output, "\\x{:02X}", c as u32);
+            }
+            c if c.is_control() => push_utf16_escape(&mut output, c),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+pub fn unescape_javascript(input: &str) -> Result<String> {
+    let quote = input
+        .chars()
+        .next()
+        .filter(|q| matches!(q, '"' | '\'' | '`'))
+        .unwrap_or('"');
+    let mut output = String::with_capacity(input.len());
+    let mut chars = Scanner::new(strip_quotes(input, quote));
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let offset = chars.offset - 1;
+        match chars.next() {
+            Some('b') => output.push('\u{08}'),
+            Some('f') => output.push('\u{0c}'),
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some('t') => output.push('\t'),
+            Some('v') => output.push('\u{0b}'),
+            Some('0') if !chars.peek().is_some_and(|c| c.is_ascii_digit()) => output.push('\0'),
+            Some('x') => output.push(chars.hex_escape(2, offset)?),
+            Some('u') if chars.peek() == Some('{') => output.push(chars.braced_escape(offset)?),
+            Some('u') => output.push(chars.utf16_escape(offset)?),
+            // Line continuation
+            Some('\n') => {}
+            Some('\r') => {
+                if chars.peek() == Some('\n') {
+                    chars.next();
+                }
+            }
+            Some(c) if !c.is_ascii_alphanumeric() => output.push(c),
+            _ => bail!("Invalid escape sequence at byte {}", offset),
+        }
+    }
+
+    Ok(output)
+}
+
+pub fn escape_rust(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\0' => output.push_str("\\0"),
+            c if c.is_control() => {
+                let _ = write!(output, "\\u{{{:x}}}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+pub fn unescape_rust(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = Scanner::new(strip_quotes(input, '"'));
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let offset = chars.offset - 1;
+        match chars.next() {
+            Some('"') => output.push('"'),
+            Some('\'') => output.push('\''),
+            Some('\\') => output.push('\\'),
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some('t') => output.push('\t'),
+            Some('0') => output.push('\0'),
+            Some('x') => {
+                let c = chars.hex_escape(2, offset)?;
+                if !c.is_ascii() {
+                    bail!("\\x escape above 0x7F at byte {}", offset);
+                }
+                output.push(c);
+            }
+            Some('u') if chars.peek() == Some('{') => output.push(chars.braced_escape(offset)?),
+            // Line continuation skips the newline and leading whitespace of the next line
+            Some('\n') => {
+                while chars.peek().is_some_and(char::is_whitespace) {
+                    chars.next();
+                }
+            }
+            _ => bail!("Invalid escape sequence at byte {}", offset),
+        }
+    }
+
+    Ok(output)
+}
+
+pub fn escape_c(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\u{07}' => output.push_str("\\a"),
+            '\u{08}' => output.push_str("\\b"),
+            '\u{0c}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{0b}' => output.push_str("\\v"),
+            // Octal escapes stop after three digits, unlike greedy \x escapes
+            c if c.is_ascii_control() => {
+                let _ = write!(output, "\\{:03o}", c as u32);
+            }
+            c if c.is_ascii() => output.push(c),
+            c if (c as u32) <= 0xffff => {
+                let _ = write!(output, "\\u{:04X}", c as u32);
+            }
+            c => {
+                let _ = write!(output, "\\U{:08X}", c as u32);
+            }
+        }
+    }
+    output
+}
+
+/// C escapes describe bytes (`\xff`, `\377`) as well as characters, so the result is raw bytes.
+pub fn unescape_c(input: &str) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut chars = Scanner::new(strip_quotes(input, '"'));
+    let mut buf = [0u8; 4];
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let offset = chars.offset - 1;
+        let byte = match chars.next() {
+            Some('a') => 0x07,
+            Some('b') => 0x08,
+            Some('f') => 0x0c,
+            Some('n') => b'\n',
+            Some('r') => b'\r',
+            Some('t') => b'\t',
+            Some('v') => 0x0b,
+            Some(c @ ('"' | '\'' | '\\' | '?')) => c as u8,
+            Some(d @ '0'..='7') => {
+                let mut value = d.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                u8::try_from(value)
+                    .map_err(|_| anyhow!("Octal escape out of range at byte {}", offset))?
+            }
+            Some('x') => {
+                let mut value = 0u32;
+                let mut digits = 0;
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) {
+                    value = value.saturating_mul(16).saturating_add(digit);
+                    digits += 1;
+                    chars.next();
+                }
+                if digits == 0 {
+                    bail!("\\x escape without digits at byte {}", offset);
+                }
+                u8::try_from(value)
+                    .map_err(|_| anyhow!("\\x escape out of range at byte {}", offset))?
+            }
+            Some('u') => {
+                let c = chars.hex_escape(4, offset)?;
+                output.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                continue;
+            }
+            Some('U') => {
+                let c = chars.hex_escape(8, offset)?;
+                output.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                continue;
+            }
+            _ => bail!("Invalid escape sequence at byte {}", offset),
+        };
+        output.push(byte);
+    }
+
+    Ok(output)
+}
+
+/// Escapes a `.properties` value. Non-Latin characters use `\uXXXX` so the output is valid in
+/// both ISO-8859-1 and UTF-8 encoded files.
+pub fn escape_java_properties(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for (i, c) in input.chars().enumerate() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{0c}' => output.push_str("\\f"),
+            '=' | ':' | '#' | '!' => {
+                output.push('\\');
+                output.push(c);
+            }
+            ' ' if i == 0 => output.push_str("\\ "),
+            c if c.is_control() || !c.is_ascii() => push_utf16_escape(&mut output, c),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+pub fn unescape_java_properties(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = Scanner::new(input);
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let offset = chars.offset - 1;
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some('t') => output.push('\t'),
+            Some('f') => output.push('\u{0c}'),
+            Some('u') => output.push(chars.utf16_escape(offset)?),
+            // Line continuation skips the line break and leading whitespace of the next line
+            Some('\n' | '\r') => {
+                while chars.peek().is_some_and(char::is_whitespace) {
+                    chars.next();
+                }
+            }
+            Some(c) => output.push(c),
+            None => bail!("Dangling backslash at byte {}", offset),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Quotes the input as a single POSIX shell word.
+pub fn escape_shell(input: &str) -> String {
+    format!("'{}'", input.replace('\'', "'\\''"))
+}
+
+/// Parses a single POSIX shell word made of single-quoted, double-quoted and bare segments.
+pub fn unescape_shell(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = Scanner::new(input);
+
+    while let Some(c) = chars.next() {
+        let offset = chars.offset - c.len_utf8();
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => output.push(c),
+                    None => bail!("Unterminated single quote at byte {}", offset),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('$' | '`' | '"' | '\\')) => output.push(c),
+                        Some('\n') => {}
+                        Some(c) => {
+                            output.push('\\');
+                            output.push(c);
+                        }
+                        None => bail!("Unterminated double quote at byte {}", offset),
+                    },
+                    Some(c) => output.push(c),
+                    None => bail!("Unterminated double quote at byte {}", offset),
+                }
+            },
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c) => output.push(c),
+                None => bail!("Dangling backslash at byte {}", offset),
+            },
+            c => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Quotes a CSV field (RFC 4180) when it contains a delimiter, quote, line break or
+/// surrounding whitespace.
+pub fn escape_csv(input: &str) -> String {
+    let needs_quotes = input.contains([',', '"', '\n', '\r'])
+        || input.starts_with(char::is_whitespace)
+        || input.ends_with(char::is_whitespace);
+
+    if needs_quotes {
+        format!("\"{}\"", input.replace('"', "\"\""))
+    } else {
+        input.to_string()
+    }
+}
+
+pub fn unescape_csv(input: &str) -> Result<String> {
+    let Some(inner) = input.strip_prefix('"') else {
+        return Ok(input.to_string());
+    };
+    let inner = inner
+        .strip_suffix('"')
+        .ok_or_else(|| anyhow!("Quoted field is missing its closing quote"))?;
+
+    let mut output = String::with_capacity(inner.len());
+    let mut chars = Scanner::new(inner);
+    while let Some(c) = chars.next() {
+        if c == '"' && chars.next() != Some('"') {
+            bail!("Unescaped quote at byte {}", chars.offset);
+        }
+        output.push(c);
+    }
+
+    Ok(output)
+}
+
+/// Removes one pair of surrounding quotes, unless the closing quote is itself escaped.
+fn strip_quotes(input: &str, quote: char) -> &str {
+    let Some(inner) = input
+        .strip_prefix(quote)
+        .and_then(|s| s.strip_suffix(quote))
+    else {
+        return input;
+    };
+    let trailing_backslashes = inner.chars().rev().take_while(|&c| c == '\\').count();
+    if trailing_backslashes % 2 == 0 {
+        inner
+    } else {
+        input
+    }
+}
+
+/// Character iterator that tracks the byte offset for error reporting.
+struct Scanner<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+            offset: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn hex_digits(&mut self, count: usize, offset: usize) -> Result<u32> {
+        let mut value = 0u32;
+        for _ in 0..count {
+            let digit = self
+                .next()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| anyhow!("Invalid hex escape at byte {}", offset))?;
+            value = value * 16 + digit;
+        }
+        Ok(value)
+    }
+
+    fn hex_escape(&mut self, count: usize, offset: usize) -> Result<char> {
+        let value = self.hex_digits(count, offset)?;
+        char::from_u32(value).ok_or_else(|| anyhow!("Invalid code point at byte {}", offset))
+    }
+
+    /// Parses `{1F600}` after a `\u`.
+    fn braced_escape(&mut self, offset: usize) -> Result<char> {
+        self.next();
+        let mut value = 0u32;
+        let mut digits = 0;
+        loop {
+            match self.next() {
+                Some('}') if digits > 0 => break,
+                Some('_') => {}
+                Some(c) if c.is_ascii_hexdigit() && digits < 6 => {
+                    value = value * 16 + c.to_digit(16).unwrap_or_default();
+                    digits += 1;
+                }
+                _ => bail!("Invalid \\u{{...}} escape at byte {}", offset),
+            }
+        }
+        char::from_u32(value).ok_or_else(|| anyhow!("Invalid code point at byte {}", offset))
+    }
+
+    /// Parses `XXXX` after a `\u`, combining a following `\uXXXX` low surrogate if needed.
+    fn utf16_escape(&mut self, offset: usize) -> Result<char> {
+        let high = self.hex_digits(4, offset)?;
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high)
+                .ok_or_else(|| anyhow!("Lone surrogate \\u{:04X} at byte {}", high, offset));
+        }
+        if self.next() != Some('\\') || self.next() != Some('u') {
+            bail!("High surrogate without low surrogate at byte {}", offset);
+        }
+        let low = self.hex_digits(4, offset)?;
+        if !(0xdc00..0xe000).contains(&low) {
+            bail!("Invalid low surrogate \\u{:04X} at byte {}", low, offset);
+        }
+        let code = 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00);
+        char::from_u32(code).ok_or_else(|| anyhow!("Invalid code point at byte {}", offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "a\"b'c\\d<e>&f\n\r\t\0\u{1}\u{7f}é€😀\u{2028}=:# x`";
+
+    #[test]
+    fn round_trips() {
+        let markup = SAMPLE.replace(['\0', '\u{1}', '\u{7f}'], "");
+        assert_eq!(unescape_html(&escape_html(&markup)).unwrap(), markup);
+        assert_eq!(unescape_xml(&escape_xml(&markup)).unwrap(), markup);
+        assert_eq!(unescape_json(&escape_json(SAMPLE)).unwrap(), SAMPLE);
+        assert_eq!(
+            unescape_javascript(&escape_javascript(SAMPLE)).unwrap(),
+            SAMPLE
+        );
+        assert_eq!(unescape_rust(&escape_rust(SAMPLE)).unwrap(), SAMPLE);
+        assert_eq!(unescape_c(&escape_c(SAMPLE)).unwrap(), SAMPLE.as_bytes());
+        assert_eq!(
+            unescape_java_properties(&escape_java_properties(SAMPLE)).unwrap(),
+            SAMPLE
+        );
+        assert_eq!(unescape_shell(&escape_shell(SAMPLE)).unwrap(), SAMPLE);
+        assert_eq!(unescape_csv(&escape_csv(SAMPLE)).unwrap(), SAMPLE);
+    }
+
+    #[test]
+    fn json_matches_serde() {
+        let escaped = escape_json(SAMPLE);
+        let parsed: String = serde_json::from_str(&format!("\"{}\"", escaped)).unwrap();
+        assert_eq!(parsed, SAMPLE);
+        assert_eq!(unescape_json("\"\\ud83d\\ude00\"").unwrap(), "😀");
+    }
+
+    #[test]
+    fn html_entities() {
+        assert_eq!(
+            unescape_html("&lt;p&gt;caf&eacute; &#233; &#xE9; &euro;").unwrap(),
+            "<p>café é é €"
+        );
+        assert_eq!(unescape_html("&bogus;").unwrap(), "&bogus;");
+    }
+
+    #[test]
+    fn markup_replaces_disallowed_controls() {
+        let input = "a\0b\u{1}c\r\t\n\u{7f}\u{85}";
+        assert_eq!(
+            escape_xml(input),
+            "a\u{fffd}b\u{fffd}c&#xD;\t\n&#x7F;&#x85;"
+        );
+        assert_eq!(
+            escape_html(input),
+            "a\u{fffd}b\u{fffd}c\r\t\n\u{fffd}\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn html_keeps_bare_ampersands() {
+        assert_eq!(unescape_html("AT&T").unwrap(), "AT&T");
+        assert_eq!(
+            unescape_html("href=\"?a=1&b=2&amp;c=3\"").unwrap(),
+            "href=\"?a=1&b=2&c=3\""
+        );
+        assert!(unescape_xml("AT&T").is_err());
+        assert!(unescape_xml("&bogus;").is_err());
+    }
+
+    #[test]
+    fn c_escapes_bytes() {
+        assert_eq!(unescape_c("\\xff\\0\\101").unwrap(), vec![0xff, 0, b'A']);
+        assert_eq!(escape_c("😀"), "\\U0001F600");
+    }
+
+    #[test]
+    fn shell_and_csv_quoting() {
+        assert_eq!(escape_shell("it's"), "'it'\\''s'");
+        assert_eq!(unescape_shell("\"a \\\"b\\\"\"' c'").unwrap(), "a \"b\" c");
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn reports_error_offset() {
+        let err = unescape_json("ab\\q").unwrap_err();
+        assert!(err.to_string().contains("byte 2"));
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine};
+use std::fmt;
+
+/// How pasted text is turned into bytes by the binary inspection tools.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryInput {
+    #[default]
+    Auto,
+    Hex,
+    Base64,
+}
+
+impl fmt::Display for BinaryInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryInput::Auto => write!(f, "Auto-detect"),
+            BinaryInput::Hex => write!(f, "Hex"),
+            BinaryInput::Base64 => write!(f, "Base64"),
+        }
+    }
+}
+
+impl BinaryInput {
+    pub fn variants() -> &'static [BinaryInput] {
+        &[BinaryInput::Auto, BinaryInput::Hex, BinaryInput::Base64]
+    }
+
+    /// Decodes `input` into bytes. Whitespace is ignored in both formats, hex may use `0x`
+    /// prefixes and `:` separators, and Base64 may be standard or URL-safe with or without
+    /// padding. `Auto` picks hex when the input only contains an even number of hex digits.
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>> {
+        let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.is_empty() {
+            return Err(anyhow!("Input is empty"));
+        }
+
+        match self {
+            BinaryInput::Hex => decode_hex(&compact),
+            BinaryInput::Base64 => decode_base64(&compact),
+            BinaryInput::Auto => {
+                let hex = strip_hex_decoration(&compact);
+                if hex.len().is_multiple_of(2) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    decode_hex(&compact)
+                } else {
+                    decode_base64(&compact)
+                }
+            }
+        }
+    }
+}
+
+fn strip_hex_decoration(input: &str) -> String {
+    input
+        .replace("0x", "")
+        .replace("0X", "")
+        .replace([':', ','], "")
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>> {
+    hex::decode(strip_hex_decoration(input)).map_err(|e| anyhow!("Invalid hex input: {}", e))
+}
+
+fn decode_base64(input: &str) -> Result<Vec<u8>> {
+    let trimmed = input.trim_end_matches('=');
+    let engine = if trimmed.contains(['-', '_']) {
+        &general_purpose::URL_SAFE_NO_PAD
+    } else {
+        &general_purpose::STANDARD_NO_PAD
+    };
+    engine
+        .decode(trimmed)
+        .map_err(|e| anyhow!("Invalid Base64 input: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_detects_hex_and_base64() {
+        assert_eq!(
+            BinaryInput::Auto.decode("08 96 01").unwrap(),
+            [0x08, 0x96, 0x01]
+        );
+        assert_eq!(BinaryInput::Auto.decode("0x08:0x96").unwrap(), [0x08, 0x96]);
+        assert_eq!(
+            BinaryInput::Auto.decode("CJYB").unwrap(),
+            [0x08, 0x96, 0x01]
+        );
+        assert_eq!(BinaryInput::Auto.decode("_-8").unwrap(), [0xff, 0xef]);
+        assert!(BinaryInput::Hex.decode("abc").is_err());
+        assert!(BinaryInput::Auto.decode("  ").is_err());
+    }
+}
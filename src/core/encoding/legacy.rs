@@ -0,0 +1,370 @@
+use anyhow::{anyhow, Result};
+
+const QP_LINE_LENGTH: usize = 76;
+const UU_LINE_BYTES: usize = 45;
+const YENC_LINE_LENGTH: usize = 128;
+
+/// Encodes text-mode quoted-printable (RFC 2045 section 6.7). Line breaks in the input are
+/// kept as hard CRLF breaks, long lines are wrapped with soft `=` breaks.
+pub fn encode_quoted_printable(input: &[u8]) -> String {
+    let mut output = String::new();
+    let mut line_len = 0;
+    let mut i = 0;
+
+    while i < input.len() {
+        let byte = input[i];
+        if byte == b'\n' || (byte == b'\r' && input.get(i + 1) == Some(&b'\n')) {
+            output.push_str("\r\n");
+            line_len = 0;
+            i += if byte == b'\r' { 2 } else { 1 };
+            continue;
+        }
+
+        // Whitespace is only literal when it is not the last character of a line
+        let at_line_end = matches!(input.get(i + 1), None | Some(b'\r') | Some(b'\n'));
+        let literal = match byte {
+            b'=' => false,
+            b' ' | b'\t' => !at_line_end,
+            33..=126 => true,
+            _ => false,
+        };
+        let token = if literal {
+            char::from(byte).to_string()
+        } else {
+            format!("={:02X}", byte)
+        };
+
+        // Leave room for the trailing `=` of a soft line break
+        if line_len + token.len() > QP_LINE_LENGTH - 1 {
+            output.push_str("=\r\n");
+            line_len = 0;
+        }
+        output.push_str(&token);
+        line_len += token.len();
+        i += 1;
+    }
+
+    output
+}
+
+pub fn decode_quoted_printable(input: &str) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let (content, line_break) = match line.strip_suffix('\n') {
+            Some(rest) => match rest.strip_suffix('\r') {
+                Some(rest) => (rest, "\r\n"),
+                None => (rest, "\n"),
+            },
+            None => (line, ""),
+        };
+        // Trailing whitespace may have been added in transport and is not part of the data
+        let content = content.trim_end_matches([' ', '\t']);
+
+        let (content, soft_break) = match content.strip_suffix('=') {
+            Some(rest) => (rest, true),
+            None => (content, false),
+        };
+
+        let bytes = content.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'=' {
+                let byte = content
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| {
+                        anyhow!("Invalid quoted-printable escape at byte {}", offset + i)
+                    })?;
+                output.push(byte);
+                i += 3;
+            } else {
+                output.push(bytes[i]);
+                i += 1;
+            }
+        }
+
+        if !soft_break {
+            output.extend_from_slice(line_break.as_bytes());
+        }
+        offset += line.len();
+    }
+
+    Ok(output)
+}
+
+/// Encodes `input` as a complete uuencoded file named `data`.
+pub fn uuencode(input: &[u8]) -> String {
+    let mut output = String::from("begin 644 data\n");
+
+    for chunk in input.chunks(UU_LINE_BYTES) {
+        output.push(uu_char(chunk.len() as u8));
+        for group in chunk.chunks(3) {
+            let b = [
+                group[0],
+                group.get(1).copied().unwrap_or(0),
+                group.get(2).copied().unwrap_or(0),
+            ];
+            output.push(uu_char(b[0] >> 2));
+            output.push(uu_char((b[0] << 4 | b[1] >> 4) & 0x3f));
+            output.push(uu_char((b[1] << 2 | b[2] >> 6) & 0x3f));
+            output.push(uu_char(b[2] & 0x3f));
+        }
+        output.push('\n');
+    }
+
+    output.push_str("`\nend\n");
+    output
+}
+
+/// Decodes uuencoded data. The `begin`/`end` lines are optional so that a bare block of
+/// encoded lines can be pasted as well.
+pub fn uudecode(input: &str) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut lines = input.lines().enumerate();
+
+    if input.lines().any(|line| line.starts_with("begin ")) {
+        for (_, line) in lines.by_ref() {
+            if line.starts_with("begin ") {
+                break;
+            }
+        }
+    }
+
+    for (number, line) in lines {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        if line == "end" {
+            break;
+        }
+
+        let bytes = line.as_bytes();
+        let length = uu_value(bytes[0]) as usize;
+        if length == 0 {
+            break;
+        }
+
+        let needed = length.div_ceil(3) * 4;
+        let data = &bytes[1..];
+        if data.len() < needed {
+            return Err(anyhow!(
+                "UUencode line {} is too short: expected {} characters, found {}",
+                number + 1,
+                needed,
+                data.len()
+            ));
+        }
+
+        let mut decoded = Vec::with_capacity(length + 2);
+        for group in data[..needed].chunks(4) {
+            let v: Vec<u8> = group.iter().map(|&c| uu_value(c)).collect();
+            decoded.push(v[0] << 2 | v[1] >> 4);
+            decoded.push(v[1] << 4 | v[2] >> 2);
+            decoded.push(v[2] << 6 | v[3]);
+        }
+        decoded.truncate(length);
+        output.extend(decoded);
+    }
+
+    Ok(output)
+}
+
+fn uu_char(value: u8) -> char {
+    // Zero is written as a backtick rather than a space so lines survive whitespace trimming
+    if value == 0 {
+        '`'
+    } else {
+        char::from(value + 32)
+    }
+}
+
+fn uu_value(c: u8) -> u8 {
+    c.wrapping_sub(32) & 0x3f
+}
+
+/// Encodes `input` as a single-part yEnc file named `data`. yEnc output is 8-bit; bytes above
+/// 0x7F are represented by the Latin-1 character with the same value.
+pub fn yenc_encode(input: &[u8]) -> String {
+    let mut output = format!(
+        "=ybegin line={} size={} name=data\r\n",
+        YENC_LINE_LENGTH,
+        input.len()
+    );
+    let mut line_len = 0;
+
+    for (i, &byte) in input.iter().enumerate() {
+        let encoded = byte.wrapping_add(42);
+        let line_start = line_len == 0;
+        let line_end = line_len + 1 >= YENC_LINE_LENGTH || i + 1 == input.len();
+        let escape = match encoded {
+            0x00 | 0x0a | 0x0d | b'=' => true,
+            b'\t' | b' ' => line_start || line_end,
+            b'.' => line_start,
+            _ => false,
+        };
+
+        if escape {
+            output.push('=');
+            output.push(char::from(encoded.wrapping_add(64)));
+            line_len += 2;
+        } else {
+            output.push(char::from(encoded));
+            line_len += 1;
+        }
+
+        if line_len >= YENC_LINE_LENGTH {
+            output.push_str("\r\n");
+            line_len = 0;
+        }
+    }
+
+    if line_len > 0 {
+        output.push_str("\r\n");
+    }
+    output.push_str(&format!(
+        "=yend size={} crc32={:08x}\r\n",
+        input.len(),
+        crc32(input)
+    ));
+    output
+}
+
+/// Decodes a yEnc block, checking the size and CRC32 from the `=yend` trailer when present.
+pub fn yenc_decode(input: &str) -> Result<Vec<u8>> {
+    let mut lines = input.lines();
+    let header = lines
+        .by_ref()
+        .find(|line| line.starts_with("=ybegin "))
+        .ok_or_else(|| anyhow!("Missing =ybegin header"))?;
+    let expected_size = keyword(header, "size").and_then(|v| v.parse::<usize>().ok());
+    let multipart = keyword(header, "part").is_some();
+
+    let mut output = Vec::new();
+    let mut trailer = None;
+    for line in lines {
+        let line = line.trim_end_matches('\r');
+        if line.starts_with("=ypart ") {
+            continue;
+        }
+        if line.starts_with("=yend") {
+            trailer = Some(line);
+            break;
+        }
+
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            let mut byte = latin1_byte(c)?;
+            if byte == b'=' {
+                let next = chars
+                    .next()
+                    .ok_or_else(|| anyhow!("yEnc escape at end of line"))?;
+                byte = latin1_byte(next)?.wrapping_sub(64);
+            }
+            output.push(byte.wrapping_sub(42));
+        }
+    }
+
+    let trailer = trailer.ok_or_else(|| anyhow!("Missing =yend trailer"))?;
+    if let Some(size) = keyword(trailer, "size").and_then(|v| v.parse::<usize>().ok()) {
+        if size != output.len() {
+            return Err(anyhow!(
+                "yEnc size mismatch: trailer says {} bytes, decoded {}",
+                size,
+                output.len()
+            ));
+        }
+    }
+    if let (false, Some(size)) = (multipart, expected_size) {
+        if size != output.len() {
+            return Err(anyhow!(
+                "yEnc size mismatch: header says {} bytes, decoded {}",
+                size,
+                output.len()
+            ));
+        }
+    }
+    let crc = keyword(trailer, "pcrc32").or_else(|| keyword(trailer, "crc32"));
+    if let Some(crc) = crc {
+        let expected =
+            u32::from_str_radix(crc, 16).map_err(|_| anyhow!("Invalid yEnc CRC32 '{}'", crc))?;
+        let actual = crc32(&output);
+        if expected != actual {
+            return Err(anyhow!(
+                "yEnc CRC32 mismatch: expected {:08x}, got {:08x}",
+                expected,
+                actual
+            ));
+        }
+    }
+
+    Ok(output)
+}
+
+/// Looks up `key=value` in a yEnc header or trailer line.
+fn keyword<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.split_whitespace()
+        .find_map(|token| token.strip_prefix(key)?.strip_prefix('='))
+}
+
+fn latin1_byte(c: char) -> Result<u8> {
+    u8::try_from(c).map_err(|_| anyhow!("U+{:04X} is not an 8-bit yEnc character", c as u32))
+}
+
+fn crc32(input: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(input);
+    crc.sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_printable_round_trip() {
+        let text = "Caf\u{e9} = caf\u{e9}\nend of line \n".repeat(5) + &"x".repeat(100);
+        let encoded = encode_quoted_printable(text.as_bytes());
+        assert!(encoded.starts_with("Caf=C3=A9 =3D caf=C3=A9\r\nend of line=20\r\n"));
+        assert!(encoded.lines().all(|line| line.len() <= QP_LINE_LENGTH));
+        let decoded = decode_quoted_printable(&encoded).unwrap();
+        assert_eq!(
+            String::from_utf8(decoded).unwrap(),
+            text.replace('\n', "\r\n")
+        );
+    }
+
+    #[test]
+    fn quoted_printable_soft_breaks_and_errors() {
+        let decoded = decode_quoted_printable("long =  \r\nline=0a").unwrap();
+        assert_eq!(decoded, b"long line\n");
+        let err = decode_quoted_printable("ok\r\nbad=ZZ").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid quoted-printable escape at byte 7");
+    }
+
+    #[test]
+    fn uuencode_round_trip() {
+        assert_eq!(uuencode(b"Cat"), "begin 644 data\n#0V%T\n`\nend\n");
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(uudecode(&uuencode(&data)).unwrap(), data);
+        assert_eq!(uudecode("#0V%T").unwrap(), b"Cat");
+        assert!(uudecode("M0V%T").is_err());
+    }
+
+    #[test]
+    fn yenc_round_trip_and_crc() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let encoded = yenc_encode(&data);
+        assert!(encoded.starts_with("=ybegin line=128 size=1000 name=data\r\n"));
+        assert_eq!(yenc_decode(&encoded).unwrap(), data);
+
+        let crc = format!("crc32={:08x}", crc32(&data));
+        let corrupted = encoded.replace(&crc, "crc32=00000000");
+        assert!(yenc_decode(&corrupted)
+            .unwrap_err()
+            .to_string()
+            .contains("CRC32 mismatch"));
+    }
+}
@@ -0,0 +1,406 @@
+use anyhow::{anyhow, Result};
+
+use super::{display_bytes, EncodingType};
+
+/// A single entity of a MIME message. Multipart containers are listed alongside their children
+/// so the structure of the message stays visible.
+#[derive(Debug, Clone, Default)]
+pub struct MimePart {
+    /// IMAP-style section number, e.g. `1.2`. Empty for the message itself.
+    pub path: String,
+    pub depth: usize,
+    pub headers: Vec<(String, String)>,
+    pub content_type: String,
+    pub charset: Option<String>,
+    pub transfer_encoding: String,
+    pub filename: Option<String>,
+    pub body: String,
+}
+
+impl MimePart {
+    pub fn is_multipart(&self) -> bool {
+        self.content_type.starts_with("multipart/")
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The codec that undoes this part's Content-Transfer-Encoding. yEnc is not a transfer
+    /// encoding, it is recognised from the `=ybegin` line in an 8bit body.
+    pub fn codec(&self) -> Option<EncodingType> {
+        match self.transfer_encoding.as_str() {
+            "base64" => Some(EncodingType::Base64Mime),
+            "quoted-printable" => Some(EncodingType::QuotedPrintable),
+            "x-uuencode" | "x-uue" | "uuencode" => Some(EncodingType::UuEncode),
+            _ if self.body.trim_start().starts_with("=ybegin ") => Some(EncodingType::YEnc),
+            _ => None,
+        }
+    }
+
+    pub fn decode(&self) -> Result<Vec<u8>> {
+        if self.is_multipart() {
+            return Err(anyhow!(
+                "{} is a container, select one of its parts",
+                self.content_type
+            ));
+        }
+
+        match self.codec() {
+            Some(codec) => codec.decode_bytes(&self.body),
+            None if self.is_latin1() => Ok(self
+                .body
+                .chars()
+                .map(latin1_or_utf8)
+                .collect::<Vec<_>>()
+                .concat()),
+            None => Ok(self.body.as_bytes().to_vec()),
+        }
+    }
+
+    fn is_latin1(&self) -> bool {
+        matches!(
+            self.charset.as_deref(),
+            Some("iso-8859-1" | "latin1" | "windows-1252")
+        )
+    }
+
+    /// Decodes the part and interprets the bytes with its charset. Returns `None` for binary
+    /// payloads that are not text.
+    pub fn decode_text(&self) -> Result<Option<String>> {
+        let bytes = self.decode()?;
+        if self.is_latin1() {
+            return Ok(Some(bytes.iter().map(|&b| char::from(b)).collect()));
+        }
+        Ok(String::from_utf8(bytes).ok())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MimeInspector {
+    pub raw: String,
+    pub parts: Vec<MimePart>,
+    pub selected: Option<usize>,
+    pub output: String,
+}
+
+impl MimeInspector {
+    pub fn parse(&mut self) -> Result<()> {
+        self.selected = None;
+        self.output.clear();
+        self.parts = parse_message(&self.raw)?;
+        Ok(())
+    }
+
+    /// Decodes the part at `index` into `output`. Binary payloads are shown as a hex dump.
+    pub fn select(&mut self, index: usize) -> Result<()> {
+        let part = self
+            .parts
+            .get(index)
+            .ok_or_else(|| anyhow!("No MIME part at index {}", index))?;
+        self.selected = Some(index);
+        self.output = match part.decode_text()? {
+            Some(text) => text,
+            None => display_bytes(&part.decode()?),
+        };
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.raw.clear();
+        self.parts.clear();
+        self.selected = None;
+        self.output.clear();
+    }
+}
+
+/// Splits a raw RFC 822 message into its MIME parts, depth first.
+pub fn parse_message(raw: &str) -> Result<Vec<MimePart>> {
+    let raw = raw.replace("\r\n", "\n");
+    if raw.trim().is_empty() {
+        return Err(anyhow!("Message is empty"));
+    }
+
+    let mut parts = Vec::new();
+    parse_entity(&raw, String::new(), 0, &mut parts)?;
+    if parts[0].headers.is_empty() {
+        return Err(anyhow!("No message headers found"));
+    }
+    Ok(parts)
+}
+
+fn parse_entity(raw: &str, path: String, depth: usize, parts: &mut Vec<MimePart>) -> Result<()> {
+    let (header_block, body) = match raw.strip_prefix('\n') {
+        // A part that starts with a blank line has no headers
+        Some(body) => ("", body),
+        None => raw.split_once("\n\n").unwrap_or((raw, "")),
+    };
+
+    let headers = parse_headers(header_block);
+    let find = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+
+    let (content_type, params) = find("Content-Type")
+        .map(parse_header_value)
+        .unwrap_or_else(|| ("text/plain".to_string(), Vec::new()));
+    let param = |params: &[(String, String)], name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    let filename = find("Content-Disposition")
+        .map(parse_header_value)
+        .and_then(|(_, disposition)| param(&disposition, "filename"))
+        .or_else(|| param(&params, "name"));
+
+    let part = MimePart {
+        path: path.clone(),
+        depth,
+        content_type: content_type.clone(),
+        charset: param(&params, "charset").map(|c| c.to_ascii_lowercase()),
+        transfer_encoding: find("Content-Transfer-Encoding")
+            .map(|v| v.trim().to_ascii_lowercase())
+            .unwrap_or_else(|| "7bit".to_string()),
+        filename,
+        body: body.to_string(),
+        headers,
+    };
+    parts.push(part);
+
+    if content_type.starts_with("multipart/") {
+        let boundary = param(&params, "boundary").ok_or_else(|| {
+            anyhow!(
+                "{} part {} has no boundary parameter",
+                content_type,
+                display_path(&path)
+            )
+        })?;
+
+        for (i, child) in split_multipart(body, &boundary)?.iter().enumerate() {
+            let child_path = if path.is_empty() {
+                (i + 1).to_string()
+            } else {
+                format!("{}.{}", path, i + 1)
+            };
+            parse_entity(child, child_path, depth + 1, parts)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn split_multipart<'a>(body: &'a str, boundary: &str) -> Result<Vec<&'a str>> {
+    let delimiter = format!("--{}", boundary);
+    let close = format!("--{}--", boundary);
+
+    let mut children = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    let mut closed = false;
+
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == delimiter || trimmed == close {
+            if let Some(start) = start {
+                // The line break before a delimiter belongs to the delimiter
+                let end = if offset > start { offset - 1 } else { offset };
+                children.push(&body[start..end]);
+            }
+            if trimmed == close {
+                closed = true;
+                break;
+            }
+            start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+
+    if !closed {
+        if let Some(start) = start {
+            children.push(&body[start..]);
+        }
+    }
+
+    if children.is_empty() {
+        return Err(anyhow!(
+            "Boundary '{}' not found in multipart body",
+            boundary
+        ));
+    }
+    Ok(children)
+}
+
+fn parse_headers(block: &str) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in block.lines() {
+        if line.starts_with([' ', '\t']) {
+            // Folded continuation of the previous header
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    headers
+}
+
+/// Splits `type/subtype; key=value; key="quoted value"` into the lowercase value and its
+/// parameters. Parameter names are lowercased, values are unquoted.
+fn parse_header_value(value: &str) -> (String, Vec<(String, String)>) {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ';' if !quoted => segments.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    segments.push(current);
+
+    let mut segments = segments.into_iter();
+    let main = segments
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let params = segments
+        .filter_map(|segment| {
+            let (key, value) = segment.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            Some((key.trim().to_ascii_lowercase(), value.to_string()))
+        })
+        .collect();
+
+    (main, params)
+}
+
+pub fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "message"
+    } else {
+        path
+    }
+}
+
+/// 8bit Latin-1 bodies are pasted as text. Characters up to U+00FF map back to their original
+/// byte, anything else can only have come from UTF-8 and is re-encoded as such.
+fn latin1_or_utf8(c: char) -> Vec<u8> {
+    match u8::try_from(c) {
+        Ok(byte) => vec![byte],
+        Err(_) => c.to_string().into_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "From: alice@example.com\r\n\
+        Subject: Report\r\n\
+        MIME-Version: 1.0\r\n\
+        Content-Type: multipart/mixed;\r\n \
+        boundary=\"outer\"\r\n\
+        \r\n\
+        preamble\r\n\
+        --outer\r\n\
+        Content-Type: multipart/alternative; boundary=inner\r\n\
+        \r\n\
+        --inner\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        Content-Transfer-Encoding: quoted-printable\r\n\
+        \r\n\
+        Caf=C3=A9 report=\r\n attached\r\n\
+        --inner\r\n\
+        Content-Type: text/html; charset=iso-8859-1\r\n\
+        \r\n\
+        <p>Caf\u{e9}</p>\r\n\
+        --inner--\r\n\
+        --outer\r\n\
+        Content-Type: application/octet-stream; name=\"data.bin\"\r\n\
+        Content-Disposition: attachment; filename=\"report.bin\"\r\n\
+        Content-Transfer-Encoding: base64\r\n\
+        \r\n\
+        AAEC/w==\r\n\
+        --outer--\r\n";
+
+    #[test]
+    fn lists_nested_parts() {
+        let parts = parse_message(MESSAGE).unwrap();
+        let summary: Vec<_> = parts
+            .iter()
+            .map(|p| (p.path.as_str(), p.content_type.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("", "multipart/mixed"),
+                ("1", "multipart/alternative"),
+                ("1.1", "text/plain"),
+                ("1.2", "text/html"),
+                ("2", "application/octet-stream"),
+            ]
+        );
+        assert_eq!(parts[0].header("subject"), Some("Report"));
+        assert_eq!(parts[2].transfer_encoding, "quoted-printable");
+        assert_eq!(parts[4].filename.as_deref(), Some("report.bin"));
+    }
+
+    #[test]
+    fn decodes_parts_with_their_codec() {
+        let parts = parse_message(MESSAGE).unwrap();
+        assert_eq!(
+            parts[2].decode_text().unwrap().as_deref(),
+            Some("Caf\u{e9} report attached")
+        );
+        assert_eq!(
+            parts[3].decode_text().unwrap().as_deref(),
+            Some("<p>Caf\u{e9}</p>")
+        );
+        assert_eq!(parts[4].decode().unwrap(), vec![0x00, 0x01, 0x02, 0xff]);
+        assert!(parts[1].decode().is_err());
+    }
+
+    #[test]
+    fn keeps_utf8_bytes_of_8bit_bodies() {
+        let message = "Content-Type: text/plain; charset=utf-8\r\n\
+            Content-Transfer-Encoding: 8bit\r\n\
+            \r\n\
+            Caf\u{e9}\r\n";
+        let parts = parse_message(message).unwrap();
+        assert_eq!(parts[0].decode().unwrap(), "Caf\u{e9}\n".as_bytes());
+        assert_eq!(
+            parts[0].decode_text().unwrap().as_deref(),
+            Some("Caf\u{e9}\n")
+        );
+    }
+
+    #[test]
+    fn reports_malformed_messages() {
+        assert!(parse_message("").is_err());
+        let err = parse_message("Content-Type: multipart/mixed\n\nbody").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "multipart/mixed part message has no boundary parameter"
+        );
+    }
+}
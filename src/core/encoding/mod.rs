@@ -1,9 +1,22 @@
+pub mod compression;
+pub mod data_uri;
+pub mod escape;
+pub mod input;
+pub mod legacy;
+pub mod mime;
+
 use anyhow::Result;
 use base32::Alphabet;
 use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use url::form_urlencoded;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+use compression::Compression;
+use data_uri::DataUriConverter;
+use mime::MimeInspector;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EncodingType {
     #[default]
     Base64,
@@ -11,6 +24,207 @@ pub enum EncodingType {
     Base64Mime,
     Base32,
     UrlEncoding,
+    QuotedPrintable,
+    UuEncode,
+    YEnc,
+    HtmlEntities,
+    Xml,
+    JsonString,
+    JavaScriptString,
+    RustString,
+    CString,
+    JavaProperties,
+    ShellQuote,
+    CsvField,
+}
+
+impl fmt::Display for EncodingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingType::Base64 => write!(f, "Base64"),
+            EncodingType::Base64Url => write!(f, "Base64 URL"),
+            EncodingType::Base64Mime => write!(f, "Base64 MIME"),
+            EncodingType::Base32 => write!(f, "Base32"),
+            EncodingType::UrlEncoding => write!(f, "URL Encoding"),
+            EncodingType::QuotedPrintable => write!(f, "Quoted-Printable"),
+            EncodingType::UuEncode => write!(f, "UUencode"),
+            EncodingType::YEnc => write!(f, "yEnc"),
+            EncodingType::HtmlEntities => write!(f, "HTML Entities"),
+            EncodingType::Xml => write!(f, "XML"),
+            EncodingType::JsonString => write!(f, "JSON String"),
+            EncodingType::JavaScriptString => write!(f, "JavaScript String"),
+            EncodingType::RustString => write!(f, "Rust String"),
+            EncodingType::CString => write!(f, "C String"),
+            EncodingType::JavaProperties => write!(f, "Java Properties"),
+            EncodingType::ShellQuote => write!(f, "Shell Quote"),
+            EncodingType::CsvField => write!(f, "CSV Field"),
+        }
+    }
+}
+
+impl EncodingType {
+    pub fn variants() -> &'static [EncodingType] {
+        &[
+            EncodingType::Base64,
+            EncodingType::Base64Url,
+            EncodingType::Base64Mime,
+            EncodingType::Base32,
+            EncodingType::UrlEncoding,
+            EncodingType::QuotedPrintable,
+            EncodingType::UuEncode,
+            EncodingType::YEnc,
+            EncodingType::HtmlEntities,
+            EncodingType::Xml,
+            EncodingType::JsonString,
+            EncodingType::JavaScriptString,
+            EncodingType::RustString,
+            EncodingType::CString,
+            EncodingType::JavaProperties,
+            EncodingType::ShellQuote,
+            EncodingType::CsvField,
+        ]
+    }
+
+    /// Whether this is a text escaping scheme rather than a binary-to-text encoding.
+    pub fn is_escape(&self) -> bool {
+        !matches!(
+            self,
+            EncodingType::Base64
+                | EncodingType::Base64Url
+                | EncodingType::Base64Mime
+                | EncodingType::Base32
+                | EncodingType::UrlEncoding
+                | EncodingType::QuotedPrintable
+                | EncodingType::UuEncode
+                | EncodingType::YEnc
+        )
+    }
+
+    /// Encodes raw bytes into the textual representation of this encoding.
+    pub fn encode_bytes(&self, input: &[u8]) -> Result<String> {
+        if self.is_escape() {
+            let text = std::str::from_utf8(input)
+                .map_err(|_| anyhow::anyhow!("{} input is not valid UTF-8", self))?;
+            return Ok(self.escape(text));
+        }
+
+        let encoded = match self {
+            EncodingType::Base64 => general_purpose::STANDARD.encode(input),
+            EncodingType::Base64Url => general_purpose::URL_SAFE.encode(input),
+            EncodingType::Base64Mime => {
+                let encoded = general_purpose::STANDARD.encode(input);
+                // MIME base64 adds line breaks every 76 characters
+                encoded
+                    .chars()
+                    .collect::<Vec<char>>()
+                    .chunks(76)
+                    .map(|chunk| chunk.iter().collect::<String>())
+                    .collect::<Vec<String>>()
+                    .join("\r\n")
+            }
+            EncodingType::Base32 => base32::encode(Alphabet::Rfc4648 { padding: true }, input),
+            EncodingType::UrlEncoding => form_urlencoded::byte_serialize(input).collect(),
+            EncodingType::QuotedPrintable => legacy::encode_quoted_printable(input),
+            EncodingType::UuEncode => legacy::uuencode(input),
+            EncodingType::YEnc => legacy::yenc_encode(input),
+            _ => unreachable!("escapes are handled above"),
+        };
+
+        Ok(encoded)
+    }
+
+    /// Decodes the textual representation of this encoding back into raw bytes.
+    pub fn decode_bytes(&self, input: &str) -> Result<Vec<u8>> {
+        let decoded = match self {
+            EncodingType::Base64 => general_purpose::STANDARD.decode(percent_decode(input))?,
+            EncodingType::Base64Url => general_purpose::URL_SAFE.decode(percent_decode(input))?,
+            EncodingType::Base64Mime => {
+                let clean_input = percent_decode(input).replace(['\r', '\n', ' '], "");
+                general_purpose::STANDARD.decode(&clean_input)?
+            }
+            EncodingType::Base32 => base32::decode(Alphabet::Rfc4648 { padding: true }, input)
+                .ok_or_else(|| anyhow::anyhow!("Invalid Base32 input"))?,
+            EncodingType::UrlEncoding => {
+                let decoded = form_urlencoded::parse(format!("dummy={}", input).as_bytes())
+                    .find(|(key, _)| key == "dummy")
+                    .map(|(_, value)| value.into_owned())
+                    .ok_or_else(|| anyhow::anyhow!("Invalid URL encoding"))?;
+                decoded.into_bytes()
+            }
+            EncodingType::QuotedPrintable => legacy::decode_quoted_printable(input)?,
+            EncodingType::UuEncode => legacy::uudecode(input)?,
+            EncodingType::YEnc => legacy::yenc_decode(input)?,
+            EncodingType::HtmlEntities => escape::unescape_html(input)?.into_bytes(),
+            EncodingType::Xml => escape::unescape_xml(input)?.into_bytes(),
+            EncodingType::JsonString => escape::unescape_json(input)?.into_bytes(),
+            EncodingType::JavaScriptString => escape::unescape_javascript(input)?.into_bytes(),
+            EncodingType::RustString => escape::unescape_rust(input)?.into_bytes(),
+            EncodingType::CString => escape::unescape_c(input)?,
+            EncodingType::JavaProperties => escape::unescape_java_properties(input)?.into_bytes(),
+            EncodingType::ShellQuote => escape::unescape_shell(input)?.into_bytes(),
+            EncodingType::CsvField => escape::unescape_csv(input)?.into_bytes(),
+        };
+
+        Ok(decoded)
+    }
+
+    fn escape(&self, input: &str) -> String {
+        match self {
+            EncodingType::HtmlEntities => escape::escape_html(input),
+            EncodingType::Xml => escape::escape_xml(input),
+            EncodingType::JsonString => escape::escape_json(input),
+            EncodingType::JavaScriptString => escape::escape_javascript(input),
+            EncodingType::RustString => escape::escape_rust(input),
+            EncodingType::CString => escape::escape_c(input),
+            EncodingType::JavaProperties => escape::escape_java_properties(input),
+            EncodingType::ShellQuote => escape::escape_shell(input),
+            EncodingType::CsvField => escape::escape_csv(input),
+            _ => input.to_string(),
+        }
+    }
+}
+
+/// Base64 payloads copied out of query strings (e.g. `SAMLRequest`) are often still
+/// percent-encoded. `%` is not part of any Base64 alphabet, so decoding it first is lossless.
+fn percent_decode(input: &str) -> String {
+    if !input.contains('%') {
+        return input.to_string();
+    }
+
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(Ok(byte)) = input.get(i + 1..i + 3).map(|h| u8::from_str_radix(h, 16)) {
+                output.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        output.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// UTF-8 text when the bytes are valid UTF-8, otherwise a hex dump with 16 bytes per line.
+pub fn display_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes
+            .chunks(16)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -20,6 +234,16 @@ pub struct EncodingProcessor {
     pub encoding_type: EncodingType,
     pub handle_line_breaks: bool,
     pub live_conversion: bool,
+    /// Compression applied before encoding and reverted after decoding
+    pub compression: Option<Compression>,
+    /// Detect the compression format from magic bytes when decoding
+    pub detect_compression: bool,
+    /// Compression format found by the last decode
+    pub detected_compression: Option<Compression>,
+    /// Raw message split into MIME parts
+    pub mime: MimeInspector,
+    /// Files and payloads converted to and from `data:` URIs
+    pub data_uri: DataUriConverter,
 }
 
 impl EncodingProcessor {
@@ -30,6 +254,11 @@ impl EncodingProcessor {
             encoding_type: EncodingType::Base64,
             handle_line_breaks: false,
             live_conversion: false,
+            compression: None,
+            detect_compression: false,
+            detected_compression: None,
+            mime: MimeInspector::default(),
+            data_uri: DataUriConverter::default(),
         }
     }
 
@@ -38,6 +267,8 @@ impl EncodingProcessor {
         self.encoded_text.clear();
         self.encoding_type = EncodingType::Base64;
         self.handle_line_breaks = false;
+        self.compression = None;
+        self.detected_compression = None;
     }
 
     pub fn encode(&mut self) -> Result<()> {
@@ -47,55 +278,18 @@ impl EncodingProcessor {
             self.decoded_text.clone()
         };
 
-        self.encoded_text = match self.encoding_type {
-            EncodingType::Base64 => general_purpose::STANDARD.encode(&input),
-            EncodingType::Base64Url => general_purpose::URL_SAFE.encode(&input),
-            EncodingType::Base64Mime => {
-                let encoded = general_purpose::STANDARD.encode(&input);
-                // MIME base64 adds line breaks every 76 characters
-                encoded
-                    .chars()
-                    .collect::<Vec<char>>()
-                    .chunks(76)
-                    .map(|chunk| chunk.iter().collect::<String>())
-                    .collect::<Vec<String>>()
-                    .join("\r\n")
-            }
-            EncodingType::Base32 => {
-                base32::encode(Alphabet::Rfc4648 { padding: true }, input.as_bytes())
-            }
-            EncodingType::UrlEncoding => form_urlencoded::Serializer::new(String::new())
-                .append_pair("", &input)
-                .finish()
-                .trim_start_matches('=')
-                .to_string(),
+        let bytes = match &self.compression {
+            Some(compression) => compression.compress(input.as_bytes())?,
+            None => input.into_bytes(),
         };
 
+        self.encoded_text = self.encoding_type.encode_bytes(&bytes)?;
+
         Ok(())
     }
 
     pub fn decode(&mut self) -> Result<()> {
-        let decoded_bytes = match self.encoding_type {
-            EncodingType::Base64 => general_purpose::STANDARD.decode(&self.encoded_text)?,
-            EncodingType::Base64Url => general_purpose::URL_SAFE.decode(&self.encoded_text)?,
-            EncodingType::Base64Mime => {
-                let clean_input = self.encoded_text.replace(['\r', '\n', ' '], "");
-                general_purpose::STANDARD.decode(&clean_input)?
-            }
-            EncodingType::Base32 => {
-                base32::decode(Alphabet::Rfc4648 { padding: true }, &self.encoded_text)
-                    .ok_or_else(|| anyhow::anyhow!("Invalid Base32 input"))?
-            }
-            EncodingType::UrlEncoding => {
-                let decoded =
-                    form_urlencoded::parse(format!("dummy={}", self.encoded_text).as_bytes())
-                        .find(|(key, _)| key == "dummy")
-                        .map(|(_, value)| value.into_owned())
-                        .ok_or_else(|| anyhow::anyhow!("Invalid URL encoding"))?;
-                decoded.into_bytes()
-            }
-        };
-
+        let decoded_bytes = self.decode_to_bytes()?;
         let mut decoded_string = String::from_utf8(decoded_bytes)
             .map_err(|_| anyhow::anyhow!("Decoded data is not valid UTF-8"))?;
 
@@ -106,4 +300,66 @@ impl EncodingProcessor {
         self.decoded_text = decoded_string;
         Ok(())
     }
+
+    /// Decodes and decompresses the encoded text without requiring the result to be UTF-8,
+    /// so binary payloads can be handed to the other inspection tools.
+    pub fn decode_to_bytes(&mut self) -> Result<Vec<u8>> {
+        // Surrounding whitespace is significant for escaped text but not for binary encodings
+        let encoded = if self.encoding_type.is_escape() {
+            self.encoded_text.as_str()
+        } else {
+            self.encoded_text.trim()
+        };
+        let mut decoded_bytes = self.encoding_type.decode_bytes(encoded)?;
+
+        self.detected_compression = None;
+        if self.detect_compression {
+            if let Ok((compression, bytes)) = Compression::decompress_detected(&decoded_bytes) {
+                self.detected_compression = Some(compression);
+                decoded_bytes = bytes;
+            }
+        } else if let Some(compression) = &self.compression {
+            decoded_bytes = compression.decompress(&decoded_bytes)?;
+        }
+
+        Ok(decoded_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressed_round_trip() {
+        let mut processor = EncodingProcessor::new();
+        processor.decoded_text = "hello hello hello".to_string();
+        processor.compression = Some(Compression::Zlib);
+        processor.encode().unwrap();
+
+        processor.decoded_text.clear();
+        processor.compression = None;
+        processor.detect_compression = true;
+        processor.decode().unwrap();
+
+        assert_eq!(processor.decoded_text, "hello hello hello");
+        assert_eq!(processor.detected_compression, Some(Compression::Zlib));
+    }
+
+    #[test]
+    fn saml_request_decodes_in_one_step() {
+        let xml = "<samlp:AuthnRequest ID=\"_1\"/>";
+        let deflated = Compression::Deflate.compress(xml.as_bytes()).unwrap();
+        let parameter: String =
+            form_urlencoded::byte_serialize(general_purpose::STANDARD.encode(deflated).as_bytes())
+                .collect();
+
+        let mut processor = EncodingProcessor::new();
+        processor.encoded_text = parameter;
+        processor.detect_compression = true;
+        processor.decode().unwrap();
+
+        assert_eq!(processor.decoded_text, xml);
+        assert_eq!(processor.detected_compression, Some(Compression::Deflate));
+    }
 }
@@ -77,11 +77,11 @@ impl GeneratorProcessor {
         Ok(())
     }
 
-    fn generate_uuid(&self) -> String {
+    pub fn generate_uuid(&self) -> String {
         Uuid::new_v4().to_string()
     }
 
-    fn generate_ulid(&self) -> String {
+    pub fn generate_ulid(&self) -> String {
         ulid::Ulid::new().to_string()
     }
 
@@ -0,0 +1,252 @@
+//! Offline audit of HS-signed tokens against a local wordlist of candidate secrets.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::{anyhow, bail, Result};
+use jsonwebtoken::DecodingKey;
+
+use super::Algorithm;
+
+/// Candidates tried between two progress updates.
+const PROGRESS_INTERVAL: usize = 5_000;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum AuditState {
+    #[default]
+    Idle,
+    Running {
+        tried: usize,
+        /// Fraction of the wordlist read, from 0 to 1
+        progress: f32,
+    },
+    Found {
+        secret: String,
+        tried: usize,
+    },
+    NotFound {
+        tried: usize,
+    },
+    Cancelled {
+        tried: usize,
+    },
+    Failed(String),
+}
+
+#[derive(Debug)]
+enum AuditMessage {
+    Progress { tried: usize, progress: f32 },
+    Done(AuditState),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SecretAudit {
+    pub wordlist: Option<PathBuf>,
+    pub state: AuditState,
+    receiver: Option<Arc<Mutex<Receiver<AuditMessage>>>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl SecretAudit {
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, AuditState::Running { .. })
+    }
+
+    /// Starts trying every line of the wordlist as the HMAC secret of `token` on a background
+    /// thread, stopping at the first one that verifies.
+    pub fn start(&mut self, token: &str, algorithm: &Algorithm) -> Result<()> {
+        if self.is_running() {
+            return Ok(());
+        }
+        if !algorithm.is_hmac() {
+            bail!("The secret audit only applies to HS256, HS384 and HS512 tokens");
+        }
+        let path = self
+            .wordlist
+            .clone()
+            .ok_or_else(|| anyhow!("Choose a wordlist file first"))?;
+        let (message, signature) = token
+            .trim()
+            .rsplit_once('.')
+            .filter(|(message, _)| message.split('.').count() == 2)
+            .ok_or_else(|| anyhow!("Paste a single signed token to audit"))?;
+        let (message, signature) = (message.to_string(), signature.to_string());
+        let algorithm: jsonwebtoken::Algorithm = algorithm.clone().into();
+
+        let (sender, receiver) = mpsc::channel();
+        self.cancel = Arc::new(AtomicBool::new(false));
+        self.receiver = Some(Arc::new(Mutex::new(receiver)));
+        self.state = AuditState::Running {
+            tried: 0,
+            progress: 0.0,
+        };
+
+        let cancel = Arc::clone(&self.cancel);
+        std::thread::spawn(move || {
+            let state = run(&path, &message, &signature, algorithm, &cancel, &sender)
+                .unwrap_or_else(|e| AuditState::Failed(e.to_string()));
+            let _ = sender.send(AuditMessage::Done(state));
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Applies the messages sent by the audit thread since the last call.
+    pub fn check_progress(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        let Ok(receiver) = receiver.try_lock() else {
+            return;
+        };
+
+        let mut finished = false;
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                AuditMessage::Progress { tried, progress } => {
+                    self.state = AuditState::Running { tried, progress };
+                }
+                AuditMessage::Done(state) => {
+                    self.state = state;
+                    finished = true;
+                }
+            }
+        }
+        drop(receiver);
+
+        if finished {
+            self.receiver = None;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.cancel();
+        self.receiver = None;
+        self.state = AuditState::Idle;
+    }
+}
+
+fn run(
+    path: &Path,
+    message: &str,
+    signature: &str,
+    algorithm: jsonwebtoken::Algorithm,
+    cancel: &AtomicBool,
+    sender: &Sender<AuditMessage>,
+) -> Result<AuditState> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or_default().max(1);
+    let mut reader = BufReader::new(file);
+
+    let mut candidate = Vec::new();
+    let mut read = 0u64;
+    let mut tried = 0;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(AuditState::Cancelled { tried });
+        }
+
+        candidate.clear();
+        let len = reader
+            .read_until(b'\n', &mut candidate)
+            .map_err(|e| anyhow!("Failed to read the wordlist: {}", e))?;
+        if len == 0 {
+            return Ok(AuditState::NotFound { tried });
+        }
+        read += len as u64;
+        while matches!(candidate.last(), Some(b'\n' | b'\r')) {
+            candidate.pop();
+        }
+
+        tried += 1;
+        let key = DecodingKey::from_secret(&candidate);
+        if jsonwebtoken::crypto::verify(signature, message.as_bytes(), &key, algorithm)
+            .unwrap_or(false)
+        {
+            return Ok(AuditState::Found {
+                secret: String::from_utf8_lossy(&candidate).into_owned(),
+                tried,
+            });
+        }
+
+        if tried % PROGRESS_INTERVAL == 0 {
+            let _ = sender.send(AuditMessage::Progress {
+                tried,
+                progress: read as f32 / size as f32,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn audit(words: &str, token: &str) -> AuditState {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wordlist.txt");
+        std::fs::write(&path, words).unwrap();
+
+        let mut audit = SecretAudit {
+            wordlist: Some(path),
+            ..Default::default()
+        };
+        audit.start(token, &Algorithm::HS256).unwrap();
+        let started = Instant::now();
+        while audit.is_running() && started.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(10));
+            audit.check_progress();
+        }
+        audit.state
+    }
+
+    fn token(secret: &str) -> String {
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &serde_json::json!({ "sub": "1" }),
+            &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_finds_secret_in_wordlist() {
+        let state = audit("password\r\nletmein\nhunter2\nzzz\n", &token("hunter2"));
+        assert_eq!(
+            state,
+            AuditState::Found {
+                secret: "hunter2".to_string(),
+                tried: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_reports_exhausted_wordlist() {
+        let state = audit("a\nb\nc", &token("not-in-the-list"));
+        assert_eq!(state, AuditState::NotFound { tried: 3 });
+    }
+
+    #[test]
+    fn test_rejects_non_hmac_tokens() {
+        let mut audit = SecretAudit {
+            wordlist: Some(PathBuf::from("words.txt")),
+            ..Default::default()
+        };
+        assert!(audit.start(&token("x"), &Algorithm::RS256).is_err());
+        assert_eq!(audit.state, AuditState::Idle);
+    }
+}
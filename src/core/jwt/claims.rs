@@ -0,0 +1,394 @@
+//! Registered claim validation (RFC 7519 section 4.1) with clock-skew leeway.
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde_json::{Map, Value};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimStatus {
+    /// The claim passes its check
+    Pass,
+    /// The claim is absent or unchecked but does not make the token unacceptable
+    Warn,
+    /// The claim makes the token unacceptable
+    Fail,
+    /// The claim is shown for information only
+    Info,
+}
+
+impl fmt::Display for ClaimStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimStatus::Pass => write!(f, "✔"),
+            ClaimStatus::Warn => write!(f, "⚠"),
+            ClaimStatus::Fail => write!(f, "❌"),
+            ClaimStatus::Info => write!(f, "ℹ"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimCheck {
+    pub claim: &'static str,
+    pub value: Option<String>,
+    pub status: ClaimStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimReport {
+    pub checks: Vec<ClaimCheck>,
+    pub acceptable: bool,
+    pub verdict: String,
+}
+
+/// Values the registered claims are checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimExpectations {
+    /// Required `iss`, unchecked when empty
+    pub issuer: String,
+    /// Audience that must appear in `aud`, unchecked when empty
+    pub audience: String,
+    /// Clock skew tolerated on `exp`, `nbf` and `iat`, in seconds
+    pub leeway: u64,
+}
+
+impl Default for ClaimExpectations {
+    fn default() -> Self {
+        Self {
+            issuer: String::new(),
+            audience: String::new(),
+            leeway: 60,
+        }
+    }
+}
+
+/// Absolute UTC and local rendering of a NumericDate, followed by its relative time.
+pub fn describe_timestamp(timestamp: i64, relative: &str) -> String {
+    match Utc.timestamp_opt(timestamp, 0).single() {
+        Some(utc) => format!(
+            "{} ({} local), {}",
+            utc.format("%Y-%m-%d %H:%M:%S UTC"),
+            utc.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z"),
+            relative
+        ),
+        None => format!("{} (out of range)", timestamp),
+    }
+}
+
+fn numeric_date(value: &Value) -> Option<i64> {
+    value
+        .as_i64()
+        .or_else(|| value.as_f64().map(|seconds| seconds.floor() as i64))
+}
+
+fn check_timestamp(
+    claims: &Map<String, Value>,
+    claim: &'static str,
+    now: i64,
+    leeway: i64,
+    relative: &dyn Fn(i64) -> String,
+) -> ClaimCheck {
+    let Some(raw) = claims.get(claim) else {
+        let (status, message) = match claim {
+            "exp" => (ClaimStatus::Warn, "Not set, the token never expires"),
+            "nbf" => (ClaimStatus::Info, "Not set, valid from issuance"),
+            _ => (ClaimStatus::Info, "Not set"),
+        };
+        return ClaimCheck {
+            claim,
+            value: None,
+            status,
+            message: message.to_string(),
+        };
+    };
+    let Some(timestamp) = numeric_date(raw) else {
+        return ClaimCheck {
+            claim,
+            value: Some(raw.to_string()),
+            status: ClaimStatus::Fail,
+            message: "Not a NumericDate".to_string(),
+        };
+    };
+
+    let (status, message) = match claim {
+        "exp" if now > timestamp.saturating_add(leeway) => (ClaimStatus::Fail, "Expired"),
+        "exp" if now > timestamp => (ClaimStatus::Warn, "Expired, but within the leeway"),
+        "exp" => (ClaimStatus::Pass, "Not expired"),
+        "nbf" if now.saturating_add(leeway) < timestamp => (ClaimStatus::Fail, "Not valid yet"),
+        "nbf" if now < timestamp => (ClaimStatus::Warn, "Not valid yet, but within the leeway"),
+        "nbf" => (ClaimStatus::Pass, "Valid since"),
+        _ if now.saturating_add(leeway) < timestamp => (ClaimStatus::Fail, "Issued in the future"),
+        _ => (ClaimStatus::Pass, "Issued"),
+    };
+    ClaimCheck {
+        claim,
+        value: Some(describe_timestamp(timestamp, &relative(timestamp))),
+        status,
+        message: message.to_string(),
+    }
+}
+
+fn check_issuer(claims: &Map<String, Value>, expected: &str) -> ClaimCheck {
+    let value = claims.get("iss").map(|iss| match iss.as_str() {
+        Some(iss) => iss.to_string(),
+        None => iss.to_string(),
+    });
+    let (status, message) = match (&value, expected.is_empty()) {
+        (None, true) => (ClaimStatus::Info, "Not set".to_string()),
+        (None, false) => (ClaimStatus::Fail, format!("Missing, expected {}", expected)),
+        (Some(_), true) => (
+            ClaimStatus::Info,
+            "No expected issuer configured".to_string(),
+        ),
+        (Some(iss), false) if iss == expected => (ClaimStatus::Pass, "Matches".to_string()),
+        (Some(_), false) => (ClaimStatus::Fail, format!("Expected {}", expected)),
+    };
+    ClaimCheck {
+        claim: "iss",
+        value,
+        status,
+        message,
+    }
+}
+
+fn check_audience(claims: &Map<String, Value>, expected: &str) -> ClaimCheck {
+    let audiences: Option<Vec<String>> = claims.get("aud").map(|aud| match aud {
+        Value::Array(values) => values
+            .iter()
+            .map(|value| {
+                value
+                    .as_str()
+                    .map_or_else(|| value.to_string(), str::to_string)
+            })
+            .collect(),
+        Value::String(aud) => vec![aud.clone()],
+        other => vec![other.to_string()],
+    });
+    let value = audiences.as_ref().map(|audiences| audiences.join(", "));
+    let (status, message) = match (&audiences, expected.is_empty()) {
+        (None, true) => (ClaimStatus::Info, "Not set".to_string()),
+        (None, false) => (ClaimStatus::Fail, format!("Missing, expected {}", expected)),
+        (Some(_), true) => (
+            ClaimStatus::Info,
+            "No expected audience configured".to_string(),
+        ),
+        (Some(audiences), false) if audiences.iter().any(|aud| aud == expected) => {
+            (ClaimStatus::Pass, format!("Includes {}", expected))
+        }
+        (Some(_), false) => (ClaimStatus::Fail, format!("Does not include {}", expected)),
+    };
+    ClaimCheck {
+        claim: "aud",
+        value,
+        status,
+        message,
+    }
+}
+
+fn check_subject(claims: &Map<String, Value>) -> ClaimCheck {
+    match claims.get("sub") {
+        Some(Value::String(sub)) => ClaimCheck {
+            claim: "sub",
+            value: Some(sub.clone()),
+            status: ClaimStatus::Info,
+            message: "Present".to_string(),
+        },
+        Some(other) => ClaimCheck {
+            claim: "sub",
+            value: Some(other.to_string()),
+            status: ClaimStatus::Fail,
+            message: "Must be a string".to_string(),
+        },
+        None => ClaimCheck {
+            claim: "sub",
+            value: None,
+            status: ClaimStatus::Warn,
+            message: "Not set".to_string(),
+        },
+    }
+}
+
+/// Checks the registered claims at `now`. `relative` renders a timestamp relative to the
+/// current time, such as "in 3 hours".
+pub fn validate(
+    claims: &Map<String, Value>,
+    expectations: &ClaimExpectations,
+    now: DateTime<Utc>,
+    relative: &dyn Fn(i64) -> String,
+    signature: Option<bool>,
+) -> ClaimReport {
+    let now = now.timestamp();
+    let leeway = expectations.leeway as i64;
+    let checks = vec![
+        check_timestamp(claims, "exp", now, leeway, relative),
+        check_timestamp(claims, "nbf", now, leeway, relative),
+        check_timestamp(claims, "iat", now, leeway, relative),
+        check_issuer(claims, &expectations.issuer),
+        check_audience(claims, &expectations.audience),
+        check_subject(claims),
+    ];
+
+    let failures: Vec<String> = checks
+        .iter()
+        .filter(|check| check.status == ClaimStatus::Fail)
+        .map(|check| format!("{} {}", check.claim, check.message.to_lowercase()))
+        .collect();
+    let (acceptable, verdict) = if signature == Some(false) {
+        (
+            false,
+            "Not acceptable: the signature does not verify".to_string(),
+        )
+    } else if !failures.is_empty() {
+        (false, format!("Not acceptable: {}", failures.join("; ")))
+    } else if signature.is_none() {
+        (
+            true,
+            "Claims are acceptable now, but the signature has not been verified".to_string(),
+        )
+    } else {
+        (
+            true,
+            "Acceptable now: signature verified and all claim checks pass".to_string(),
+        )
+    };
+
+    ClaimReport {
+        checks,
+        acceptable,
+        verdict,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn claims(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    fn relative(_: i64) -> String {
+        "relative".to_string()
+    }
+
+    fn status(report: &ClaimReport, claim: &str) -> ClaimStatus {
+        report
+            .checks
+            .iter()
+            .find(|check| check.claim == claim)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_expiry_respects_leeway() {
+        let now = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let expectations = ClaimExpectations::default();
+
+        let report = validate(
+            &claims(json!({ "exp": 999_970 })),
+            &expectations,
+            now,
+            &relative,
+            Some(true),
+        );
+        assert_eq!(status(&report, "exp"), ClaimStatus::Warn);
+        assert!(report.acceptable);
+
+        let report = validate(
+            &claims(json!({ "exp": 999_900 })),
+            &expectations,
+            now,
+            &relative,
+            Some(true),
+        );
+        assert_eq!(status(&report, "exp"), ClaimStatus::Fail);
+        assert!(!report.acceptable);
+        assert!(report.verdict.contains("exp expired"));
+    }
+
+    #[test]
+    fn test_extreme_timestamps_do_not_overflow() {
+        let now = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let expectations = ClaimExpectations::default();
+
+        for exp in [json!(i64::MAX), json!(1e300)] {
+            let report = validate(
+                &claims(json!({ "exp": exp, "nbf": i64::MIN, "iat": i64::MIN })),
+                &expectations,
+                now,
+                &relative,
+                Some(true),
+            );
+            assert_eq!(status(&report, "exp"), ClaimStatus::Pass);
+            assert_eq!(status(&report, "nbf"), ClaimStatus::Pass);
+            assert!(report.acceptable);
+        }
+
+        let report = validate(
+            &claims(json!({ "exp": i64::MIN, "nbf": i64::MAX })),
+            &expectations,
+            now,
+            &relative,
+            Some(true),
+        );
+        assert_eq!(status(&report, "exp"), ClaimStatus::Fail);
+        assert_eq!(status(&report, "nbf"), ClaimStatus::Fail);
+    }
+
+    #[test]
+    fn test_not_before_and_issued_at_in_the_future() {
+        let now = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let report = validate(
+            &claims(json!({ "nbf": 1_000_500, "iat": 1_000_500.5 })),
+            &ClaimExpectations::default(),
+            now,
+            &relative,
+            None,
+        );
+        assert_eq!(status(&report, "nbf"), ClaimStatus::Fail);
+        assert_eq!(status(&report, "iat"), ClaimStatus::Fail);
+        assert!(!report.acceptable);
+    }
+
+    #[test]
+    fn test_issuer_and_audience_expectations() {
+        let now = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let expectations = ClaimExpectations {
+            issuer: "https://idp.example.com".to_string(),
+            audience: "api".to_string(),
+            leeway: 0,
+        };
+        let token = claims(json!({
+            "iss": "https://idp.example.com",
+            "aud": ["web", "api"],
+            "sub": "user-1",
+        }));
+        let report = validate(&token, &expectations, now, &relative, Some(true));
+        assert_eq!(status(&report, "iss"), ClaimStatus::Pass);
+        assert_eq!(status(&report, "aud"), ClaimStatus::Pass);
+        assert!(report.acceptable);
+
+        let token = claims(json!({ "iss": "https://evil.example.com", "aud": "web" }));
+        let report = validate(&token, &expectations, now, &relative, Some(true));
+        assert_eq!(status(&report, "iss"), ClaimStatus::Fail);
+        assert_eq!(status(&report, "aud"), ClaimStatus::Fail);
+        assert_eq!(status(&report, "sub"), ClaimStatus::Warn);
+    }
+
+    #[test]
+    fn test_bad_signature_is_never_acceptable() {
+        let now = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let report = validate(
+            &claims(json!({ "exp": 2_000_000 })),
+            &ClaimExpectations::default(),
+            now,
+            &relative,
+            Some(false),
+        );
+        assert!(!report.acceptable);
+        assert!(report.verdict.contains("signature"));
+    }
+}
@@ -0,0 +1,178 @@
+//! JOSE header editing (RFC 7515 section 4) for the encoder.
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::{Map, Value};
+
+use super::Algorithm;
+
+/// Registered header parameters that must be strings when present.
+const STRING_PARAMETERS: &[&str] = &["typ", "cty", "kid", "jku", "x5u", "x5t", "x5t#S256"];
+
+/// Registered header parameters, which may not be listed in `crit`.
+const REGISTERED_PARAMETERS: &[&str] = &[
+    "alg", "jku", "jwk", "kid", "x5u", "x5c", "x5t", "x5t#S256", "typ", "cty", "crit",
+];
+
+/// Default header for a new token signed with `algorithm`.
+pub fn default_header(algorithm: &Algorithm) -> String {
+    let mut header = Map::new();
+    header.insert("alg".to_string(), Value::String(algorithm.to_string()));
+    header.insert("typ".to_string(), Value::String("JWT".to_string()));
+    to_pretty(&header)
+}
+
+/// Pretty prints the raw header segment of a token, keeping custom parameters.
+pub fn decode_header(token: &str) -> Result<String> {
+    let segment = token
+        .trim()
+        .split('.')
+        .next()
+        .filter(|segment| !segment.is_empty())
+        .ok_or_else(|| anyhow!("Token has no header"))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment.trim_end_matches('='))
+        .map_err(|e| anyhow!("Header is not base64url: {}", e))?;
+    let header: Map<String, Value> = serde_json::from_slice(&bytes)
+        .map_err(|e| anyhow!("Header is not a JSON object: {}", e))?;
+    Ok(to_pretty(&header))
+}
+
+/// Rewrites the `alg` of a header JSON; anything that does not parse is left untouched.
+pub fn with_algorithm(header: &str, algorithm: &Algorithm) -> String {
+    match serde_json::from_str::<Map<String, Value>>(header) {
+        Ok(mut header) => {
+            header.insert("alg".to_string(), Value::String(algorithm.to_string()));
+            to_pretty(&header)
+        }
+        Err(_) => header.to_string(),
+    }
+}
+
+/// Parses and validates the header JSON before signing with `algorithm`. A missing `alg` is
+/// filled in, and `kid` is only set from `default_kid` when the header has none.
+pub fn prepare(
+    header: &str,
+    algorithm: &Algorithm,
+    default_kid: Option<String>,
+) -> Result<Map<String, Value>> {
+    let mut header: Map<String, Value> = if header.trim().is_empty() {
+        Map::new()
+    } else {
+        serde_json::from_str(header).map_err(|e| anyhow!("Header is not a JSON object: {}", e))?
+    };
+
+    match header.get("alg") {
+        None => {
+            header.insert("alg".to_string(), Value::String(algorithm.to_string()));
+        }
+        Some(Value::String(alg)) if *alg == algorithm.to_string() => {}
+        Some(Value::String(alg)) if alg.eq_ignore_ascii_case("none") => {
+            bail!("Header alg \"none\" produces unsigned tokens and is not supported")
+        }
+        Some(alg) => bail!(
+            "Header alg {} does not match the selected algorithm {}",
+            alg,
+            algorithm
+        ),
+    }
+
+    for name in STRING_PARAMETERS {
+        if let Some(value) = header.get(*name) {
+            if !value.is_string() {
+                bail!("Header parameter {} must be a string", name);
+            }
+        }
+    }
+    if let Some(x5c) = header.get("x5c") {
+        if !x5c
+            .as_array()
+            .is_some_and(|chain| chain.iter().all(Value::is_string))
+        {
+            bail!("Header parameter x5c must be an array of base64 certificates");
+        }
+    }
+    if header.get("jwk").is_some_and(|jwk| !jwk.is_object()) {
+        bail!("Header parameter jwk must be a JSON object");
+    }
+    if header.get("b64") == Some(&Value::Bool(false)) {
+        bail!("Unencoded payloads (b64: false) are not supported");
+    }
+    if let Some(crit) = header.get("crit") {
+        let names = crit
+            .as_array()
+            .filter(|names| !names.is_empty())
+            .ok_or_else(|| anyhow!("Header parameter crit must be a non-empty array"))?;
+        for name in names {
+            let name = name
+                .as_str()
+                .ok_or_else(|| anyhow!("Header parameter crit must list parameter names"))?;
+            if REGISTERED_PARAMETERS.contains(&name) {
+                bail!("Header crit may not list the registered parameter {}", name);
+            }
+            if !header.contains_key(name) {
+                bail!("Header crit lists {}, which is not in the header", name);
+            }
+        }
+    }
+
+    if let Some(kid) = default_kid {
+        header
+            .entry("kid".to_string())
+            .or_insert(Value::String(kid));
+    }
+    Ok(header)
+}
+
+fn to_pretty(header: &Map<String, Value>) -> String {
+    serde_json::to_string_pretty(header).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepare_keeps_custom_parameters() {
+        let header = prepare(
+            r#"{"typ":"at+jwt","x-tenant":"acme","crit":["x-tenant"]}"#,
+            &Algorithm::ES256,
+            Some("from-jwk".to_string()),
+        )
+        .unwrap();
+        assert_eq!(header["alg"], "ES256");
+        assert_eq!(header["typ"], "at+jwt");
+        assert_eq!(header["x-tenant"], "acme");
+        assert_eq!(header["kid"], "from-jwk");
+
+        let header = prepare(r#"{"kid":"mine"}"#, &Algorithm::HS256, Some("jwk".into())).unwrap();
+        assert_eq!(header["kid"], "mine");
+    }
+
+    #[test]
+    fn test_prepare_rejects_invalid_headers() {
+        for header in [
+            r#"{"alg":"RS256"}"#,
+            r#"{"alg":"none"}"#,
+            r#"{"kid":42}"#,
+            r#"{"crit":["exp"]}"#,
+            r#"{"crit":["alg"]}"#,
+            r#"{"x5c":"MIIB"}"#,
+            r#"["alg"]"#,
+        ] {
+            assert!(
+                prepare(header, &Algorithm::HS256, None).is_err(),
+                "{}",
+                header
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_algorithm_round_trip() {
+        let header = with_algorithm(r#"{"alg":"HS256","cty":"JWT"}"#, &Algorithm::EdDSA);
+        let header: Map<String, Value> = serde_json::from_str(&header).unwrap();
+        assert_eq!(header["alg"], "EdDSA");
+        assert_eq!(header["cty"], "JWT");
+    }
+}
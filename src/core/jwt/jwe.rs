@@ -0,0 +1,733 @@
+//! JSON Web Encryption (RFC 7516) in compact serialization with the key management and content
+//! encryption algorithms of RFC 7518.
+
+use std::{fmt, io::Read};
+
+use aes::{Aes128, Aes192, Aes256};
+use aes_kw::{KekAes128, KekAes192, KekAes256};
+use anyhow::{anyhow, bail, Result};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use cbc::{Decryptor, Encryptor};
+use cipher::{
+    block_padding::Pkcs7, BlockCipher, BlockDecryptMut, BlockEncryptMut, KeyInit, KeyIvInit,
+};
+use rand::{thread_rng, RngCore};
+use ring::{aead, hmac};
+use rsa::{
+    pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey},
+    pkcs8::{DecodePrivateKey, DecodePublicKey},
+    Oaep, Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey,
+};
+use serde_json::{Map, Value};
+use subtle::ConstantTimeEq;
+
+use super::jwk;
+use crate::core::asn1::pem::decode_pem;
+
+/// Key management algorithm, the JWE `alg`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeyManagement {
+    /// The shared key is the content encryption key
+    Dir,
+    /// RSAES OAEP using SHA-1 and MGF1 with SHA-1
+    RsaOaep,
+    /// RSAES OAEP using SHA-256 and MGF1 with SHA-256
+    #[default]
+    RsaOaep256,
+    /// RSAES-PKCS1-v1_5
+    Rsa1_5,
+    /// AES Key Wrap with a 128-bit key
+    A128Kw,
+    /// AES Key Wrap with a 192-bit key
+    A192Kw,
+    /// AES Key Wrap with a 256-bit key
+    A256Kw,
+}
+
+impl fmt::Display for KeyManagement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            KeyManagement::Dir => "dir",
+            KeyManagement::RsaOaep => "RSA-OAEP",
+            KeyManagement::RsaOaep256 => "RSA-OAEP-256",
+            KeyManagement::Rsa1_5 => "RSA1_5",
+            KeyManagement::A128Kw => "A128KW",
+            KeyManagement::A192Kw => "A192KW",
+            KeyManagement::A256Kw => "A256KW",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl KeyManagement {
+    pub fn variants() -> &'static [KeyManagement] {
+        &[
+            KeyManagement::Dir,
+            KeyManagement::RsaOaep,
+            KeyManagement::RsaOaep256,
+            KeyManagement::Rsa1_5,
+            KeyManagement::A128Kw,
+            KeyManagement::A192Kw,
+            KeyManagement::A256Kw,
+        ]
+    }
+
+    fn from_name(name: &str) -> Result<Self> {
+        Self::variants()
+            .iter()
+            .find(|alg| alg.to_string() == name)
+            .copied()
+            .ok_or_else(|| anyhow!("Unsupported JWE key management algorithm {}", name))
+    }
+
+    /// Whether the key is an RSA key pair rather than a shared symmetric key.
+    pub fn is_rsa(&self) -> bool {
+        matches!(
+            self,
+            KeyManagement::RsaOaep | KeyManagement::RsaOaep256 | KeyManagement::Rsa1_5
+        )
+    }
+
+    fn wrap_key_len(&self) -> Option<usize> {
+        match self {
+            KeyManagement::A128Kw => Some(16),
+            KeyManagement::A192Kw => Some(24),
+            KeyManagement::A256Kw => Some(32),
+            _ => None,
+        }
+    }
+}
+
+/// Content encryption algorithm, the JWE `enc`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncryption {
+    /// AES-GCM using a 128-bit key
+    A128Gcm,
+    /// AES-GCM using a 256-bit key
+    #[default]
+    A256Gcm,
+    /// AES-CBC with HMAC SHA-256
+    A128CbcHs256,
+    /// AES-CBC with HMAC SHA-384
+    A192CbcHs384,
+    /// AES-CBC with HMAC SHA-512
+    A256CbcHs512,
+}
+
+impl fmt::Display for ContentEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ContentEncryption::A128Gcm => "A128GCM",
+            ContentEncryption::A256Gcm => "A256GCM",
+            ContentEncryption::A128CbcHs256 => "A128CBC-HS256",
+            ContentEncryption::A192CbcHs384 => "A192CBC-HS384",
+            ContentEncryption::A256CbcHs512 => "A256CBC-HS512",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ContentEncryption {
+    pub fn variants() -> &'static [ContentEncryption] {
+        &[
+            ContentEncryption::A128Gcm,
+            ContentEncryption::A256Gcm,
+            ContentEncryption::A128CbcHs256,
+            ContentEncryption::A192CbcHs384,
+            ContentEncryption::A256CbcHs512,
+        ]
+    }
+
+    fn from_name(name: &str) -> Result<Self> {
+        Self::variants()
+            .iter()
+            .find(|enc| enc.to_string() == name)
+            .copied()
+            .ok_or_else(|| anyhow!("Unsupported JWE content encryption {}", name))
+    }
+
+    /// Length of the content encryption key in bytes.
+    pub fn key_len(&self) -> usize {
+        match self {
+            ContentEncryption::A128Gcm => 16,
+            ContentEncryption::A256Gcm | ContentEncryption::A128CbcHs256 => 32,
+            ContentEncryption::A192CbcHs384 => 48,
+            ContentEncryption::A256CbcHs512 => 64,
+        }
+    }
+
+    fn iv_len(&self) -> usize {
+        match self {
+            ContentEncryption::A128Gcm | ContentEncryption::A256Gcm => 12,
+            _ => 16,
+        }
+    }
+
+    fn encrypt(&self, cek: &[u8], iv: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Encrypted> {
+        match self {
+            ContentEncryption::A128Gcm | ContentEncryption::A256Gcm => {
+                let key = self.gcm_key(cek)?;
+                let nonce = aead::Nonce::try_assume_unique_for_key(iv)
+                    .map_err(|_| anyhow!("Invalid GCM IV"))?;
+                let mut ciphertext = plaintext.to_vec();
+                let tag = key
+                    .seal_in_place_separate_tag(nonce, aead::Aad::from(aad), &mut ciphertext)
+                    .map_err(|_| anyhow!("AES-GCM encryption failed"))?;
+                Ok(Encrypted {
+                    ciphertext,
+                    tag: tag.as_ref().to_vec(),
+                })
+            }
+            _ => {
+                let (mac_key, enc_key) = cek.split_at(cek.len() / 2);
+                let ciphertext = match enc_key.len() {
+                    16 => cbc_encrypt::<Aes128>(enc_key, iv, plaintext)?,
+                    24 => cbc_encrypt::<Aes192>(enc_key, iv, plaintext)?,
+                    _ => cbc_encrypt::<Aes256>(enc_key, iv, plaintext)?,
+                };
+                let tag = self.cbc_tag(mac_key, aad, iv, &ciphertext);
+                Ok(Encrypted { ciphertext, tag })
+            }
+        }
+    }
+
+    fn decrypt(
+        &self,
+        cek: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> Result<Vec<u8>> {
+        if cek.len() != self.key_len() {
+            bail!(
+                "{} needs a {}-byte content encryption key, got {}",
+                self,
+                self.key_len(),
+                cek.len()
+            );
+        }
+        match self {
+            ContentEncryption::A128Gcm | ContentEncryption::A256Gcm => {
+                let key = self.gcm_key(cek)?;
+                let nonce = aead::Nonce::try_assume_unique_for_key(iv)
+                    .map_err(|_| anyhow!("Invalid GCM IV"))?;
+                let mut in_out = [ciphertext, tag].concat();
+                let plaintext = key
+                    .open_in_place(nonce, aead::Aad::from(aad), &mut in_out)
+                    .map_err(|_| anyhow!("Decryption failed: wrong key or tampered token"))?;
+                Ok(plaintext.to_vec())
+            }
+            _ => {
+                let (mac_key, enc_key) = cek.split_at(cek.len() / 2);
+                let expected = self.cbc_tag(mac_key, aad, iv, ciphertext);
+                if !bool::from(expected.as_slice().ct_eq(tag)) {
+                    bail!("Authentication tag mismatch: wrong key or tampered token");
+                }
+                match enc_key.len() {
+                    16 => cbc_decrypt::<Aes128>(enc_key, iv, ciphertext),
+                    24 => cbc_decrypt::<Aes192>(enc_key, iv, ciphertext),
+                    _ => cbc_decrypt::<Aes256>(enc_key, iv, ciphertext),
+                }
+            }
+        }
+    }
+
+    fn gcm_key(&self, cek: &[u8]) -> Result<aead::LessSafeKey> {
+        let algorithm = match self {
+            ContentEncryption::A128Gcm => &aead::AES_128_GCM,
+            _ => &aead::AES_256_GCM,
+        };
+        let key =
+            aead::UnboundKey::new(algorithm, cek).map_err(|_| anyhow!("Invalid {} key", self))?;
+        Ok(aead::LessSafeKey::new(key))
+    }
+
+    /// RFC 7518 section 5.2.2.1: HMAC over AAD, IV, ciphertext and the AAD bit length,
+    /// truncated to half.
+    fn cbc_tag(&self, mac_key: &[u8], aad: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let algorithm = match self {
+            ContentEncryption::A192CbcHs384 => hmac::HMAC_SHA384,
+            ContentEncryption::A256CbcHs512 => hmac::HMAC_SHA512,
+            _ => hmac::HMAC_SHA256,
+        };
+        let key = hmac::Key::new(algorithm, mac_key);
+        let mut context = hmac::Context::with_key(&key);
+        context.update(aad);
+        context.update(iv);
+        context.update(ciphertext);
+        context.update(&((aad.len() as u64) * 8).to_be_bytes());
+        let tag = context.sign();
+        tag.as_ref()[..mac_key.len()].to_vec()
+    }
+}
+
+struct Encrypted {
+    ciphertext: Vec<u8>,
+    tag: Vec<u8>,
+}
+
+fn cbc_encrypt<C>(key: &[u8], iv: &[u8], plaintext: &[u8]) -> Result<Vec<u8>>
+where
+    C: BlockEncryptMut + BlockCipher + KeyInit,
+{
+    let encryptor = Encryptor::<C>::new_from_slices(key, iv)
+        .map_err(|e| anyhow!("Invalid AES-CBC key or IV: {}", e))?;
+    Ok(encryptor.encrypt_padded_vec_mut::<Pkcs7>(plaintext))
+}
+
+fn cbc_decrypt<C>(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>>
+where
+    C: BlockDecryptMut + BlockCipher + KeyInit,
+{
+    let decryptor = Decryptor::<C>::new_from_slices(key, iv)
+        .map_err(|e| anyhow!("Invalid AES-CBC key or IV: {}", e))?;
+    decryptor
+        .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+        .map_err(|_| anyhow!("Invalid padding"))
+}
+
+/// RFC 3394 AES Key Wrap.
+pub fn aes_key_wrap(kek: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    match kek.len() {
+        16 => KekAes128::try_from(kek).and_then(|kek| kek.wrap_vec(key)),
+        24 => KekAes192::try_from(kek).and_then(|kek| kek.wrap_vec(key)),
+        32 => KekAes256::try_from(kek).and_then(|kek| kek.wrap_vec(key)),
+        len => bail!("AES key wrap needs a 16, 24 or 32-byte key, got {}", len),
+    }
+    .map_err(|e| anyhow!("Key wrap failed: {}", e))
+}
+
+/// RFC 3394 AES Key Unwrap, checking the integrity value.
+pub fn aes_key_unwrap(kek: &[u8], wrapped: &[u8]) -> Result<Vec<u8>> {
+    match kek.len() {
+        16 => KekAes128::try_from(kek).and_then(|kek| kek.unwrap_vec(wrapped)),
+        24 => KekAes192::try_from(kek).and_then(|kek| kek.unwrap_vec(wrapped)),
+        32 => KekAes256::try_from(kek).and_then(|kek| kek.unwrap_vec(wrapped)),
+        len => bail!("AES key wrap needs a 16, 24 or 32-byte key, got {}", len),
+    }
+    .map_err(|e| anyhow!("Key unwrap failed: {}", e))
+}
+
+/// Keys and algorithms of the JWE section, with the last decryption result.
+#[derive(Debug, Default, Clone)]
+pub struct JweSettings {
+    pub algorithm: KeyManagement,
+    pub encryption: ContentEncryption,
+    /// RSA key (a public key is enough to encrypt) or shared symmetric key
+    pub key: String,
+    /// Sign the payload as a JWS before encrypting it
+    pub nested: bool,
+    /// Header of the last JWE decoded, kept even when decryption fails
+    pub header: Option<ProtectedHeader>,
+    pub decrypted: Option<Decrypted>,
+}
+
+/// Whether the token is in JWE compact serialization, which has five parts.
+pub fn is_jwe(token: &str) -> bool {
+    token.trim().split('.').count() == 5
+}
+
+/// Protected header of a JWE, which can be read without the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtectedHeader {
+    /// The JWE the header was read from
+    pub token: String,
+    pub algorithm: KeyManagement,
+    pub encryption: ContentEncryption,
+    /// The header as pretty-printed JSON
+    pub json: String,
+    members: Map<String, Value>,
+}
+
+/// Result of decrypting a JWE.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decrypted {
+    /// The JWE that was decrypted
+    pub token: String,
+    pub algorithm: KeyManagement,
+    pub encryption: ContentEncryption,
+    pub plaintext: String,
+    /// Signed token carried by a nested JWT
+    pub nested: Option<String>,
+}
+
+fn base64url_part(part: &str, name: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|e| anyhow!("Invalid base64url in the JWE {}: {}", name, e))
+}
+
+/// Upper bound on a `zip: "DEF"` payload once inflated, so a tiny token can't expand
+/// into gigabytes.
+const MAX_INFLATED_LEN: u64 = 1024 * 1024;
+
+/// Reads the protected header of a compact JWE.
+pub fn parse_header(token: &str) -> Result<ProtectedHeader> {
+    let token = token.trim();
+    if !is_jwe(token) {
+        bail!("A compact JWE has five dot-separated parts");
+    }
+    let protected = token.split('.').next().unwrap_or_default();
+    let members: Map<String, Value> = serde_json::from_slice(&base64url_part(protected, "header")?)
+        .map_err(|e| anyhow!("JWE header is not a JSON object: {}", e))?;
+    let member = |name: &str| {
+        members
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("JWE header has no \"{}\"", name))
+    };
+    Ok(ProtectedHeader {
+        token: token.to_string(),
+        algorithm: KeyManagement::from_name(member("alg")?)?,
+        encryption: ContentEncryption::from_name(member("enc")?)?,
+        json: serde_json::to_string_pretty(&members)?,
+        members,
+    })
+}
+
+/// Decrypts a compact JWE with an RSA private key or a shared symmetric key.
+pub fn decrypt(token: &str, key: &str) -> Result<Decrypted> {
+    let ProtectedHeader {
+        token,
+        algorithm,
+        encryption,
+        members: header,
+        ..
+    } = parse_header(token)?;
+    let parts: Vec<&str> = token.split('.').collect();
+    let [protected, encrypted_key, iv, ciphertext, tag] = parts[..] else {
+        bail!("A compact JWE has five dot-separated parts");
+    };
+    if key.trim().is_empty() {
+        bail!("A decryption key is required");
+    }
+
+    let encrypted_key = base64url_part(encrypted_key, "encrypted key")?;
+    let cek = match algorithm {
+        KeyManagement::Dir => {
+            if !encrypted_key.is_empty() {
+                bail!("dir tokens must have an empty encrypted key");
+            }
+            symmetric_key(key, encryption.key_len())?
+        }
+        KeyManagement::RsaOaep => rsa_private_key(key)?
+            .decrypt(Oaep::new::<sha1::Sha1>(), &encrypted_key)
+            .map_err(|e| anyhow!("RSA-OAEP decryption failed: {}", e))?,
+        KeyManagement::RsaOaep256 => rsa_private_key(key)?
+            .decrypt(Oaep::new::<sha2::Sha256>(), &encrypted_key)
+            .map_err(|e| anyhow!("RSA-OAEP-256 decryption failed: {}", e))?,
+        KeyManagement::Rsa1_5 => rsa_private_key(key)?
+            .decrypt(Pkcs1v15Encrypt, &encrypted_key)
+            .map_err(|e| anyhow!("RSA1_5 decryption failed: {}", e))?,
+        KeyManagement::A128Kw | KeyManagement::A192Kw | KeyManagement::A256Kw => {
+            let kek = symmetric_key(key, algorithm.wrap_key_len().unwrap_or_default())?;
+            aes_key_unwrap(&kek, &encrypted_key)?
+        }
+    };
+
+    let mut plaintext = encryption.decrypt(
+        &cek,
+        &base64url_part(iv, "IV")?,
+        protected.as_bytes(),
+        &base64url_part(ciphertext, "ciphertext")?,
+        &base64url_part(tag, "tag")?,
+    )?;
+    match header.get("zip").and_then(Value::as_str) {
+        None => {}
+        Some("DEF") => {
+            let mut inflated = Vec::new();
+            flate2::read::DeflateDecoder::new(plaintext.as_slice())
+                .take(MAX_INFLATED_LEN + 1)
+                .read_to_end(&mut inflated)
+                .map_err(|e| anyhow!("Failed to inflate the payload: {}", e))?;
+            if inflated.len() as u64 > MAX_INFLATED_LEN {
+                bail!("Inflated payload is larger than {} bytes", MAX_INFLATED_LEN);
+            }
+            plaintext = inflated;
+        }
+        Some(other) => bail!("Unsupported JWE compression {}", other),
+    }
+    let plaintext =
+        String::from_utf8(plaintext).map_err(|_| anyhow!("Decrypted payload is not UTF-8"))?;
+
+    let cty_is_jwt = header
+        .get("cty")
+        .and_then(Value::as_str)
+        .is_some_and(|cty| cty.eq_ignore_ascii_case("JWT"));
+    let nested = (cty_is_jwt || plaintext.trim().split('.').count() == 3)
+        .then(|| plaintext.trim().to_string());
+
+    Ok(Decrypted {
+        token,
+        algorithm,
+        encryption,
+        plaintext,
+        nested,
+    })
+}
+
+/// Encrypts `payload` into a compact JWE. `key` is an RSA public key or a shared symmetric key;
+/// `cty` marks a nested JWT.
+pub fn encrypt(
+    payload: &[u8],
+    algorithm: KeyManagement,
+    encryption: ContentEncryption,
+    key: &str,
+    cty: Option<&str>,
+) -> Result<String> {
+    if key.trim().is_empty() {
+        bail!("An encryption key is required");
+    }
+
+    let mut header = Map::new();
+    header.insert("alg".into(), algorithm.to_string().into());
+    header.insert("enc".into(), encryption.to_string().into());
+    if let Some(cty) = cty {
+        header.insert("cty".into(), cty.into());
+    }
+    if jwk::is_jwk(key) {
+        if let Some(kid) = jwk::jwk_object(key)?.get("kid") {
+            header.insert("kid".into(), kid.clone());
+        }
+    }
+
+    let mut rng = thread_rng();
+    let (cek, encrypted_key) = if algorithm == KeyManagement::Dir {
+        (symmetric_key(key, encryption.key_len())?, Vec::new())
+    } else {
+        let mut cek = vec![0u8; encryption.key_len()];
+        rng.fill_bytes(&mut cek);
+        let encrypted_key = match algorithm {
+            KeyManagement::RsaOaep => rsa_public_key(key)?
+                .encrypt(&mut rng, Oaep::new::<sha1::Sha1>(), &cek)
+                .map_err(|e| anyhow!("RSA-OAEP encryption failed: {}", e))?,
+            KeyManagement::RsaOaep256 => rsa_public_key(key)?
+                .encrypt(&mut rng, Oaep::new::<sha2::Sha256>(), &cek)
+                .map_err(|e| anyhow!("RSA-OAEP-256 encryption failed: {}", e))?,
+            KeyManagement::Rsa1_5 => rsa_public_key(key)?
+                .encrypt(&mut rng, Pkcs1v15Encrypt, &cek)
+                .map_err(|e| anyhow!("RSA1_5 encryption failed: {}", e))?,
+            _ => {
+                let kek = symmetric_key(key, algorithm.wrap_key_len().unwrap_or_default())?;
+                aes_key_wrap(&kek, &cek)?
+            }
+        };
+        (cek, encrypted_key)
+    };
+
+    let mut iv = vec![0u8; encryption.iv_len()];
+    rng.fill_bytes(&mut iv);
+    let protected = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?);
+    let encrypted = encryption.encrypt(&cek, &iv, protected.as_bytes(), payload)?;
+
+    Ok([
+        protected,
+        URL_SAFE_NO_PAD.encode(encrypted_key),
+        URL_SAFE_NO_PAD.encode(iv),
+        URL_SAFE_NO_PAD.encode(encrypted.ciphertext),
+        URL_SAFE_NO_PAD.encode(encrypted.tag),
+    ]
+    .join("."))
+}
+
+/// Shared key of `len` bytes from an oct JWK, hex, base64url or base64, or raw text.
+pub fn symmetric_key(input: &str, len: usize) -> Result<Vec<u8>> {
+    if jwk::is_jwk(input) {
+        let jwk = jwk::jwk_object(input)?;
+        let key = jwk::base64url_member(&jwk, "k")?;
+        if key.len() != len {
+            bail!("JWK key is {} bytes, expected {}", key.len(), len);
+        }
+        return Ok(key);
+    }
+
+    let input = input.trim();
+    let candidates = [
+        hex::decode(input).ok(),
+        URL_SAFE_NO_PAD.decode(input.trim_end_matches('=')).ok(),
+        STANDARD.decode(input).ok(),
+        Some(input.as_bytes().to_vec()),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|key| key.len() == len)
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected a {}-byte key as hex, base64url, an oct JWK or {} characters",
+                len,
+                len
+            )
+        })
+}
+
+fn rsa_private_key(input: &str) -> Result<RsaPrivateKey> {
+    if jwk::is_jwk(input) {
+        return jwk::rsa_private_key(&jwk::jwk_object(input)?);
+    }
+    let block = decode_pem(input)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Expected an RSA private key PEM or JWK"))?;
+    match block.label.as_str() {
+        "RSA PRIVATE KEY" => RsaPrivateKey::from_pkcs1_der(&block.der)
+            .map_err(|e| anyhow!("Invalid RSA private key: {}", e)),
+        "PRIVATE KEY" => RsaPrivateKey::from_pkcs8_der(&block.der)
+            .map_err(|e| anyhow!("Invalid RSA private key: {}", e)),
+        other => bail!("Expected an RSA private key, found {}", other),
+    }
+}
+
+fn rsa_public_key(input: &str) -> Result<RsaPublicKey> {
+    if jwk::is_jwk(input) {
+        return jwk::rsa_public_key(&jwk::jwk_object(input)?);
+    }
+    let block = decode_pem(input)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Expected an RSA public key PEM or JWK"))?;
+    match block.label.as_str() {
+        "PUBLIC KEY" => RsaPublicKey::from_public_key_der(&block.der)
+            .map_err(|e| anyhow!("Invalid RSA public key: {}", e)),
+        "RSA PUBLIC KEY" => RsaPublicKey::from_pkcs1_der(&block.der)
+            .map_err(|e| anyhow!("Invalid RSA public key: {}", e)),
+        "RSA PRIVATE KEY" | "PRIVATE KEY" => Ok(rsa_private_key(input)?.to_public_key()),
+        other => bail!("Expected an RSA public key, found {}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3394_key_wrap() {
+        let kek = hex::decode("000102030405060708090A0B0C0D0E0F").unwrap();
+        let key = hex::decode("00112233445566778899AABBCCDDEEFF").unwrap();
+        let wrapped = aes_key_wrap(&kek, &key).unwrap();
+        assert_eq!(
+            hex::encode_upper(&wrapped),
+            "1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5"
+        );
+        assert_eq!(aes_key_unwrap(&kek, &wrapped).unwrap(), key);
+        assert!(aes_key_unwrap(&key, &wrapped).is_err());
+    }
+
+    #[test]
+    fn test_symmetric_round_trips() {
+        let payload = br#"{"sub":"1234567890"}"#;
+        for (algorithm, encryption, key) in [
+            (
+                KeyManagement::Dir,
+                ContentEncryption::A128CbcHs256,
+                "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            ),
+            (
+                KeyManagement::Dir,
+                ContentEncryption::A256Gcm,
+                r#"{"kty":"oct","k":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8"}"#,
+            ),
+            (
+                KeyManagement::A128Kw,
+                ContentEncryption::A256CbcHs512,
+                "GawgguFyGrWKav7AX4VKUg",
+            ),
+        ] {
+            let token = encrypt(payload, algorithm, encryption, key, None).unwrap();
+            let decrypted = decrypt(&token, key).unwrap();
+            assert_eq!(decrypted.plaintext.as_bytes(), payload);
+            assert_eq!(decrypted.encryption, encryption);
+            assert!(decrypted.nested.is_none());
+        }
+    }
+
+    #[test]
+    fn test_rsa_oaep_round_trip_and_tampering() {
+        let token = encrypt(
+            b"eyJhbGciOiJIUzI1NiJ9.e30.c2ln",
+            KeyManagement::RsaOaep256,
+            ContentEncryption::A256Gcm,
+            include_str!("testdata/rsa.pub.pem"),
+            Some("JWT"),
+        )
+        .unwrap();
+        let decrypted = decrypt(&token, include_str!("testdata/rsa.pem")).unwrap();
+        assert_eq!(decrypted.algorithm, KeyManagement::RsaOaep256);
+        assert_eq!(
+            decrypted.nested.as_deref(),
+            Some("eyJhbGciOiJIUzI1NiJ9.e30.c2ln")
+        );
+
+        let mut parts: Vec<String> = token.split('.').map(str::to_string).collect();
+        parts[3] = URL_SAFE_NO_PAD.encode(b"tampered ciphertext");
+        assert!(decrypt(&parts.join("."), include_str!("testdata/rsa.pem")).is_err());
+    }
+
+    #[test]
+    fn test_rfc7516_rsa_oaep_a256gcm() {
+        // RFC 7516 Appendix A.1
+        let token = "eyJhbGciOiJSU0EtT0FFUCIsImVuYyI6IkEyNTZHQ00ifQ.\
+            OKOawDo13gRp2ojaHV7LFpZcgV7T6DVZKTyKOMTYUmKoTCVJRgckCL9kiMT03JGeipsEdY3mx_etLbbWSrFr05kLzc\
+            Sr4qKAq7YN7e9jwQRb23nfa6c9d-StnImGyFDbSv04uVuxIp5Zms1gNxKKK2Da14B8S4rzVRltdYwam_lDp5XnZAYp\
+            Qdb76FdIKLaVmqgfwX7XWRxv2322i-vDxRfqNzo_tETKzpVLzfiwQyeyPGLBIO56YJ7eObdv0je81860ppamavo35Ug\
+            oRdbYaBcoh9QcfylQr66oc6vFWXRcZ_ZT2LawVCWTIy3brGPi6UklfCpIMfIjf7iGdXKHzg.\
+            48V1_ALb6US04U3b.\
+            5eym8TW_c8SuK0ltJ3rpYIzOeDQz7TALvtu6UG9oMo4vpzs9tX_EFShS8iB7j6jiSdiwkIr3ajwQzaBtQD_A.\
+            XFBoMYUZodetZdvTiFvSkQ";
+        let decrypted = decrypt(token, include_str!("testdata/rfc7516-a1.jwk.json")).unwrap();
+        assert_eq!(decrypted.algorithm, KeyManagement::RsaOaep);
+        assert_eq!(decrypted.encryption, ContentEncryption::A256Gcm);
+        assert_eq!(
+            decrypted.plaintext,
+            "The true sign of intelligence is not knowledge but imagination."
+        );
+    }
+
+    #[test]
+    fn test_rfc7516_a128kw_a128cbc_hs256() {
+        // RFC 7516 Appendix A.3
+        let token = "eyJhbGciOiJBMTI4S1ciLCJlbmMiOiJBMTI4Q0JDLUhTMjU2In0.\
+            6KB707dM9YTIgHtLvtgWQ8mKwboJW3of9locizkDTHzBC2IlrT1oOQ.\
+            AxY8DCtDaGlsbGljb3RoZQ.\
+            KDlTtXchhZTGufMYmOYGS4HffxPSUrfmqCHXaI9wOGY.\
+            U0m_YmjN04DJvceFICbCVQ";
+        let key = r#"{"kty":"oct","k":"GawgguFyGrWKav7AX4VKUg"}"#;
+        let decrypted = decrypt(token, key).unwrap();
+        assert_eq!(decrypted.algorithm, KeyManagement::A128Kw);
+        assert_eq!(decrypted.encryption, ContentEncryption::A128CbcHs256);
+        assert_eq!(decrypted.plaintext, "Live long and prosper.");
+    }
+
+    #[test]
+    fn test_inflated_payload_is_capped() {
+        let key = "0123456789abcdef0123456789abcdef";
+        let token = |len: usize| {
+            let mut deflater =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+            std::io::Write::write_all(&mut deflater, &vec![b'a'; len]).unwrap();
+            let compressed = deflater.finish().unwrap();
+            let protected = URL_SAFE_NO_PAD.encode(r#"{"alg":"dir","enc":"A256GCM","zip":"DEF"}"#);
+            let iv = [0u8; 12];
+            let encrypted = ContentEncryption::A256Gcm
+                .encrypt(key.as_bytes(), &iv, protected.as_bytes(), &compressed)
+                .unwrap();
+            [
+                protected,
+                String::new(),
+                URL_SAFE_NO_PAD.encode(iv),
+                URL_SAFE_NO_PAD.encode(encrypted.ciphertext),
+                URL_SAFE_NO_PAD.encode(encrypted.tag),
+            ]
+            .join(".")
+        };
+
+        let decrypted = decrypt(&token(1000), key).unwrap();
+        assert_eq!(decrypted.plaintext.len(), 1000);
+        let err = decrypt(&token(MAX_INFLATED_LEN as usize + 1), key).unwrap_err();
+        assert!(err.to_string().contains("larger than"));
+    }
+}
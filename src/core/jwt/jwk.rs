@@ -0,0 +1,499 @@
+//! JSON Web Keys (RFC 7517): key selection from JWKS documents, signing with private JWKs and
+//! PEM to JWK export with RFC 7638 thumbprints.
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    jwk::{AlgorithmParameters, Jwk, JwkSet},
+    DecodingKey, EncodingKey, Header,
+};
+use ring::{
+    rand::SystemRandom,
+    signature::{
+        EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING,
+        ECDSA_P384_SHA384_FIXED_SIGNING,
+    },
+};
+use rsa::{
+    pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey, EncodeRsaPrivateKey},
+    pkcs8::{DecodePrivateKey, PrivateKeyInfo},
+    traits::PublicKeyParts,
+    BigUint, RsaPrivateKey, RsaPublicKey,
+};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use x509_parser::{prelude::FromDer, public_key::PublicKey, x509::SubjectPublicKeyInfo};
+
+use super::Algorithm;
+use crate::core::asn1::pem::decode_pem;
+
+const OID_RSA: &str = "1.2.840.113549.1.1.1";
+const OID_EC: &str = "1.2.840.10045.2.1";
+const OID_ED25519: &str = "1.3.101.112";
+const OID_P256: &str = "1.2.840.10045.3.1.7";
+const OID_P384: &str = "1.3.132.0.34";
+
+/// Whether a key field holds JWK or JWKS JSON rather than PEM.
+pub fn is_jwk(input: &str) -> bool {
+    input.trim_start().starts_with('{')
+}
+
+/// Keys of a JWKS document, or a single JWK.
+pub fn parse_keys(input: &str) -> Result<Vec<Jwk>> {
+    let value: Value =
+        serde_json::from_str(input).map_err(|e| anyhow!("Invalid JWK JSON: {}", e))?;
+    if value.get("keys").is_some() {
+        let set: JwkSet =
+            serde_json::from_value(value).map_err(|e| anyhow!("Invalid JWKS: {}", e))?;
+        Ok(set.keys)
+    } else {
+        let jwk: Jwk = serde_json::from_value(value).map_err(|e| anyhow!("Invalid JWK: {}", e))?;
+        Ok(vec![jwk])
+    }
+}
+
+fn fits_algorithm(jwk: &Jwk, algorithm: &Algorithm) -> bool {
+    let family_matches = match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => matches!(
+            algorithm,
+            Algorithm::RS256
+                | Algorithm::RS384
+                | Algorithm::RS512
+                | Algorithm::PS256
+                | Algorithm::PS384
+                | Algorithm::PS512
+        ),
+        AlgorithmParameters::EllipticCurve(_) => {
+            matches!(algorithm, Algorithm::ES256 | Algorithm::ES384)
+        }
+        AlgorithmParameters::OctetKeyPair(_) => *algorithm == Algorithm::EdDSA,
+        AlgorithmParameters::OctetKey(_) => algorithm.is_hmac(),
+    };
+    let declared = jwk
+        .common
+        .key_algorithm
+        .map(|declared| format!("{:?}", declared) == algorithm.to_string())
+        .unwrap_or(true);
+    family_matches && declared
+}
+
+/// Verification keys for a token: the key named by the header `kid`, otherwise every key
+/// usable with the token algorithm. Each key comes with its `kid`, if any.
+pub fn select_decoding_keys(
+    input: &str,
+    header: &Header,
+    algorithm: &Algorithm,
+) -> Result<Vec<(Option<String>, DecodingKey)>> {
+    let keys = parse_keys(input)?;
+    let candidates: Vec<&Jwk> = match &header.kid {
+        Some(kid) => {
+            let jwk = keys
+                .iter()
+                .find(|jwk| jwk.common.key_id.as_deref() == Some(kid.as_str()))
+                .ok_or_else(|| anyhow!("No key with kid \"{}\" in the JWKS", kid))?;
+            vec![jwk]
+        }
+        None => keys
+            .iter()
+            .filter(|jwk| fits_algorithm(jwk, algorithm))
+            .collect(),
+    };
+    if candidates.is_empty() {
+        bail!("No key in the JWKS can verify {} tokens", algorithm);
+    }
+
+    candidates
+        .into_iter()
+        .map(|jwk| {
+            let key = DecodingKey::from_jwk(jwk)
+                .map_err(|e| anyhow!("Unusable JWK {:?}: {}", jwk.common.key_id, e))?;
+            Ok((jwk.common.key_id.clone(), key))
+        })
+        .collect()
+}
+
+fn member<'a>(jwk: &'a Map<String, Value>, name: &str) -> Result<&'a str> {
+    jwk.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("JWK is missing the \"{}\" member", name))
+}
+
+pub(super) fn base64url_member(jwk: &Map<String, Value>, name: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(member(jwk, name)?)
+        .map_err(|e| anyhow!("Invalid base64url in \"{}\": {}", name, e))
+}
+
+/// Single JWK object from a key field.
+pub(super) fn jwk_object(input: &str) -> Result<Map<String, Value>> {
+    let value: Value =
+        serde_json::from_str(input).map_err(|e| anyhow!("Invalid JWK JSON: {}", e))?;
+    match value {
+        Value::Object(jwk) if !jwk.contains_key("keys") => Ok(jwk),
+        Value::Object(_) => bail!("Expected a single JWK, not a JWKS"),
+        _ => bail!("A JWK must be a JSON object"),
+    }
+}
+
+pub(super) fn rsa_public_key(jwk: &Map<String, Value>) -> Result<RsaPublicKey> {
+    let component = |name| base64url_member(jwk, name).map(|b| BigUint::from_bytes_be(&b));
+    RsaPublicKey::new(component("n")?, component("e")?)
+        .map_err(|e| anyhow!("Invalid RSA JWK: {}", e))
+}
+
+pub(super) fn rsa_private_key(jwk: &Map<String, Value>) -> Result<RsaPrivateKey> {
+    let component = |name| base64url_member(jwk, name).map(|b| BigUint::from_bytes_be(&b));
+    RsaPrivateKey::from_components(
+        component("n")?,
+        component("e")?,
+        component("d")?,
+        vec![component("p")?, component("q")?],
+    )
+    .map_err(|e| anyhow!("Invalid RSA JWK: {}", e))
+}
+
+/// Signing key from a private JWK, with the JWK `kid` to put in the token header.
+pub fn encoding_key_from_jwk(input: &str) -> Result<(EncodingKey, Option<String>)> {
+    let value: Value =
+        serde_json::from_str(input).map_err(|e| anyhow!("Invalid JWK JSON: {}", e))?;
+    let jwk = value
+        .as_object()
+        .ok_or_else(|| anyhow!("A JWK must be a JSON object"))?;
+    if jwk.contains_key("keys") {
+        bail!("Signing needs a single private JWK, not a JWKS");
+    }
+    if !jwk.contains_key("d") && !jwk.contains_key("k") {
+        bail!("JWK has no private key material");
+    }
+    let kid = jwk.get("kid").and_then(Value::as_str).map(str::to_string);
+
+    let key = match member(jwk, "kty")? {
+        "RSA" => {
+            let der = rsa_private_key(jwk)?
+                .to_pkcs1_der()
+                .map_err(|e| anyhow!("Failed to encode RSA key: {}", e))?;
+            EncodingKey::from_rsa_der(der.as_bytes())
+        }
+        "EC" => {
+            let curve_oid = match member(jwk, "crv")? {
+                "P-256" => OID_P256,
+                "P-384" => OID_P384,
+                other => bail!("Unsupported curve {}", other),
+            };
+            let der = ec_pkcs8(
+                curve_oid,
+                &base64url_member(jwk, "d")?,
+                &base64url_member(jwk, "x")?,
+                &base64url_member(jwk, "y")?,
+            );
+            EncodingKey::from_ec_der(&der)
+        }
+        "OKP" => {
+            if member(jwk, "crv")? != "Ed25519" {
+                bail!("Only Ed25519 octet key pairs are supported");
+            }
+            let seed = base64url_member(jwk, "d")?;
+            if seed.len() != 32 {
+                bail!("Ed25519 private key must be 32 bytes");
+            }
+            EncodingKey::from_ed_der(&ed25519_pkcs8(&seed))
+        }
+        "oct" => EncodingKey::from_secret(&base64url_member(jwk, "k")?),
+        other => bail!("Unsupported key type {}", other),
+    };
+    Ok((key, kid))
+}
+
+fn der_element(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut element = vec![tag];
+    let length = content.len();
+    if length < 0x80 {
+        element.push(length as u8);
+    } else {
+        let bytes: Vec<u8> = length
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|&b| b == 0)
+            .collect();
+        element.push(0x80 | bytes.len() as u8);
+        element.extend(bytes);
+    }
+    element.extend_from_slice(content);
+    element
+}
+
+fn der_oid(oid: &str) -> Vec<u8> {
+    let arcs: Vec<u64> = oid.split('.').filter_map(|arc| arc.parse().ok()).collect();
+    let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+    for &arc in &arcs[2..] {
+        let mut chunk = vec![(arc & 0x7f) as u8];
+        let mut rest = arc >> 7;
+        while rest > 0 {
+            chunk.insert(0, 0x80 | (rest & 0x7f) as u8);
+            rest >>= 7;
+        }
+        content.extend(chunk);
+    }
+    der_element(0x06, &content)
+}
+
+/// PKCS#8 wrapping of a SEC1 EC private key that includes its public point.
+fn ec_pkcs8(curve_oid: &str, d: &[u8], x: &[u8], y: &[u8]) -> Vec<u8> {
+    let mut point = vec![0x00, 0x04];
+    point.extend_from_slice(x);
+    point.extend_from_slice(y);
+    let ec_private_key = der_element(
+        0x30,
+        &[
+            der_element(0x02, &[1]),
+            der_element(0x04, d),
+            der_element(0xa1, &der_element(0x03, &point)),
+        ]
+        .concat(),
+    );
+    der_element(
+        0x30,
+        &[
+            der_element(0x02, &[0]),
+            der_element(0x30, &[der_oid(OID_EC), der_oid(curve_oid)].concat()),
+            der_element(0x04, &ec_private_key),
+        ]
+        .concat(),
+    )
+}
+
+fn ed25519_pkcs8(seed: &[u8]) -> Vec<u8> {
+    der_element(
+        0x30,
+        &[
+            der_element(0x02, &[0]),
+            der_element(0x30, &der_oid(OID_ED25519)),
+            der_element(0x04, &der_element(0x04, seed)),
+        ]
+        .concat(),
+    )
+}
+
+fn base64url(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Unsigned big-endian integer without leading zero bytes, as JWK requires.
+fn unsigned(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn rsa_jwk(key: &RsaPublicKey) -> Map<String, Value> {
+    let mut jwk = Map::new();
+    jwk.insert("kty".into(), "RSA".into());
+    jwk.insert("n".into(), base64url(&key.n().to_bytes_be()).into());
+    jwk.insert("e".into(), base64url(&key.e().to_bytes_be()).into());
+    jwk
+}
+
+fn ec_jwk(curve_oid: &str, point: &[u8]) -> Result<Map<String, Value>> {
+    let (curve, algorithm) = match curve_oid {
+        OID_P256 => ("P-256", "ES256"),
+        OID_P384 => ("P-384", "ES384"),
+        other => bail!("Unsupported curve {}", other),
+    };
+    if point.first() != Some(&0x04) || point.len() % 2 != 1 {
+        bail!("Only uncompressed EC public keys are supported");
+    }
+    let (x, y) = point[1..].split_at(point.len() / 2);
+    let mut jwk = Map::new();
+    jwk.insert("kty".into(), "EC".into());
+    jwk.insert("crv".into(), curve.into());
+    jwk.insert("x".into(), base64url(x).into());
+    jwk.insert("y".into(), base64url(y).into());
+    jwk.insert("alg".into(), algorithm.into());
+    Ok(jwk)
+}
+
+fn ed25519_jwk(public_key: &[u8]) -> Map<String, Value> {
+    let mut jwk = Map::new();
+    jwk.insert("kty".into(), "OKP".into());
+    jwk.insert("crv".into(), "Ed25519".into());
+    jwk.insert("x".into(), base64url(public_key).into());
+    jwk.insert("alg".into(), "EdDSA".into());
+    jwk
+}
+
+fn spki_to_jwk(der: &[u8]) -> Result<Map<String, Value>> {
+    let (_, spki) =
+        SubjectPublicKeyInfo::from_der(der).map_err(|e| anyhow!("Invalid public key: {}", e))?;
+    let oid = spki.algorithm.algorithm.to_id_string();
+    match oid.as_str() {
+        OID_RSA => match spki.parsed() {
+            Ok(PublicKey::RSA(key)) => {
+                let mut jwk = Map::new();
+                jwk.insert("kty".into(), "RSA".into());
+                jwk.insert("n".into(), base64url(unsigned(key.modulus)).into());
+                jwk.insert("e".into(), base64url(unsigned(key.exponent)).into());
+                Ok(jwk)
+            }
+            _ => bail!("Invalid RSA public key"),
+        },
+        OID_EC => {
+            let curve = spki
+                .algorithm
+                .parameters
+                .as_ref()
+                .and_then(|params| params.as_oid().ok())
+                .map(|oid| oid.to_id_string())
+                .ok_or_else(|| anyhow!("EC public key has no named curve"))?;
+            ec_jwk(&curve, &spki.subject_public_key.data)
+        }
+        OID_ED25519 => Ok(ed25519_jwk(&spki.subject_public_key.data)),
+        other => bail!("Unsupported public key algorithm {}", other),
+    }
+}
+
+fn pkcs8_to_jwk(der: &[u8]) -> Result<Map<String, Value>> {
+    let info =
+        PrivateKeyInfo::try_from(der).map_err(|e| anyhow!("Invalid PKCS#8 private key: {}", e))?;
+    match info.algorithm.oid.to_string().as_str() {
+        OID_RSA => {
+            let key = RsaPrivateKey::from_pkcs8_der(der)
+                .map_err(|e| anyhow!("Invalid RSA private key: {}", e))?;
+            Ok(rsa_jwk(&key.to_public_key()))
+        }
+        OID_EC => {
+            let curve = info
+                .algorithm
+                .parameters_oid()
+                .map_err(|_| anyhow!("EC private key has no named curve"))?
+                .to_string();
+            let signing = match curve.as_str() {
+                OID_P256 => &ECDSA_P256_SHA256_FIXED_SIGNING,
+                OID_P384 => &ECDSA_P384_SHA384_FIXED_SIGNING,
+                other => bail!("Unsupported curve {}", other),
+            };
+            let pair = EcdsaKeyPair::from_pkcs8(signing, der, &SystemRandom::new())
+                .map_err(|e| anyhow!("Invalid EC private key: {}", e))?;
+            ec_jwk(&curve, pair.public_key().as_ref())
+        }
+        OID_ED25519 => {
+            let pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+                .map_err(|e| anyhow!("Invalid Ed25519 private key: {}", e))?;
+            Ok(ed25519_jwk(pair.public_key().as_ref()))
+        }
+        other => bail!("Unsupported private key algorithm {}", other),
+    }
+}
+
+/// RFC 7638 thumbprint: SHA-256 of the required members in lexicographic order.
+pub fn thumbprint(jwk: &Map<String, Value>) -> Result<String> {
+    let required: &[&str] = match member(jwk, "kty")? {
+        "RSA" => &["e", "kty", "n"],
+        "EC" => &["crv", "kty", "x", "y"],
+        "OKP" => &["crv", "kty", "x"],
+        "oct" => &["k", "kty"],
+        other => bail!("Unsupported key type {}", other),
+    };
+    let members = required
+        .iter()
+        .map(|name| {
+            let value = serde_json::to_string(member(jwk, name)?)?;
+            Ok(format!("\"{}\":{}", name, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let canonical = format!("{{{}}}", members.join(","));
+    Ok(base64url(&Sha256::digest(canonical.as_bytes())))
+}
+
+/// Public JWK of a PEM key, private keys being reduced to their public half.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwkExport {
+    pub jwk: String,
+    pub thumbprint: String,
+}
+
+pub fn export_pem(pem: &str) -> Result<JwkExport> {
+    let block = decode_pem(pem)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No PEM block found"))?;
+    let mut jwk = match block.label.as_str() {
+        "PUBLIC KEY" => spki_to_jwk(&block.der)?,
+        "RSA PUBLIC KEY" => rsa_jwk(
+            &RsaPublicKey::from_pkcs1_der(&block.der)
+                .map_err(|e| anyhow!("Invalid RSA public key: {}", e))?,
+        ),
+        "PRIVATE KEY" => pkcs8_to_jwk(&block.der)?,
+        "RSA PRIVATE KEY" => rsa_jwk(
+            &RsaPrivateKey::from_pkcs1_der(&block.der)
+                .map_err(|e| anyhow!("Invalid RSA private key: {}", e))?
+                .to_public_key(),
+        ),
+        "EC PRIVATE KEY" => bail!("Convert SEC1 EC keys to PKCS#8 (BEGIN PRIVATE KEY) first"),
+        other => bail!("Unsupported PEM block {}", other),
+    };
+
+    let thumbprint = thumbprint(&jwk)?;
+    jwk.insert("use".into(), "sig".into());
+    jwk.insert("kid".into(), thumbprint.clone().into());
+    Ok(JwkExport {
+        jwk: serde_json::to_string_pretty(&jwk)?,
+        thumbprint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc7638_thumbprint() {
+        // Example key from RFC 7638 section 3.1
+        let jwk: Map<String, Value> = serde_json::from_str(
+            r#"{"kty":"RSA","n":"0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw","e":"AQAB","alg":"RS256","kid":"2011-04-29"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            thumbprint(&jwk).unwrap(),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+    }
+
+    #[test]
+    fn test_private_and_public_pem_export_the_same_key() {
+        for (private, public) in [
+            (
+                include_str!("testdata/rsa.pem"),
+                include_str!("testdata/rsa.pub.pem"),
+            ),
+            (
+                include_str!("testdata/es384.pem"),
+                include_str!("testdata/es384.pub.pem"),
+            ),
+            (
+                include_str!("testdata/ed25519.pem"),
+                include_str!("testdata/ed25519.pub.pem"),
+            ),
+        ] {
+            assert_eq!(export_pem(private).unwrap(), export_pem(public).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_selects_key_by_kid() {
+        let exported = export_pem(include_str!("testdata/es256.pub.pem")).unwrap();
+        let other = export_pem(include_str!("testdata/ed25519.pub.pem")).unwrap();
+        let jwks = format!(r#"{{"keys":[{},{}]}}"#, other.jwk, exported.jwk);
+
+        let mut header = Header::new(jsonwebtoken::Algorithm::ES256);
+        header.kid = Some(exported.thumbprint.clone());
+        let keys = select_decoding_keys(&jwks, &header, &Algorithm::ES256).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0.as_deref(), Some(exported.thumbprint.as_str()));
+
+        header.kid = Some("missing".into());
+        assert!(select_decoding_keys(&jwks, &header, &Algorithm::ES256).is_err());
+
+        header.kid = None;
+        let keys = select_decoding_keys(&jwks, &header, &Algorithm::EdDSA).unwrap();
+        assert_eq!(keys[0].0.as_deref(), Some(other.thumbprint.as_str()));
+    }
+}
@@ -0,0 +1,349 @@
+//! Security review of a signed token: unsigned and weakly signed tokens, algorithm confusion,
+//! token lifetime and injection in key-locating header parameters.
+
+use std::fmt;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::hmac;
+use serde_json::{Map, Value};
+
+/// Secrets commonly found in tutorials, framework defaults and leaked configurations.
+const WEAK_SECRETS: &str = include_str!("weak_secrets.txt");
+
+/// Lifetimes above this many seconds are reported as excessive.
+const MAX_LIFETIME: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub title: String,
+    pub detail: String,
+}
+
+impl Finding {
+    fn new(severity: Severity, title: &str, detail: impl Into<String>) -> Self {
+        Self {
+            severity,
+            title: title.to_string(),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Parts of a JWS, decoded without any validation.
+struct RawToken<'a> {
+    header: Map<String, Value>,
+    claims: Map<String, Value>,
+    signing_input: &'a str,
+    signature: Vec<u8>,
+}
+
+fn parse(token: &str) -> Option<RawToken<'_>> {
+    let token = token.trim();
+    let (signing_input, signature) = token.rsplit_once('.')?;
+    let (header, claims) = signing_input.split_once('.')?;
+    let json = |part: &str| {
+        URL_SAFE_NO_PAD
+            .decode(part.trim_end_matches('='))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Map<String, Value>>(&bytes).ok())
+    };
+    Some(RawToken {
+        header: json(header)?,
+        claims: json(claims).unwrap_or_default(),
+        signing_input,
+        signature: URL_SAFE_NO_PAD.decode(signature).unwrap_or_default(),
+    })
+}
+
+fn hmac_algorithm(alg: &str) -> Option<hmac::Algorithm> {
+    match alg {
+        "HS256" => Some(hmac::HMAC_SHA256),
+        "HS384" => Some(hmac::HMAC_SHA384),
+        "HS512" => Some(hmac::HMAC_SHA512),
+        _ => None,
+    }
+}
+
+fn signed_with(token: &RawToken<'_>, algorithm: hmac::Algorithm, secret: &[u8]) -> bool {
+    let key = hmac::Key::new(algorithm, secret);
+    hmac::verify(&key, token.signing_input.as_bytes(), &token.signature).is_ok()
+}
+
+/// Reviews `token` and returns its findings, most severe first. `secret` is the decoded HMAC key
+/// and `public_key` the key entered in the tool, `now` the current Unix time.
+pub fn lint(token: &str, secret: &[u8], public_key: &str, now: i64) -> Vec<Finding> {
+    let Some(token) = parse(token) else {
+        return Vec::new();
+    };
+    let mut findings = Vec::new();
+    let alg = token
+        .header
+        .get("alg")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+
+    if alg.eq_ignore_ascii_case("none") {
+        findings.push(Finding::new(
+            Severity::Critical,
+            "Unsigned token",
+            "alg is \"none\": anyone can forge this token, verifiers must reject it",
+        ));
+    } else if let Some(algorithm) = hmac_algorithm(alg) {
+        lint_hmac(&token, algorithm, secret, public_key, &mut findings);
+    }
+
+    lint_lifetime(&token.claims, now, &mut findings);
+    for name in ["jku", "x5u"] {
+        if let Some(url) = token.header.get(name).and_then(Value::as_str) {
+            lint_key_url(name, url, &mut findings);
+        }
+    }
+    if let Some(kid) = token.header.get("kid").and_then(Value::as_str) {
+        lint_kid(kid, &mut findings);
+    }
+
+    findings.sort_by_key(|finding| finding.severity);
+    findings
+}
+
+fn lint_hmac(
+    token: &RawToken<'_>,
+    algorithm: hmac::Algorithm,
+    secret: &[u8],
+    public_key: &str,
+    findings: &mut Vec<Finding>,
+) {
+    if let Some(weak) = WEAK_SECRETS
+        .lines()
+        .find(|candidate| signed_with(token, algorithm, candidate.as_bytes()))
+    {
+        findings.push(Finding::new(
+            Severity::Critical,
+            "Weak secret",
+            format!("Signed with the well-known secret \"{}\"", weak),
+        ));
+    }
+
+    let hash_len = algorithm.digest_algorithm().output_len();
+    if !secret.is_empty() && secret.len() < hash_len && signed_with(token, algorithm, secret) {
+        findings.push(Finding::new(
+            Severity::High,
+            "Short secret",
+            format!(
+                "The {}-byte secret is shorter than the {}-byte hash output, RFC 7518 requires at least {}",
+                secret.len(),
+                hash_len,
+                hash_len
+            ),
+        ));
+    }
+
+    if !public_key.trim().is_empty() {
+        if signed_with(token, algorithm, public_key.as_bytes())
+            || signed_with(token, algorithm, public_key.trim().as_bytes())
+        {
+            findings.push(Finding::new(
+                Severity::Critical,
+                "Algorithm confusion",
+                "The HMAC secret is the public key: a verifier that accepts HS tokens with its public key is forgeable",
+            ));
+        } else {
+            findings.push(Finding::new(
+                Severity::Medium,
+                "Algorithm confusion risk",
+                "HS token with a public key configured: verifiers must pin the algorithm instead of trusting alg",
+            ));
+        }
+    }
+}
+
+fn lint_lifetime(claims: &Map<String, Value>, now: i64, findings: &mut Vec<Finding>) {
+    let Some(exp) = claims.get("exp").and_then(Value::as_i64) else {
+        findings.push(Finding::new(
+            Severity::Medium,
+            "No expiry",
+            "The token has no exp claim and stays valid forever",
+        ));
+        return;
+    };
+    let issued = claims.get("iat").and_then(Value::as_i64).unwrap_or(now);
+    let lifetime = exp.saturating_sub(issued);
+    if lifetime > MAX_LIFETIME {
+        findings.push(Finding::new(
+            Severity::Low,
+            "Excessive lifetime",
+            format!(
+                "Valid for {} hours{}, longer than {} hours",
+                lifetime / 3600,
+                if claims.contains_key("iat") {
+                    ""
+                } else {
+                    " from now"
+                },
+                MAX_LIFETIME / 3600
+            ),
+        ));
+    }
+}
+
+fn lint_key_url(name: &str, url: &str, findings: &mut Vec<Finding>) {
+    let lower = url.to_lowercase();
+    let detail = if !lower.starts_with("https://") {
+        Some("is not an https URL")
+    } else if lower.contains("..") || lower.contains("%2e%2e") {
+        Some("contains path traversal")
+    } else if ["localhost", "127.", "0.0.0.0", "169.254.", "[::1]"]
+        .iter()
+        .any(|host| lower.contains(host))
+    {
+        Some("points to a local or metadata address")
+    } else if lower.contains('@') {
+        Some("contains credentials or a misleading host")
+    } else {
+        None
+    };
+    let (severity, detail) = match detail {
+        Some(detail) => (Severity::High, format!("{} {}: {}", name, detail, url)),
+        None => (
+            Severity::Low,
+            format!(
+                "{} names a key URL ({}); verifiers must only fetch keys from an allowlist",
+                name, url
+            ),
+        ),
+    };
+    findings.push(Finding::new(severity, "Key URL in header", detail));
+}
+
+fn lint_kid(kid: &str, findings: &mut Vec<Finding>) {
+    let lower = kid.to_lowercase();
+    let injection = if kid.contains("../") || kid.contains("..\\") || lower.contains("%2e%2e") {
+        Some("path traversal")
+    } else if kid.starts_with('/') || lower.starts_with("file:") || kid.contains('\0') {
+        Some("a file path")
+    } else if kid.contains('\'')
+        || kid.contains("--")
+        || kid.contains(';')
+        || [" union ", " or ", " and ", "select "]
+            .iter()
+            .any(|keyword| lower.contains(keyword))
+    {
+        Some("SQL injection")
+    } else if kid.contains('|') || kid.contains('`') || kid.contains("$(") {
+        Some("command injection")
+    } else {
+        None
+    };
+    if let Some(injection) = injection {
+        findings.push(Finding::new(
+            Severity::High,
+            "Suspicious kid",
+            format!("kid looks like {}: {}", injection, kid),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(header: &str, claims: &str, secret: &[u8]) -> String {
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header),
+            URL_SAFE_NO_PAD.encode(claims)
+        );
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+        let signature = hmac::sign(&key, signing_input.as_bytes());
+        format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_ref())
+        )
+    }
+
+    fn titles(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_unsigned_token() {
+        let token = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#),
+            URL_SAFE_NO_PAD.encode(r#"{"exp":100}"#)
+        );
+        let findings = lint(&token, b"", "", 0);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(titles(&findings), ["Unsigned token"]);
+    }
+
+    #[test]
+    fn test_weak_and_short_secrets() {
+        let token = token(r#"{"alg":"HS256"}"#, r#"{"exp":100}"#, b"secret");
+        let findings = lint(&token, b"secret", "", 0);
+        assert_eq!(titles(&findings), ["Weak secret", "Short secret"]);
+
+        let strong = "k".repeat(32);
+        let token = self::token(r#"{"alg":"HS256"}"#, r#"{"exp":100}"#, strong.as_bytes());
+        assert!(lint(&token, strong.as_bytes(), "", 0).is_empty());
+    }
+
+    #[test]
+    fn test_algorithm_confusion() {
+        let public_key = "-----BEGIN PUBLIC KEY-----\nMIIB\n-----END PUBLIC KEY-----\n";
+        let token = token(
+            r#"{"alg":"HS256"}"#,
+            r#"{"exp":100}"#,
+            public_key.as_bytes(),
+        );
+        let findings = lint(&token, b"", public_key, 0);
+        assert_eq!(titles(&findings), ["Algorithm confusion"]);
+    }
+
+    #[test]
+    fn test_lifetime_and_header_injection() {
+        let token = token(
+            r#"{"alg":"HS256","kid":"../../dev/null","jku":"http://attacker.example/jwks"}"#,
+            r#"{"iat":0,"exp":604800}"#,
+            &[7; 32],
+        );
+        let findings = lint(&token, b"", "", 0);
+        assert_eq!(
+            titles(&findings),
+            ["Key URL in header", "Suspicious kid", "Excessive lifetime"]
+        );
+
+        for claims in [
+            r#"{"exp":-9223372036854775808}"#,
+            r#"{"iat":-1,"exp":9223372036854775807}"#,
+        ] {
+            lint(
+                &self::token(r#"{"alg":"HS256"}"#, claims, &[7; 32]),
+                b"",
+                "",
+                0,
+            );
+        }
+
+        let token = self::token(r#"{"alg":"HS256","kid":"1' OR '1'='1"}"#, "{}", &[7; 32]);
+        let findings = lint(&token, b"", "", 0);
+        assert!(findings[0].detail.contains("SQL injection"));
+        assert_eq!(findings[1].title, "No expiry");
+    }
+}
@@ -0,0 +1,119 @@
+//! HMAC secrets as text or encoded key material, read the same way for signing and verifying.
+
+use std::fmt;
+
+use anyhow::{anyhow, bail, Result};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SecretEncoding {
+    /// The secret text itself, whitespace included
+    #[default]
+    Utf8,
+    /// Standard Base64, as handed out by e.g. Auth0
+    Base64,
+    Base64Url,
+    Hex,
+}
+
+impl fmt::Display for SecretEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretEncoding::Utf8 => write!(f, "UTF-8"),
+            SecretEncoding::Base64 => write!(f, "Base64"),
+            SecretEncoding::Base64Url => write!(f, "Base64Url"),
+            SecretEncoding::Hex => write!(f, "Hex"),
+        }
+    }
+}
+
+impl SecretEncoding {
+    pub fn variants() -> &'static [SecretEncoding] {
+        &[
+            SecretEncoding::Utf8,
+            SecretEncoding::Base64,
+            SecretEncoding::Base64Url,
+            SecretEncoding::Hex,
+        ]
+    }
+
+    /// Key bytes of `secret`. Encoded secrets ignore whitespace, such as the line breaks of a
+    /// wrapped Base64 file; UTF-8 secrets are used verbatim.
+    pub fn decode(&self, secret: &str) -> Result<Vec<u8>> {
+        let compact = || -> String { secret.chars().filter(|c| !c.is_whitespace()).collect() };
+        let bytes = match self {
+            SecretEncoding::Utf8 => Ok(secret.as_bytes().to_vec()),
+            SecretEncoding::Base64 => STANDARD.decode(compact()).map_err(|e| e.to_string()),
+            SecretEncoding::Base64Url => URL_SAFE_NO_PAD
+                .decode(compact().trim_end_matches('='))
+                .map_err(|e| e.to_string()),
+            SecretEncoding::Hex => hex::decode(compact()).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| anyhow!("Secret is not valid {}: {}", self, e))?;
+        if bytes.is_empty() {
+            bail!("Secret is required");
+        }
+        Ok(bytes)
+    }
+}
+
+/// Secret field contents for the key material of a file: its text when it is UTF-8, with a
+/// single trailing line break removed, or hex for binary keys.
+pub fn from_file(bytes: Vec<u8>) -> (String, Option<SecretEncoding>) {
+    match String::from_utf8(bytes) {
+        Ok(mut text) => {
+            if text.ends_with('\n') {
+                text.pop();
+                if text.ends_with('\r') {
+                    text.pop();
+                }
+            }
+            (text, None)
+        }
+        Err(e) => (hex::encode(e.into_bytes()), Some(SecretEncoding::Hex)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_every_encoding() {
+        let key = b"\xfb\xff\x00secret".to_vec();
+        for (encoding, secret) in [
+            (SecretEncoding::Base64, "+/8Ac2VjcmV0"),
+            (SecretEncoding::Base64Url, "-_8Ac2VjcmV0"),
+            (SecretEncoding::Hex, "fbff00 73656372 6574\n"),
+        ] {
+            assert_eq!(encoding.decode(secret).unwrap(), key, "{}", encoding);
+        }
+        assert_eq!(
+            SecretEncoding::Utf8.decode(" secret ").unwrap(),
+            b" secret "
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_secrets() {
+        assert!(SecretEncoding::Utf8.decode("").is_err());
+        assert!(SecretEncoding::Hex.decode("abc").is_err());
+        let error = SecretEncoding::Base64.decode("not base64!").unwrap_err();
+        assert!(error.to_string().starts_with("Secret is not valid Base64"));
+    }
+
+    #[test]
+    fn test_from_file() {
+        assert_eq!(
+            from_file(b"secret\r\n".to_vec()),
+            ("secret".to_string(), None)
+        );
+        assert_eq!(
+            from_file(vec![0xff, 0x00]),
+            ("ff00".to_string(), Some(SecretEncoding::Hex))
+        );
+    }
+}
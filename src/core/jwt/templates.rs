@@ -0,0 +1,264 @@
+//! Claim templates: JSON claims whose string values may hold `{{now}}`, `{{now+1h}}`, `{{uuid}}`
+//! and `{{ulid}}` expressions, expanded each time a token is encoded.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::generators::GeneratorProcessor;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimTemplate {
+    pub name: String,
+    pub claims: String,
+}
+
+enum Expansion {
+    Timestamp(i64),
+    Text(String),
+}
+
+impl Expansion {
+    fn into_text(self) -> String {
+        match self {
+            Expansion::Timestamp(timestamp) => timestamp.to_string(),
+            Expansion::Text(text) => text,
+        }
+    }
+}
+
+/// Seconds in `offset`, such as `+1h`, `-30m` or `+7d`.
+fn parse_offset(offset: &str) -> Result<i64> {
+    let invalid = || {
+        anyhow!(
+            "Invalid time offset \"{}\", expected e.g. +1h or -30m",
+            offset
+        )
+    };
+    let (sign, rest) = match offset.chars().next() {
+        Some('+') => (1, &offset[1..]),
+        Some('-') => (-1, &offset[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits = rest.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit = match &rest[digits.len()..] {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let amount: i64 = digits.parse().map_err(|_| invalid())?;
+    amount
+        .checked_mul(unit * sign)
+        .ok_or_else(|| anyhow!("Time offset \"{}\" is out of range", offset))
+}
+
+fn evaluate(expression: &str, now: i64, generator: &GeneratorProcessor) -> Result<Expansion> {
+    let expression: String = expression.chars().filter(|c| !c.is_whitespace()).collect();
+    match expression.as_str() {
+        "uuid" => Ok(Expansion::Text(generator.generate_uuid())),
+        "ulid" => Ok(Expansion::Text(generator.generate_ulid())),
+        "now" => Ok(Expansion::Timestamp(now)),
+        other => match other.strip_prefix("now") {
+            Some(offset) => now
+                .checked_add(parse_offset(offset)?)
+                .map(Expansion::Timestamp)
+                .ok_or_else(|| anyhow!("Time offset \"{}\" is out of range", offset)),
+            None => bail!(
+                "Unknown template expression {{{{{}}}}}, expected now, now±offset, uuid or ulid",
+                other
+            ),
+        },
+    }
+}
+
+/// Expands the expressions of a string value. A string holding a single time expression becomes
+/// a number, so `"exp": "{{now+1h}}"` yields a NumericDate.
+fn expand_string(text: &str, now: i64, generator: &GeneratorProcessor) -> Result<Value> {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unclosed template expression in \"{}\"", text))?;
+        let expansion = evaluate(&rest[start + 2..start + end], now, generator)?;
+        if start == 0 && start + end + 2 == rest.len() && output.is_empty() {
+            if let Expansion::Timestamp(timestamp) = expansion {
+                return Ok(timestamp.into());
+            }
+        }
+        output.push_str(&rest[..start]);
+        output.push_str(&expansion.into_text());
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+    Ok(Value::String(output))
+}
+
+fn expand_value(value: Value, now: i64, generator: &GeneratorProcessor) -> Result<Value> {
+    Ok(match value {
+        Value::String(text) if text.contains("{{") => expand_string(&text, now, generator)?,
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| expand_value(value, now, generator))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| Ok((key, expand_value(value, now, generator)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other,
+    })
+}
+
+/// Parses `claims` as JSON and expands its template expressions, with `now` as Unix time.
+pub fn expand(claims: &str, now: i64, generator: &GeneratorProcessor) -> Result<Value> {
+    expand_value(serde_json::from_str(claims)?, now, generator)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedTemplates {
+    templates: Vec<ClaimTemplate>,
+}
+
+impl SavedTemplates {
+    pub const FILE_NAME: &'static str = "jwt_templates.json";
+
+    pub fn iter(&self) -> impl Iterator<Item = &ClaimTemplate> {
+        self.templates.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ClaimTemplate> {
+        self.templates.iter().find(|t| t.name == name)
+    }
+
+    /// Adds the template, replacing any saved template with the same name.
+    pub fn upsert(&mut self, template: ClaimTemplate) {
+        match self.templates.iter_mut().find(|t| t.name == template.name) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ClaimTemplate> {
+        self.templates
+            .iter()
+            .position(|t| t.name == name)
+            .map(|i| self.templates.remove(i))
+    }
+
+    /// Loads saved templates from the json file located at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read(path).context("failed to read saved JWT templates file")?;
+        serde_json::from_slice(&data).context("failed to deserialize saved JWT templates file")
+    }
+
+    /// Loads saved templates from `path`, or returns an empty list if the file doesn't exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load(path)
+    }
+
+    /// Saves the templates as json file in the provided `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)
+                    .context("failed to create parent directory for JWT templates file")?;
+            }
+        }
+
+        let data = serde_json::to_vec_pretty(&self).context("failed to serialize JWT templates")?;
+        fs::write(path, data).context("failed to write saved JWT templates to a file")
+    }
+
+    /// Returns system directory where saved templates should be placed joined by the `name` parameter.
+    pub fn dir(name: impl AsRef<str>) -> Option<PathBuf> {
+        let name = name.as_ref();
+        dirs::config_dir()
+            .or_else(dirs::home_dir)
+            .map(|dir| dir.join(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_expands_relative_times() {
+        let claims = r#"{"iat":"{{now}}","exp":"{{ now+1h }}","nbf":"{{now-30m}}","note":"issued at {{now}}"}"#;
+        let expanded = expand(claims, 1_000_000, &GeneratorProcessor::new()).unwrap();
+        assert_eq!(
+            expanded,
+            json!({
+                "iat": 1_000_000,
+                "exp": 1_003_600,
+                "nbf": 998_200,
+                "note": "issued at 1000000"
+            })
+        );
+    }
+
+    #[test]
+    fn test_expands_identifiers() {
+        let claims = r#"{"jti":"{{uuid}}","sid":"{{ulid}}","nested":["{{uuid}}", 1]}"#;
+        let expanded = expand(claims, 0, &GeneratorProcessor::new()).unwrap();
+        assert_eq!(expanded["jti"].as_str().unwrap().len(), 36);
+        assert_eq!(expanded["sid"].as_str().unwrap().len(), 26);
+        assert_ne!(expanded["nested"][0], expanded["jti"]);
+        assert_eq!(expanded["nested"][1], 1);
+    }
+
+    #[test]
+    fn test_rejects_invalid_expressions() {
+        let generator = GeneratorProcessor::new();
+        for claims in [
+            r#"{"exp":"{{tomorrow}}"}"#,
+            r#"{"exp":"{{now+1y}}"}"#,
+            r#"{"exp":"{{now"}"#,
+            r#"{"exp":"{{now+9223372036854775807}}"}"#,
+            r#"{"exp":"{{now++5h}}"}"#,
+            r#"{"exp":"{{now+-5h}}"}"#,
+        ] {
+            assert!(
+                expand(claims, 1_700_000_000, &generator).is_err(),
+                "{}",
+                claims
+            );
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SavedTemplates::FILE_NAME);
+
+        let mut saved = SavedTemplates::default();
+        saved.upsert(ClaimTemplate {
+            name: "1h".to_string(),
+            claims: r#"{"exp":"{{now+1h}}"}"#.to_string(),
+        });
+        saved.save(&path).unwrap();
+
+        let loaded = SavedTemplates::load_or_default(&path).unwrap();
+        assert_eq!(loaded.get("1h"), saved.get("1h"));
+    }
+}
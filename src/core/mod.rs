@@ -1,7 +1,15 @@
+pub mod asn1;
 pub mod color;
 pub mod crypto;
 pub mod datetime;
 pub mod encoding;
 pub mod generators;
 pub mod jwt;
+pub mod paseto;
+pub mod protobuf;
+pub mod qr;
+pub mod recipe;
 pub mod regex;
+pub mod serialization;
+pub mod unicode;
+pub mod url;
@@ -0,0 +1,80 @@
+//! Thin wrappers over the primitive crates PASETO local tokens are built on: keyed BLAKE2b and
+//! XChaCha20 for v4, AES-256-CTR and HKDF-SHA384 for v3.
+
+use aes::Aes256;
+use blake2::{
+    digest::{
+        generic_array::ArrayLength,
+        typenum::{IsLessOrEqual, LeEq, NonZero, U64},
+        Mac,
+    },
+    Blake2bMac,
+};
+use chacha20::XChaCha20;
+use cipher::{KeyIvInit, StreamCipher};
+use ctr::Ctr128BE;
+use ring::hkdf;
+
+fn blake2b_mac<N>(key: &[u8], message: &[u8]) -> Blake2bMac<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    let mut mac = <Blake2bMac<N> as Mac>::new_from_slice(key).expect("key of at most 64 bytes");
+    mac.update(message);
+    mac
+}
+
+/// Keyed BLAKE2b with an `N`-byte output, libsodium's `crypto_generichash`.
+pub fn blake2b<N>(key: &[u8], message: &[u8]) -> Vec<u8>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    blake2b_mac::<N>(key, message)
+        .finalize()
+        .into_bytes()
+        .to_vec()
+}
+
+/// Checks an `N`-byte keyed BLAKE2b tag in constant time.
+pub fn blake2b_verify<N>(key: &[u8], message: &[u8], tag: &[u8]) -> bool
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    blake2b_mac::<N>(key, message).verify_slice(tag).is_ok()
+}
+
+/// XChaCha20 keystream XOR with the block counter starting at zero.
+pub fn xchacha20_xor(key: &[u8; 32], nonce: &[u8; 24], data: &[u8]) -> Vec<u8> {
+    let mut output = data.to_vec();
+    XChaCha20::new(key.into(), nonce.into()).apply_keystream(&mut output);
+    output
+}
+
+/// AES-256 in counter mode with a 128-bit big-endian counter block.
+pub fn aes256_ctr_xor(key: &[u8; 32], counter: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut output = data.to_vec();
+    Ctr128BE::<Aes256>::new(key.into(), counter.into()).apply_keystream(&mut output);
+    output
+}
+
+struct OutputLen(usize);
+
+impl hkdf::KeyType for OutputLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// HKDF-SHA384 with an empty salt and a 48-byte output.
+pub fn hkdf_sha384(key: &[u8], info: &[u8]) -> [u8; 48] {
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA384, &[]).extract(key);
+    let info = [info];
+    let mut output = [0u8; 48];
+    prk.expand(&info, OutputLen(48))
+        .and_then(|okm| okm.fill(&mut output))
+        .expect("48 bytes is a valid HKDF-SHA384 output length");
+    output
+}
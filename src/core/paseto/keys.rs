@@ -0,0 +1,378 @@
+//! Key parsing for PASETO: PASERK strings (`k4.local.…`), PEM and raw hex or base64url keys, with
+//! errors that name the kind of key that was given instead.
+
+use anyhow::{anyhow, bail, Result};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use p384::{elliptic_curve::sec1::ToEncodedPoint, PublicKey, SecretKey};
+use ring::{
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P384_SHA384_FIXED_SIGNING},
+};
+use rsa::pkcs8::PrivateKeyInfo;
+use x509_parser::{prelude::FromDer, x509::SubjectPublicKeyInfo};
+
+use super::{Protocol, Purpose, Version};
+use crate::core::asn1::pem::decode_pem;
+
+const OID_EC: &str = "1.2.840.10045.2.1";
+const OID_ED25519: &str = "1.3.101.112";
+const OID_P384: &str = "1.3.132.0.34";
+
+/// Key kinds, as named by PASERK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyKind {
+    Local,
+    Secret,
+    Public,
+}
+
+impl KeyKind {
+    fn name(&self) -> &'static str {
+        match self {
+            KeyKind::Local => "local",
+            KeyKind::Secret => "secret",
+            KeyKind::Public => "public",
+        }
+    }
+}
+
+enum KeyMaterial {
+    Paserk {
+        version: Version,
+        kind: KeyKind,
+        bytes: Vec<u8>,
+    },
+    Pem {
+        label: String,
+        der: Vec<u8>,
+    },
+    Raw(Vec<u8>),
+}
+
+fn decode_raw(input: &str) -> Result<Vec<u8>> {
+    if input.len().is_multiple_of(2) && input.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(hex::decode(input)?);
+    }
+    URL_SAFE_NO_PAD
+        .decode(input.trim_end_matches('='))
+        .or_else(|_| STANDARD.decode(input))
+        .map_err(|_| anyhow!("Key is not PASERK, PEM, hex or base64url"))
+}
+
+fn parse(input: &str) -> Result<KeyMaterial> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("A key is required");
+    }
+    if input.contains("-----BEGIN ") {
+        let block = decode_pem(input)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No PEM block found"))?;
+        return Ok(KeyMaterial::Pem {
+            label: block.label,
+            der: block.der,
+        });
+    }
+
+    let mut parts = input.splitn(3, '.');
+    if let (Some(version), Some(kind), Some(data)) = (parts.next(), parts.next(), parts.next()) {
+        let version = match version {
+            "k3" => Some(Version::V3),
+            "k4" => Some(Version::V4),
+            _ => None,
+        };
+        if let Some(version) = version {
+            let kind = match kind {
+                "local" => KeyKind::Local,
+                "secret" => KeyKind::Secret,
+                "public" => KeyKind::Public,
+                other => bail!("Unsupported PASERK type {}", other),
+            };
+            let bytes = URL_SAFE_NO_PAD
+                .decode(data)
+                .map_err(|e| anyhow!("Invalid base64url in the PASERK key: {}", e))?;
+            return Ok(KeyMaterial::Paserk {
+                version,
+                kind,
+                bytes,
+            });
+        }
+    }
+    Ok(KeyMaterial::Raw(decode_raw(input)?))
+}
+
+/// Rejects a PASERK key of another version or kind than `protocol` needs.
+fn check_paserk(
+    version: Version,
+    kind: KeyKind,
+    protocol: Protocol,
+    needed: KeyKind,
+) -> Result<()> {
+    if version != protocol.version() {
+        bail!(
+            "This is a {} key, but the token is {}: PASETO keys are bound to one version",
+            version.paserk_prefix(),
+            protocol
+        );
+    }
+    if kind != needed {
+        bail!(
+            "This is a {}.{} key, but {} needs a {}.{} key",
+            version.paserk_prefix(),
+            kind.name(),
+            protocol,
+            version.paserk_prefix(),
+            needed.name()
+        );
+    }
+    Ok(())
+}
+
+/// What a key of `len` bytes most likely is, to explain length mismatches.
+fn length_hint(len: usize) -> &'static str {
+    match len {
+        32 => " (a local key or an Ed25519 public key)",
+        48 => " (looks like a v3.public secret key)",
+        49 | 97 => " (looks like a v3.public public key)",
+        64 => " (looks like a v4.public secret key)",
+        _ => "",
+    }
+}
+
+/// Symmetric key for `v3.local` and `v4.local`.
+pub fn local_key(input: &str, protocol: Protocol) -> Result<[u8; 32]> {
+    let bytes = match parse(input)? {
+        KeyMaterial::Paserk {
+            version,
+            kind,
+            bytes,
+        } => {
+            check_paserk(version, kind, protocol, KeyKind::Local)?;
+            bytes
+        }
+        KeyMaterial::Pem { label, .. } => bail!(
+            "{} needs a 32-byte symmetric key, but this is a PEM {}: asymmetric keys are for {}",
+            protocol,
+            label,
+            protocol.with_purpose(Purpose::Public)
+        ),
+        KeyMaterial::Raw(bytes) => bytes,
+    };
+    let len = bytes.len();
+    bytes.try_into().map_err(|_| {
+        anyhow!(
+            "{} needs a 32-byte symmetric key, got {} bytes{}",
+            protocol,
+            len,
+            length_hint(len)
+        )
+    })
+}
+
+pub enum SigningKey {
+    Ed25519(Ed25519KeyPair),
+    P384(EcdsaKeyPair),
+}
+
+impl SigningKey {
+    /// Public key as PASETO uses it: 32 bytes for Ed25519, compressed SEC1 for P-384.
+    pub fn public_key(&self) -> Vec<u8> {
+        match self {
+            SigningKey::Ed25519(pair) => pair.public_key().as_ref().to_vec(),
+            SigningKey::P384(pair) => compress(pair.public_key().as_ref())
+                .expect("ring's P-384 public key is a valid point"),
+        }
+    }
+
+    /// Public key in the form verification takes: uncompressed SEC1 for P-384.
+    fn verifying_key(&self) -> Vec<u8> {
+        match self {
+            SigningKey::Ed25519(pair) => pair.public_key().as_ref().to_vec(),
+            SigningKey::P384(pair) => pair.public_key().as_ref().to_vec(),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            SigningKey::Ed25519(pair) => Ok(pair.sign(message).as_ref().to_vec()),
+            SigningKey::P384(pair) => Ok(pair
+                .sign(&SystemRandom::new(), message)
+                .map_err(|_| anyhow!("ECDSA signing failed"))?
+                .as_ref()
+                .to_vec()),
+        }
+    }
+}
+
+fn expect_pem_algorithm(oid: &str, curve: Option<String>, protocol: Protocol) -> Result<()> {
+    let found = match (oid, curve.as_deref()) {
+        (OID_ED25519, _) => "an Ed25519 key",
+        (OID_EC, Some(OID_P384)) => "a P-384 key",
+        (OID_EC, _) => "an EC key on a curve other than P-384",
+        _ => "neither an Ed25519 nor a P-384 key",
+    };
+    let fits = match protocol.version() {
+        Version::V4 => oid == OID_ED25519,
+        Version::V3 => oid == OID_EC && curve.as_deref() == Some(OID_P384),
+    };
+    if !fits {
+        bail!(
+            "This is {}, but {} needs {}",
+            found,
+            protocol,
+            match protocol.version() {
+                Version::V4 => "an Ed25519 key",
+                Version::V3 => "a P-384 ECDSA key",
+            }
+        );
+    }
+    Ok(())
+}
+
+/// Secret key for `v3.public` (P-384) and `v4.public` (Ed25519).
+pub fn signing_key(input: &str, protocol: Protocol) -> Result<SigningKey> {
+    let bytes = match parse(input)? {
+        KeyMaterial::Paserk {
+            version,
+            kind,
+            bytes,
+        } => {
+            if kind == KeyKind::Public {
+                bail!("Signing needs the secret key, this is a public key");
+            }
+            check_paserk(version, kind, protocol, KeyKind::Secret)?;
+            bytes
+        }
+        KeyMaterial::Pem { label, der } => {
+            if label != "PRIVATE KEY" {
+                bail!(
+                    "Signing needs a PKCS#8 \"PRIVATE KEY\" PEM, this is a PEM {}",
+                    label
+                );
+            }
+            let info = PrivateKeyInfo::try_from(der.as_slice())
+                .map_err(|e| anyhow!("Invalid PKCS#8 private key: {}", e))?;
+            let curve = info
+                .algorithm
+                .parameters_oid()
+                .ok()
+                .map(|oid| oid.to_string());
+            expect_pem_algorithm(&info.algorithm.oid.to_string(), curve, protocol)?;
+            return Ok(match protocol.version() {
+                Version::V4 => SigningKey::Ed25519(
+                    Ed25519KeyPair::from_pkcs8_maybe_unchecked(&der)
+                        .map_err(|e| anyhow!("Invalid Ed25519 private key: {}", e))?,
+                ),
+                Version::V3 => SigningKey::P384(
+                    EcdsaKeyPair::from_pkcs8(
+                        &ECDSA_P384_SHA384_FIXED_SIGNING,
+                        &der,
+                        &SystemRandom::new(),
+                    )
+                    .map_err(|e| anyhow!("Invalid P-384 private key: {}", e))?,
+                ),
+            });
+        }
+        KeyMaterial::Raw(bytes) => bytes,
+    };
+
+    match (protocol.version(), bytes.len()) {
+        (Version::V4, 64) => Ed25519KeyPair::from_seed_and_public_key(&bytes[..32], &bytes[32..])
+            .map(SigningKey::Ed25519)
+            .map_err(|e| anyhow!("Invalid Ed25519 secret key: {}", e)),
+        (Version::V4, 32) => Ed25519KeyPair::from_seed_unchecked(&bytes)
+            .map(SigningKey::Ed25519)
+            .map_err(|e| anyhow!("Invalid Ed25519 seed: {}", e)),
+        (Version::V3, 48) => {
+            let public = SecretKey::from_slice(&bytes)
+                .map_err(|_| anyhow!("P-384 secret key is out of range"))?
+                .public_key()
+                .to_encoded_point(false);
+            EcdsaKeyPair::from_private_key_and_public_key(
+                &ECDSA_P384_SHA384_FIXED_SIGNING,
+                &bytes,
+                public.as_bytes(),
+                &SystemRandom::new(),
+            )
+            .map(SigningKey::P384)
+            .map_err(|e| anyhow!("Invalid P-384 secret key: {}", e))
+        }
+        (Version::V4, len) => bail!(
+            "v4.public needs a 64-byte Ed25519 secret key (or its 32-byte seed), got {} bytes{}",
+            len,
+            length_hint(len)
+        ),
+        (Version::V3, len) => bail!(
+            "v3.public needs a 48-byte P-384 secret key, got {} bytes{}",
+            len,
+            length_hint(len)
+        ),
+    }
+}
+
+/// Public key for verification: 32 bytes for Ed25519, uncompressed SEC1 for P-384.
+pub fn verifying_key(input: &str, protocol: Protocol) -> Result<Vec<u8>> {
+    let bytes = match parse(input)? {
+        KeyMaterial::Paserk {
+            version,
+            kind,
+            bytes,
+        } => {
+            if kind == KeyKind::Secret {
+                return signing_key(input, protocol).map(|key| key.verifying_key());
+            }
+            check_paserk(version, kind, protocol, KeyKind::Public)?;
+            bytes
+        }
+        KeyMaterial::Pem { label, der } => {
+            if label == "PRIVATE KEY" {
+                return signing_key(input, protocol).map(|key| key.verifying_key());
+            }
+            if label != "PUBLIC KEY" {
+                bail!(
+                    "Verifying needs a \"PUBLIC KEY\" PEM, this is a PEM {}",
+                    label
+                );
+            }
+            let (_, spki) = SubjectPublicKeyInfo::from_der(&der)
+                .map_err(|e| anyhow!("Invalid public key: {}", e))?;
+            let curve = spki
+                .algorithm
+                .parameters
+                .as_ref()
+                .and_then(|params| params.as_oid().ok())
+                .map(|oid| oid.to_id_string());
+            expect_pem_algorithm(&spki.algorithm.algorithm.to_id_string(), curve, protocol)?;
+            spki.subject_public_key.data.to_vec()
+        }
+        KeyMaterial::Raw(bytes) => bytes,
+    };
+
+    match (protocol.version(), bytes.len()) {
+        (Version::V4, 32) => Ok(bytes),
+        (Version::V3, 49 | 97) => PublicKey::from_sec1_bytes(&bytes)
+            .map(|key| key.to_encoded_point(false).as_bytes().to_vec())
+            .map_err(|_| anyhow!("Invalid P-384 public key: the point is not on the curve")),
+        (Version::V4, len) => bail!(
+            "v4.public needs a 32-byte Ed25519 public key, got {} bytes{}",
+            len,
+            length_hint(len)
+        ),
+        (Version::V3, len) => bail!(
+            "v3.public needs a compressed (49-byte) or uncompressed (97-byte) P-384 public key, got {} bytes{}",
+            len,
+            length_hint(len)
+        ),
+    }
+}
+
+/// Compressed SEC1 form of a P-384 public key.
+pub fn compress(point: &[u8]) -> Result<Vec<u8>> {
+    PublicKey::from_sec1_bytes(point)
+        .map(|key| key.to_encoded_point(true).as_bytes().to_vec())
+        .map_err(|_| anyhow!("Invalid P-384 public key"))
+}
@@ -0,0 +1,623 @@
+pub mod crypto;
+pub mod keys;
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use blake2::digest::consts::{U32, U56};
+use rand::RngCore;
+use ring::{
+    hmac,
+    signature::{UnparsedPublicKey, VerificationAlgorithm, ECDSA_P384_SHA384_FIXED, ED25519},
+};
+use serde_json::{Map, Value};
+use std::fmt;
+
+use crate::core::{
+    datetime::DateTimeProcessor,
+    jwt::claims::{self, ClaimExpectations, ClaimReport},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    V3,
+    V4,
+}
+
+impl Version {
+    /// Prefix of PASERK keys for this version, such as `k4`.
+    pub fn paserk_prefix(&self) -> &'static str {
+        match self {
+            Version::V3 => "k3",
+            Version::V4 => "k4",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Purpose {
+    Local,
+    Public,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// XChaCha20 encryption with a BLAKE2b MAC
+    #[default]
+    V4Local,
+    /// Ed25519 signatures
+    V4Public,
+    /// AES-256-CTR encryption with an HMAC-SHA384 MAC
+    V3Local,
+    /// ECDSA over P-384 with SHA-384
+    V3Public,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.header().trim_end_matches('.'))
+    }
+}
+
+impl Protocol {
+    pub fn variants() -> &'static [Protocol] {
+        &[
+            Protocol::V4Local,
+            Protocol::V4Public,
+            Protocol::V3Local,
+            Protocol::V3Public,
+        ]
+    }
+
+    pub fn version(&self) -> Version {
+        match self {
+            Protocol::V4Local | Protocol::V4Public => Version::V4,
+            Protocol::V3Local | Protocol::V3Public => Version::V3,
+        }
+    }
+
+    pub fn purpose(&self) -> Purpose {
+        match self {
+            Protocol::V4Local | Protocol::V3Local => Purpose::Local,
+            Protocol::V4Public | Protocol::V3Public => Purpose::Public,
+        }
+    }
+
+    pub fn with_purpose(&self, purpose: Purpose) -> Protocol {
+        match (self.version(), purpose) {
+            (Version::V4, Purpose::Local) => Protocol::V4Local,
+            (Version::V4, Purpose::Public) => Protocol::V4Public,
+            (Version::V3, Purpose::Local) => Protocol::V3Local,
+            (Version::V3, Purpose::Public) => Protocol::V3Public,
+        }
+    }
+
+    /// Token prefix, such as `v4.local.`.
+    pub fn header(&self) -> &'static str {
+        match self {
+            Protocol::V4Local => "v4.local.",
+            Protocol::V4Public => "v4.public.",
+            Protocol::V3Local => "v3.local.",
+            Protocol::V3Public => "v3.public.",
+        }
+    }
+
+    fn signature_len(&self) -> usize {
+        match self.version() {
+            Version::V4 => 64,
+            Version::V3 => 96,
+        }
+    }
+
+    fn tag_len(&self) -> usize {
+        match self.version() {
+            Version::V4 => 32,
+            Version::V3 => 48,
+        }
+    }
+}
+
+/// Pre-authentication encoding: the piece count and each piece prefixed with its length, as
+/// little-endian 64-bit integers with the top bit cleared.
+fn pae(pieces: &[&[u8]]) -> Vec<u8> {
+    let le64 = |n: usize| (n as u64 & (u64::MAX >> 1)).to_le_bytes();
+    let mut output = le64(pieces.len()).to_vec();
+    for piece in pieces {
+        output.extend_from_slice(&le64(piece.len()));
+        output.extend_from_slice(piece);
+    }
+    output
+}
+
+/// A token split into its parts, without any verification.
+struct Parts {
+    protocol: Protocol,
+    body: Vec<u8>,
+    footer: Vec<u8>,
+}
+
+fn split(token: &str) -> Result<Parts> {
+    let token = token.trim();
+    let protocol = Protocol::variants()
+        .iter()
+        .find(|protocol| token.starts_with(protocol.header()))
+        .copied()
+        .ok_or_else(|| {
+            let prefix: Vec<&str> = token.splitn(3, '.').take(2).collect();
+            anyhow!(
+                "Unsupported PASETO header \"{}\", expected v3 or v4 local or public",
+                prefix.join(".")
+            )
+        })?;
+
+    let rest = &token[protocol.header().len()..];
+    let (body, footer) = match rest.split_once('.') {
+        Some((body, footer)) => (body, footer),
+        None => (rest, ""),
+    };
+    let body = URL_SAFE_NO_PAD
+        .decode(body)
+        .map_err(|e| anyhow!("Invalid base64url in the token body: {}", e))?;
+    let footer = URL_SAFE_NO_PAD
+        .decode(footer)
+        .map_err(|e| anyhow!("Invalid base64url in the footer: {}", e))?;
+    Ok(Parts {
+        protocol,
+        body,
+        footer,
+    })
+}
+
+/// Encryption and authentication keys of the local protocols, and the stream cipher nonce.
+fn split_local_key(
+    protocol: Protocol,
+    key: &[u8; 32],
+    nonce: &[u8],
+) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let encryption_info = [b"paseto-encryption-key".as_slice(), nonce].concat();
+    let auth_info = [b"paseto-auth-key-for-aead".as_slice(), nonce].concat();
+    match protocol.version() {
+        Version::V4 => {
+            let tmp = crypto::blake2b::<U56>(key, &encryption_info);
+            let auth_key = crypto::blake2b::<U32>(key, &auth_info);
+            (tmp[..32].to_vec(), auth_key, tmp[32..].to_vec())
+        }
+        Version::V3 => {
+            let tmp = crypto::hkdf_sha384(key, &encryption_info);
+            let auth_key = crypto::hkdf_sha384(key, &auth_info);
+            (tmp[..32].to_vec(), auth_key.to_vec(), tmp[32..].to_vec())
+        }
+    }
+}
+
+fn stream_xor(protocol: Protocol, key: &[u8], nonce: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = key.try_into().expect("32-byte encryption key");
+    match protocol.version() {
+        Version::V4 => crypto::xchacha20_xor(key, nonce.try_into().expect("24-byte nonce"), data),
+        Version::V3 => crypto::aes256_ctr_xor(key, nonce.try_into().expect("16-byte nonce"), data),
+    }
+}
+
+fn local_tag(protocol: Protocol, auth_key: &[u8], pre_auth: &[u8]) -> Vec<u8> {
+    match protocol.version() {
+        Version::V4 => crypto::blake2b::<U32>(auth_key, pre_auth),
+        Version::V3 => hmac::sign(&hmac::Key::new(hmac::HMAC_SHA384, auth_key), pre_auth)
+            .as_ref()
+            .to_vec(),
+    }
+}
+
+/// Checks a local token's tag in constant time.
+fn local_tag_matches(protocol: Protocol, auth_key: &[u8], pre_auth: &[u8], tag: &[u8]) -> bool {
+    match protocol.version() {
+        Version::V4 => crypto::blake2b_verify::<U32>(auth_key, pre_auth, tag),
+        Version::V3 => {
+            hmac::verify(&hmac::Key::new(hmac::HMAC_SHA384, auth_key), pre_auth, tag).is_ok()
+        }
+    }
+}
+
+fn encrypt(
+    protocol: Protocol,
+    key: &[u8; 32],
+    message: &[u8],
+    footer: &[u8],
+    implicit: &[u8],
+) -> Vec<u8> {
+    let mut nonce = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let (encryption_key, auth_key, stream_nonce) = split_local_key(protocol, key, &nonce);
+    let ciphertext = stream_xor(protocol, &encryption_key, &stream_nonce, message);
+    let pre_auth = pae(&[
+        protocol.header().as_bytes(),
+        &nonce,
+        &ciphertext,
+        footer,
+        implicit,
+    ]);
+    let tag = local_tag(protocol, &auth_key, &pre_auth);
+    [nonce.as_slice(), &ciphertext, &tag].concat()
+}
+
+fn decrypt(parts: &Parts, key: &[u8; 32], implicit: &[u8]) -> Result<Vec<u8>> {
+    let protocol = parts.protocol;
+    if parts.body.len() < 32 + protocol.tag_len() {
+        bail!("The {} token body is too short", protocol);
+    }
+    let (nonce, rest) = parts.body.split_at(32);
+    let (ciphertext, tag) = rest.split_at(rest.len() - protocol.tag_len());
+
+    let (encryption_key, auth_key, stream_nonce) = split_local_key(protocol, key, nonce);
+    let pre_auth = pae(&[
+        protocol.header().as_bytes(),
+        nonce,
+        ciphertext,
+        &parts.footer,
+        implicit,
+    ]);
+    if !local_tag_matches(protocol, &auth_key, &pre_auth, tag) {
+        bail!("Authentication failed: wrong key, or the token, footer or implicit assertion was modified");
+    }
+    Ok(stream_xor(
+        protocol,
+        &encryption_key,
+        &stream_nonce,
+        ciphertext,
+    ))
+}
+
+/// Message the public protocols sign. v3 binds the compressed public key to prevent key
+/// substitution.
+fn signed_message(
+    protocol: Protocol,
+    public_key: &[u8],
+    message: &[u8],
+    footer: &[u8],
+    implicit: &[u8],
+) -> Vec<u8> {
+    let header = protocol.header().as_bytes();
+    match protocol.version() {
+        Version::V4 => pae(&[header, message, footer, implicit]),
+        Version::V3 => pae(&[public_key, header, message, footer, implicit]),
+    }
+}
+
+/// Converts RFC 3339 `exp`, `nbf` and `iat` claims to Unix timestamps for the JWT validator.
+fn numeric_claims(mut claims: Map<String, Value>) -> Map<String, Value> {
+    for name in ["exp", "nbf", "iat"] {
+        let timestamp = claims
+            .get(name)
+            .and_then(Value::as_str)
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+            .map(|datetime| datetime.timestamp());
+        if let Some(timestamp) = timestamp {
+            claims.insert(name.to_string(), timestamp.into());
+        }
+    }
+    claims
+}
+
+#[derive(Debug, Clone)]
+pub struct PasetoTool {
+    pub token: String,
+    /// JSON payload, decrypted for local tokens
+    pub payload: String,
+    pub footer: String,
+    /// Data bound to the token without being included in it
+    pub implicit_assertion: String,
+    pub protocol: Protocol,
+    /// Symmetric key of the local protocols
+    pub key: String,
+    pub secret_key: String,
+    pub public_key: String,
+    pub verified: Option<bool>,
+    pub claim_expectations: ClaimExpectations,
+    pub claim_report: Option<ClaimReport>,
+}
+
+impl Default for PasetoTool {
+    fn default() -> Self {
+        Self {
+            token: String::new(),
+            payload: "{\n  \"sub\": \"1234567890\",\n  \"exp\": \"2030-01-01T00:00:00+00:00\"\n}"
+                .to_string(),
+            footer: String::new(),
+            implicit_assertion: String::new(),
+            protocol: Protocol::default(),
+            key: String::new(),
+            secret_key: String::new(),
+            public_key: String::new(),
+            verified: None,
+            claim_expectations: ClaimExpectations::default(),
+            claim_report: None,
+        }
+    }
+}
+
+impl PasetoTool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.token.clear();
+        self.payload.clear();
+        self.footer.clear();
+        self.implicit_assertion.clear();
+        self.key.clear();
+        self.secret_key.clear();
+        self.public_key.clear();
+        self.verified = None;
+        self.claim_report = None;
+    }
+
+    /// Encrypts or signs the payload with the selected protocol.
+    pub fn encode(&mut self) -> Result<()> {
+        let payload = serde_json::from_str::<Value>(&self.payload)
+            .map_err(|e| anyhow!("The payload must be JSON: {}", e))?;
+        let message = serde_json::to_vec(&payload)?;
+        let footer = self.footer.as_bytes();
+        let implicit = self.implicit_assertion.as_bytes();
+
+        let body = match self.protocol.purpose() {
+            Purpose::Local => {
+                let key = keys::local_key(&self.key, self.protocol)?;
+                encrypt(self.protocol, &key, &message, footer, implicit)
+            }
+            Purpose::Public => {
+                let key = keys::signing_key(&self.secret_key, self.protocol)?;
+                let signed =
+                    signed_message(self.protocol, &key.public_key(), &message, footer, implicit);
+                [message, key.sign(&signed)?].concat()
+            }
+        };
+
+        self.token = format!("{}{}", self.protocol.header(), URL_SAFE_NO_PAD.encode(body));
+        if !footer.is_empty() {
+            self.token.push('.');
+            self.token.push_str(&URL_SAFE_NO_PAD.encode(footer));
+        }
+        self.verified = Some(true);
+        Ok(())
+    }
+
+    /// Reads the token: decrypts local tokens with the key, and shows the payload of public
+    /// tokens, which are only checked by [`Self::verify`].
+    pub fn decode(&mut self) -> Result<()> {
+        let parts = split(&self.token)?;
+        self.protocol = parts.protocol;
+        self.footer = String::from_utf8_lossy(&parts.footer).into_owned();
+        self.verified = None;
+
+        let message = match parts.protocol.purpose() {
+            Purpose::Local => {
+                if self.key.trim().is_empty() {
+                    bail!(
+                        "{} tokens are encrypted: enter the key to decrypt",
+                        parts.protocol
+                    );
+                }
+                let key = keys::local_key(&self.key, parts.protocol)?;
+                let message = decrypt(&parts, &key, self.implicit_assertion.as_bytes());
+                self.verified = Some(message.is_ok());
+                message?
+            }
+            Purpose::Public => {
+                let len = parts.body.len();
+                if len < parts.protocol.signature_len() {
+                    bail!("The {} token body is too short", parts.protocol);
+                }
+                parts.body[..len - parts.protocol.signature_len()].to_vec()
+            }
+        };
+
+        self.payload = serde_json::from_slice::<Value>(&message)
+            .and_then(|payload| serde_json::to_string_pretty(&payload))
+            .unwrap_or_else(|_| String::from_utf8_lossy(&message).into_owned());
+        Ok(())
+    }
+
+    /// Checks the signature of public tokens, or the authentication tag of local tokens.
+    pub fn verify(&mut self) -> Result<()> {
+        let parts = split(&self.token)?;
+        if parts.protocol.purpose() == Purpose::Local {
+            return self.decode();
+        }
+
+        self.verified = None;
+        let public_key = keys::verifying_key(&self.public_key, parts.protocol)?;
+        let message_len = parts
+            .body
+            .len()
+            .checked_sub(parts.protocol.signature_len())
+            .ok_or_else(|| anyhow!("The {} token body is too short", parts.protocol))?;
+        let (message, signature) = parts.body.split_at(message_len);
+        let (algorithm, bound_key): (&dyn VerificationAlgorithm, _) = match parts.protocol.version()
+        {
+            Version::V4 => (&ED25519, public_key.clone()),
+            Version::V3 => (&ECDSA_P384_SHA384_FIXED, keys::compress(&public_key)?),
+        };
+        let signed = signed_message(
+            parts.protocol,
+            &bound_key,
+            message,
+            &parts.footer,
+            self.implicit_assertion.as_bytes(),
+        );
+        self.verified = Some(
+            UnparsedPublicKey::new(algorithm, &public_key)
+                .verify(&signed, signature)
+                .is_ok(),
+        );
+        Ok(())
+    }
+
+    /// Builds the claim report for the payload, using the last verification result.
+    pub fn validate_claims(&mut self, datetime: &DateTimeProcessor) -> Result<()> {
+        let claims = match serde_json::from_str::<Map<String, Value>>(&self.payload) {
+            Ok(claims) => claims,
+            Err(err) => {
+                self.claim_report = None;
+                bail!("The payload is not a JSON object: {}", err);
+            }
+        };
+        self.claim_report = Some(claims::validate(
+            &numeric_claims(claims),
+            &self.claim_expectations,
+            chrono::Utc::now(),
+            &|timestamp| datetime.get_relative_time(timestamp),
+            self.verified,
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V4_SECRET: &str = "b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+    const V4_PUBLIC: &str = "1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+    const V3_SECRET: &str = "7b37ef78e404b7b1fd78b326918ebedf0ea23b39bd030a7d1d1f5990bfc67261d8f608669e99368f48b5040ace0dff14";
+    const V3_PUBLIC: &str = "02821818400460a48c5903b41cd383b04a0b22a7f96bdd084a0ddfeb427d73049a527ff515bbe5d84d7565800fb89fb8ef";
+    const LOCAL_KEY: &str = "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f";
+    const FOOTER: &str = r#"{"kid":"zVhMiPBP9fRf2snEcT7gFTioeA9COcNy9DfgL1W60haN"}"#;
+    const IMPLICIT: &str = r#"{"test-vector":"4-E-9"}"#;
+
+    fn tool(token: &str) -> PasetoTool {
+        PasetoTool {
+            token: token.to_string(),
+            key: LOCAL_KEY.to_string(),
+            implicit_assertion: IMPLICIT.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_v4_public_signs_and_verifies() {
+        let mut tool = PasetoTool {
+            payload: r#"{"data":"this is a signed message","exp":"2022-01-01T00:00:00+00:00"}"#
+                .to_string(),
+            protocol: Protocol::V4Public,
+            secret_key: V4_SECRET.to_string(),
+            public_key: format!(
+                "k4.public.{}",
+                URL_SAFE_NO_PAD.encode(hex::decode(V4_PUBLIC).unwrap())
+            ),
+            ..Default::default()
+        };
+        tool.encode().unwrap();
+        assert_eq!(
+            tool.token,
+            "v4.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9bg_XBBzds8lTZShVlwwKSgeKpLT3yukTw6JUz3W4h_ExsQV-P0V54zemZDcAxFaSeef1QlXEFtkqxT1ciiQEDA"
+        );
+
+        tool.verify().unwrap();
+        assert_eq!(tool.verified, Some(true));
+        tool.implicit_assertion = "other".to_string();
+        tool.verify().unwrap();
+        assert_eq!(tool.verified, Some(false));
+    }
+
+    #[test]
+    fn test_local_tokens_decrypt() {
+        for token in [
+            "v4.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvSwscFlAl1pk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XKd22te5RWNWc_8TMhKx9wNtudnq5_QJ99p0U9UEbUuYw.eyJraWQiOiJ6VmhNaVBCUDlmUmYyc25FY1Q3Z0ZUaW9lQTlDT2NOeTlEZmdMMVc2MGhhTiJ9",
+            "v3.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAADbfcIURX_0pVZVU1mAESUzrKZAsRm2EsD6yBoZYn6cpVZNzSJOhSDN-sRaWjfLU-yn9OJH1J_B8GKtOQ9gSQlb8yk9IzbxLidQSNxf55kQWgZJe58hm2r_F7xCU4s7L2fjdQ3CroyUofMGy0RWSZA9vdhKxY8.eyJraWQiOiJ6VmhNaVBCUDlmUmYyc25FY1Q3Z0ZUaW9lQTlDT2NOeTlEZmdMMVc2MGhhTiJ9",
+        ] {
+            let mut tool = tool(token);
+            tool.decode().unwrap();
+            assert_eq!(tool.verified, Some(true));
+            assert_eq!(tool.footer, FOOTER);
+            assert!(tool.payload.contains("this is a secret message"));
+
+            tool.implicit_assertion.clear();
+            assert!(tool.decode().is_err());
+            assert_eq!(tool.verified, Some(false));
+        }
+    }
+
+    #[test]
+    fn test_v3_public_keys() {
+        let mut tool = tool("v3.public.eyJkYXRhIjoidGhpcyBpcyBhIHNlY3JldCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9BwRfZvtGggHKR1V44G8PyB27lVbmKplCANVPpKyrIZe2dheXQ_OxL4cTv_tTAWrS9mib4eJhCtDAYaNINKxiEeHIx89AmCsJ82F8NlMLeCrtouDEL68DKx84_GNb0Lwy.eyJraWQiOiJ6VmhNaVBCUDlmUmYyc25FY1Q3Z0ZUaW9lQTlDT2NOeTlEZmdMMVc2MGhhTiJ9");
+        tool.decode().unwrap();
+        assert_eq!(tool.protocol, Protocol::V3Public);
+        assert_eq!(tool.verified, None);
+
+        for public_key in [
+            V3_PUBLIC,
+            "-----BEGIN PUBLIC KEY-----\nMHYwEAYHKoZIzj0CAQYFK4EEACIDYgAEghgYQARgpIxZA7Qc04OwSgsip/lr3QhK\nDd/rQn1zBJpSf/UVu+XYTXVlgA+4n7jvzoYuSto7i9ob5PZbsYXXoRx6oEVbPXKh\nTRj8N+2KFFeXSSNY7qjf7WV4lqXAGEwC\n-----END PUBLIC KEY-----\n",
+        ] {
+            tool.public_key = public_key.to_string();
+            tool.verify().unwrap();
+            assert_eq!(tool.verified, Some(true));
+        }
+
+        // A raw secret scalar needs its public point derived to sign
+        let key = keys::signing_key(V3_SECRET, Protocol::V3Public).unwrap();
+        assert_eq!(hex::encode(key.public_key()), V3_PUBLIC);
+        tool.secret_key = V3_SECRET.to_string();
+        tool.encode().unwrap();
+        tool.verify().unwrap();
+        assert_eq!(tool.verified, Some(true));
+    }
+
+    #[test]
+    fn test_key_mismatch_errors() {
+        let error = |result: Result<()>| result.unwrap_err().to_string();
+
+        let mut tool = tool("");
+        tool.protocol = Protocol::V4Public;
+        tool.secret_key = format!("k4.local.{}", URL_SAFE_NO_PAD.encode([7u8; 32]));
+        assert!(error(tool.encode()).contains("k4.local key, but v4.public needs a k4.secret key"));
+
+        tool.secret_key = V3_SECRET.to_string();
+        assert!(error(tool.encode()).contains("got 48 bytes (looks like a v3.public secret key)"));
+
+        tool.protocol = Protocol::V3Local;
+        tool.key = format!("k4.local.{}", URL_SAFE_NO_PAD.encode([7u8; 32]));
+        assert!(error(tool.encode()).contains("k4 key, but the token is v3.local"));
+
+        tool.key = "-----BEGIN PUBLIC KEY-----\nMCowBQYDK2VwAyEAHrnbu7wEfAP9cGBOAHHwmH4Wsot1ciXBHwBBXQ4gsaI=\n-----END PUBLIC KEY-----".to_string();
+        assert!(error(tool.encode()).contains("asymmetric keys are for v3.public"));
+
+        tool.protocol = Protocol::V3Public;
+        tool.token = "v3.public.AAAA".to_string();
+        tool.public_key = tool.key.clone();
+        assert!(error(tool.verify())
+            .contains("This is an Ed25519 key, but v3.public needs a P-384 ECDSA key"));
+    }
+
+    #[test]
+    fn test_round_trip_and_claims() {
+        for protocol in Protocol::variants() {
+            let mut tool = PasetoTool {
+                protocol: *protocol,
+                footer: FOOTER.to_string(),
+                key: LOCAL_KEY.to_string(),
+                secret_key: match protocol.version() {
+                    Version::V4 => V4_SECRET,
+                    Version::V3 => V3_SECRET,
+                }
+                .to_string(),
+                public_key: match protocol.version() {
+                    Version::V4 => V4_PUBLIC,
+                    Version::V3 => V3_PUBLIC,
+                }
+                .to_string(),
+                ..Default::default()
+            };
+            tool.encode().unwrap();
+            assert!(tool.token.starts_with(protocol.header()));
+
+            tool.payload.clear();
+            tool.decode().unwrap();
+            tool.verify().unwrap();
+            assert_eq!(tool.verified, Some(true), "{}", protocol);
+            assert!(tool.payload.contains("2030-01-01T00:00:00+00:00"));
+
+            tool.validate_claims(&DateTimeProcessor::new()).unwrap();
+            let report = tool.claim_report.as_ref().unwrap();
+            assert!(report.acceptable, "{}", report.verdict);
+        }
+    }
+}
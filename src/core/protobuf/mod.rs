@@ -0,0 +1,396 @@
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+use crate::core::encoding::input::BinaryInput;
+
+/// Nested messages deeper than this are shown as raw bytes.
+const MAX_DEPTH: usize = 64;
+const MAX_FIELD_NUMBER: u64 = (1 << 29) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireType {
+    Varint,
+    I64,
+    Len,
+    StartGroup,
+    EndGroup,
+    I32,
+}
+
+impl fmt::Display for WireType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireType::Varint => write!(f, "varint"),
+            WireType::I64 => write!(f, "i64"),
+            WireType::Len => write!(f, "len"),
+            WireType::StartGroup => write!(f, "sgroup"),
+            WireType::EndGroup => write!(f, "egroup"),
+            WireType::I32 => write!(f, "i32"),
+        }
+    }
+}
+
+impl WireType {
+    fn from_tag(tag: u64) -> Option<Self> {
+        match tag & 0x7 {
+            0 => Some(WireType::Varint),
+            1 => Some(WireType::I64),
+            2 => Some(WireType::Len),
+            3 => Some(WireType::StartGroup),
+            4 => Some(WireType::EndGroup),
+            5 => Some(WireType::I32),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    Message(Vec<Field>),
+    Group(Vec<Field>),
+    String(String),
+    PackedVarints(Vec<u64>),
+    Bytes(Vec<u8>),
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Varint(v) => write!(
+                f,
+                "{} (signed {}, zigzag {})",
+                v,
+                *v as i64,
+                zigzag_decode(*v)
+            ),
+            FieldValue::Fixed64(v) => write!(
+                f,
+                "{} (signed {}, double {})",
+                v,
+                *v as i64,
+                f64::from_bits(*v)
+            ),
+            FieldValue::Fixed32(v) => write!(
+                f,
+                "{} (signed {}, float {})",
+                v,
+                *v as i32,
+                f32::from_bits(*v)
+            ),
+            FieldValue::Message(fields) => write!(f, "message with {} fields", fields.len()),
+            FieldValue::Group(fields) => write!(f, "group with {} fields", fields.len()),
+            FieldValue::String(s) => write!(f, "{:?}", s),
+            FieldValue::PackedVarints(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "packed [{}]", values.join(", "))
+            }
+            FieldValue::Bytes(bytes) => write!(f, "bytes {}", hex::encode(bytes)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub number: u64,
+    pub wire_type: WireType,
+    /// Byte offset of the tag within the top-level input
+    pub offset: usize,
+    /// Length of the encoded value, excluding the tag
+    pub length: usize,
+    pub value: FieldValue,
+}
+
+impl Field {
+    pub fn children(&self) -> Option<&[Field]> {
+        match &self.value {
+            FieldValue::Message(fields) | FieldValue::Group(fields) => Some(fields),
+            _ => None,
+        }
+    }
+}
+
+pub fn zigzag_decode(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Decodes a protobuf message without a schema.
+pub fn decode_message(bytes: &[u8]) -> Result<Vec<Field>> {
+    let mut reader = Reader {
+        bytes,
+        pos: 0,
+        base: 0,
+    };
+    let fields = reader.read_fields(0, None)?;
+    if fields.is_empty() {
+        return Err(anyhow!("Input contains no protobuf fields"));
+    }
+    Ok(fields)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Offset of `bytes` within the top-level input, for error messages
+    base: usize,
+}
+
+impl Reader<'_> {
+    fn offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let start = self.offset();
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .bytes
+                .get(self.pos)
+                .ok_or_else(|| anyhow!("Truncated varint at byte {}", start))?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(anyhow!("Varint longer than 10 bytes at byte {}", start))
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<&[u8]> {
+        let remaining = self.bytes.len() - self.pos;
+        if len > remaining {
+            return Err(anyhow!(
+                "Length {} at byte {} exceeds the remaining {} bytes",
+                len,
+                self.offset(),
+                remaining
+            ));
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    /// Reads fields until the end of input, or until the end-group tag for `group`.
+    fn read_fields(&mut self, depth: usize, group: Option<u64>) -> Result<Vec<Field>> {
+        let mut fields = Vec::new();
+
+        while self.pos < self.bytes.len() {
+            let offset = self.offset();
+            let tag = self.read_varint()?;
+            let number = tag >> 3;
+            let wire_type = WireType::from_tag(tag)
+                .ok_or_else(|| anyhow!("Invalid wire type {} at byte {}", tag & 0x7, offset))?;
+            if number == 0 || number > MAX_FIELD_NUMBER {
+                return Err(anyhow!(
+                    "Invalid field number {} at byte {}",
+                    number,
+                    offset
+                ));
+            }
+
+            let value_start = self.pos;
+            let value = match wire_type {
+                WireType::Varint => FieldValue::Varint(self.read_varint()?),
+                WireType::I64 => {
+                    let bytes = self.read_slice(8)?;
+                    FieldValue::Fixed64(u64::from_le_bytes(bytes.try_into()?))
+                }
+                WireType::I32 => {
+                    let bytes = self.read_slice(4)?;
+                    FieldValue::Fixed32(u32::from_le_bytes(bytes.try_into()?))
+                }
+                WireType::Len => {
+                    let len = self.read_varint()? as usize;
+                    let base = self.offset();
+                    let bytes = self.read_slice(len)?;
+                    interpret_len(bytes, base, depth)
+                }
+                WireType::StartGroup => {
+                    if depth >= MAX_DEPTH {
+                        return Err(anyhow!("Groups nested too deeply at byte {}", offset));
+                    }
+                    FieldValue::Group(self.read_fields(depth + 1, Some(number))?)
+                }
+                WireType::EndGroup => {
+                    if group == Some(number) {
+                        return Ok(fields);
+                    }
+                    return Err(anyhow!(
+                        "Unexpected end of group {} at byte {}",
+                        number,
+                        offset
+                    ));
+                }
+            };
+
+            fields.push(Field {
+                number,
+                wire_type,
+                offset,
+                length: self.pos - value_start,
+                value,
+            });
+        }
+
+        match group {
+            Some(number) => Err(anyhow!("Group {} is never closed", number)),
+            None => Ok(fields),
+        }
+    }
+}
+
+/// Length-delimited values are ambiguous without a schema. They are tried as a nested
+/// message, then as a UTF-8 string, then as packed varints, and finally kept as raw bytes.
+fn interpret_len(bytes: &[u8], base: usize, depth: usize) -> FieldValue {
+    if bytes.is_empty() {
+        return FieldValue::String(String::new());
+    }
+
+    if depth < MAX_DEPTH {
+        let mut reader = Reader {
+            bytes,
+            pos: 0,
+            base,
+        };
+        if let Ok(fields) = reader.read_fields(depth + 1, None) {
+            return FieldValue::Message(fields);
+        }
+    }
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+            return FieldValue::String(text.to_string());
+        }
+    }
+
+    let mut reader = Reader {
+        bytes,
+        pos: 0,
+        base,
+    };
+    let mut values = Vec::new();
+    while reader.pos < bytes.len() {
+        match reader.read_varint() {
+            Ok(value) => values.push(value),
+            Err(_) => return FieldValue::Bytes(bytes.to_vec()),
+        }
+    }
+    FieldValue::PackedVarints(values)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProtobufDecoder {
+    pub input: String,
+    pub input_format: BinaryInput,
+    pub fields: Vec<Field>,
+    pub error: Option<String>,
+}
+
+impl ProtobufDecoder {
+    pub fn decode(&mut self) -> Result<()> {
+        let result = self
+            .input_format
+            .decode(&self.input)
+            .and_then(|bytes| decode_message(&bytes));
+        self.set_result(result)
+    }
+
+    /// Decodes bytes produced elsewhere, e.g. by the encoding tool.
+    pub fn decode_from(&mut self, bytes: Vec<u8>) -> Result<()> {
+        self.input = hex::encode(&bytes);
+        self.input_format = BinaryInput::Hex;
+        let result = decode_message(&bytes);
+        self.set_result(result)
+    }
+
+    fn set_result(&mut self, result: Result<Vec<Field>>) -> Result<()> {
+        self.fields.clear();
+        match result {
+            Ok(fields) => {
+                self.fields = fields;
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.fields.clear();
+        self.error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_scalar_fields() {
+        // 1: 150, 2: "testing", 3: fixed32 1.0f, 4: -2 as sint64
+        let bytes = hex::decode("089601120774657374696e671d0000803f2003").unwrap();
+        let fields = decode_message(&bytes).unwrap();
+        assert_eq!(fields[0].value, FieldValue::Varint(150));
+        assert_eq!(fields[1].value, FieldValue::String("testing".to_string()));
+        assert_eq!(fields[2].value, FieldValue::Fixed32(1.0f32.to_bits()));
+        assert_eq!(zigzag_decode(3), -2);
+        assert_eq!(fields[3].value.to_string(), "3 (signed 3, zigzag -2)");
+        assert_eq!(fields[1].offset, 3);
+    }
+
+    #[test]
+    fn decodes_nested_messages_and_packed_fields() {
+        // 3: { 1: 150 }, 4: packed [3, 270, 86942]
+        let bytes = hex::decode("1a030896012206038e029ea705").unwrap();
+        let fields = decode_message(&bytes).unwrap();
+        let nested = fields[0].children().unwrap();
+        assert_eq!(nested[0].value, FieldValue::Varint(150));
+        assert_eq!(nested[0].offset, 2);
+        assert_eq!(
+            fields[1].value,
+            FieldValue::PackedVarints(vec![3, 270, 86942])
+        );
+    }
+
+    #[test]
+    fn decodes_groups() {
+        // group 1 { 2: 1 } 3: 5
+        let bytes = hex::decode("0b10010c1805").unwrap();
+        let fields = decode_message(&bytes).unwrap();
+        assert_eq!(fields[0].wire_type, WireType::StartGroup);
+        assert_eq!(
+            fields[0].children().unwrap()[0].value,
+            FieldValue::Varint(1)
+        );
+        assert_eq!(fields[1].value, FieldValue::Varint(5));
+    }
+
+    #[test]
+    fn reports_malformed_input_offsets() {
+        let err = |input: &str| decode_message(&hex::decode(input).unwrap()).unwrap_err();
+        assert_eq!(err("0896").to_string(), "Truncated varint at byte 1");
+        assert_eq!(err("08010f").to_string(), "Invalid wire type 7 at byte 2");
+        assert_eq!(
+            err("0a056869").to_string(),
+            "Length 5 at byte 2 exceeds the remaining 2 bytes"
+        );
+    }
+
+    #[test]
+    fn decodes_base64_input() {
+        let mut decoder = ProtobufDecoder {
+            input: "CJYB".to_string(),
+            ..Default::default()
+        };
+        decoder.decode().unwrap();
+        assert_eq!(decoder.fields[0].value, FieldValue::Varint(150));
+    }
+}
@@ -0,0 +1,319 @@
+use anyhow::{anyhow, Result};
+use image::{imageops, DynamicImage, ImageFormat, Rgba, RgbaImage};
+use qrcode::{types::Color, EcLevel, QrCode, Version};
+use rqrr::PreparedImage;
+use std::{fmt, path::Path};
+
+/// Light modules required around the symbol for scanners to find it.
+const QUIET_ZONE: u32 = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum QrErrorCorrection {
+    Low,
+    #[default]
+    Medium,
+    Quartile,
+    High,
+}
+
+impl fmt::Display for QrErrorCorrection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrErrorCorrection::Low => write!(f, "L (7%)"),
+            QrErrorCorrection::Medium => write!(f, "M (15%)"),
+            QrErrorCorrection::Quartile => write!(f, "Q (25%)"),
+            QrErrorCorrection::High => write!(f, "H (30%)"),
+        }
+    }
+}
+
+impl QrErrorCorrection {
+    pub fn variants() -> &'static [QrErrorCorrection] {
+        &[
+            QrErrorCorrection::Low,
+            QrErrorCorrection::Medium,
+            QrErrorCorrection::Quartile,
+            QrErrorCorrection::High,
+        ]
+    }
+
+    /// Level of the two error correction bits in the format information.
+    fn from_format_bits(bits: u16) -> Option<Self> {
+        match bits {
+            0 => Some(QrErrorCorrection::Medium),
+            1 => Some(QrErrorCorrection::Low),
+            2 => Some(QrErrorCorrection::High),
+            3 => Some(QrErrorCorrection::Quartile),
+            _ => None,
+        }
+    }
+
+    fn ec_level(&self) -> EcLevel {
+        match self {
+            QrErrorCorrection::Low => EcLevel::L,
+            QrErrorCorrection::Medium => EcLevel::M,
+            QrErrorCorrection::Quartile => EcLevel::Q,
+            QrErrorCorrection::High => EcLevel::H,
+        }
+    }
+}
+
+/// Encodes `text` and renders it with `module_size` pixels per module and a quiet zone.
+pub fn render(
+    text: &str,
+    error_correction: QrErrorCorrection,
+    module_size: u32,
+) -> Result<(RgbaImage, usize)> {
+    let code = QrCode::with_error_correction_level(text.as_bytes(), error_correction.ec_level())
+        .map_err(|e| anyhow!("Failed to encode QR code: {}", e))?;
+    let version = match code.version() {
+        Version::Normal(version) | Version::Micro(version) => version as usize,
+    };
+    let width = code.width() as u32;
+    let colors = code.to_colors();
+    let module_size = module_size.max(1);
+    let side = (width + QUIET_ZONE * 2) * module_size;
+
+    let image = RgbaImage::from_fn(side, side, |x, y| {
+        let col = (x / module_size).checked_sub(QUIET_ZONE);
+        let row = (y / module_size).checked_sub(QUIET_ZONE);
+        let dark = match (col, row) {
+            (Some(col), Some(row)) if col < width && row < width => {
+                colors[(row * width + col) as usize] == Color::Dark
+            }
+            _ => false,
+        };
+        if dark {
+            Rgba([0, 0, 0, 255])
+        } else {
+            Rgba([255, 255, 255, 255])
+        }
+    });
+    Ok((image, version))
+}
+
+/// Text and symbol parameters of a decoded QR code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    pub text: String,
+    pub version: usize,
+    pub error_correction: QrErrorCorrection,
+}
+
+/// Finds and decodes a QR code in the image, dark-on-light or inverted.
+pub fn decode_image(image: &RgbaImage) -> Result<Decoded> {
+    let gray = DynamicImage::ImageRgba8(image.clone()).into_luma8();
+    let mut inverted = gray.clone();
+    imageops::invert(&mut inverted);
+
+    let mut last_error = None;
+    for gray in [gray, inverted] {
+        for grid in PreparedImage::prepare(gray).detect_grids() {
+            match grid.decode() {
+                Ok((meta, text)) => {
+                    return Ok(Decoded {
+                        text,
+                        version: meta.version.0,
+                        error_correction: QrErrorCorrection::from_format_bits(meta.ecc_level)
+                            .ok_or_else(|| anyhow!("Invalid error correction level"))?,
+                    })
+                }
+                Err(e) => last_error = Some(anyhow!("Failed to decode QR code: {}", e)),
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow!("No QR code found in the image")))
+}
+
+#[derive(Debug, Clone)]
+pub struct QrTool {
+    pub text: String,
+    pub error_correction: QrErrorCorrection,
+    /// Pixels per module of the generated image
+    pub module_size: u32,
+    pub image: Option<RgbaImage>,
+    pub version: Option<usize>,
+    pub decoded: Option<Decoded>,
+    pub error: Option<String>,
+}
+
+impl Default for QrTool {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            error_correction: QrErrorCorrection::default(),
+            module_size: 8,
+            image: None,
+            version: None,
+            decoded: None,
+            error: None,
+        }
+    }
+}
+
+impl QrTool {
+    pub fn generate(&mut self) -> Result<()> {
+        if self.text.is_empty() {
+            self.image = None;
+            self.version = None;
+            self.error = None;
+            return Ok(());
+        }
+        match render(&self.text, self.error_correction, self.module_size) {
+            Ok((image, version)) => {
+                self.image = Some(image);
+                self.version = Some(version);
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.image = None;
+                self.version = None;
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// PNG encoding of the generated image.
+    pub fn png_bytes(&self) -> Result<Vec<u8>> {
+        let image = self
+            .image
+            .as_ref()
+            .ok_or_else(|| anyhow!("Generate a QR code first"))?;
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut bytes, ImageFormat::Png)
+            .map_err(|e| anyhow!("Failed to encode PNG: {}", e))?;
+        Ok(bytes.into_inner())
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let bytes = self.png_bytes()?;
+        std::fs::write(path.as_ref(), bytes)
+            .map_err(|e| anyhow!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    pub fn decode(&mut self, image: &RgbaImage) -> Result<()> {
+        match decode_image(image) {
+            Ok(decoded) => {
+                self.decoded = Some(decoded);
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.decoded = None;
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    pub fn decode_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let image = image::open(path.as_ref())
+            .map_err(|e| anyhow!("Failed to open {}: {}", path.as_ref().display(), e))?
+            .to_rgba8();
+        self.decode(&image)
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.image = None;
+        self.version = None;
+        self.decoded = None;
+        self.error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(text: &str, error_correction: QrErrorCorrection, module_size: u32) -> Decoded {
+        let (image, _) = render(text, error_correction, module_size).unwrap();
+        decode_image(&image).unwrap()
+    }
+
+    #[test]
+    fn test_round_trips_across_versions_and_levels() {
+        let long = "The quick brown fox jumps over the lazy dog. ".repeat(12);
+        let cases = [
+            ("hello", QrErrorCorrection::Low),
+            ("HELLO WORLD 123", QrErrorCorrection::Medium),
+            (
+                "0123456789012345678901234567890",
+                QrErrorCorrection::Quartile,
+            ),
+            (
+                "https://example.com/some/path?query=value&x=1",
+                QrErrorCorrection::High,
+            ),
+            (long.as_str(), QrErrorCorrection::Medium),
+        ];
+        for (text, level) in cases {
+            let decoded = round_trip(text, level, 4);
+            assert_eq!(decoded.text, text);
+            assert_eq!(decoded.error_correction, level);
+        }
+    }
+
+    #[test]
+    fn test_decodes_unicode_and_large_versions() {
+        let text = "héllo wörld ✓ ".repeat(30);
+        let decoded = round_trip(&text, QrErrorCorrection::Low, 3);
+        assert!(decoded.version >= 7);
+        assert_eq!(decoded.text, text);
+    }
+
+    #[test]
+    fn test_decodes_inverted_and_scaled_images() {
+        let (image, _) = render("inverted", QrErrorCorrection::Medium, 5).unwrap();
+        let mut inverted = image.clone();
+        imageops::invert(&mut inverted);
+        assert_eq!(decode_image(&inverted).unwrap().text, "inverted");
+
+        let scaled = imageops::resize(&image, 333, 333, imageops::FilterType::Triangle);
+        assert_eq!(decode_image(&scaled).unwrap().text, "inverted");
+    }
+
+    #[test]
+    fn test_decodes_perspective_distorted_images() {
+        let (image, _) = render("tilted photo", QrErrorCorrection::Medium, 6).unwrap();
+        let side = image.width() as f32;
+        // Keystone homography, as for a code photographed at an angle: the top edge is
+        // narrower than the bottom one. Each output pixel is mapped back to the source.
+        let k = 0.3;
+        let tilted = RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+            let (u, v) = (x as f32 / side, y as f32 / side);
+            let t = v * (1.0 + k) / (1.0 + k + v * k);
+            let s = (u - 0.5) * (1.0 + k * (1.0 - t)) + 0.5;
+            if (0.0..1.0).contains(&s) {
+                *image.get_pixel((s * side) as u32, (t * side) as u32)
+            } else {
+                Rgba([255, 255, 255, 255])
+            }
+        });
+        assert_eq!(decode_image(&tilted).unwrap().text, "tilted photo");
+    }
+
+    #[test]
+    fn test_corrects_damaged_modules() {
+        let module_size = 4;
+        let (mut image, _) =
+            render("damaged but readable", QrErrorCorrection::High, module_size).unwrap();
+        // Blank out a block of data modules in the lower right area
+        let side = image.width();
+        for y in side / 2..side / 2 + module_size * 4 {
+            for x in side / 2..side / 2 + module_size * 4 {
+                image.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+        assert_eq!(decode_image(&image).unwrap().text, "damaged but readable");
+    }
+
+    #[test]
+    fn test_reports_missing_code() {
+        let blank = RgbaImage::from_pixel(64, 64, Rgba([255, 255, 255, 255]));
+        assert!(decode_image(&blank).is_err());
+    }
+}
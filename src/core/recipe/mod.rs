@@ -0,0 +1,432 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::core::encoding::{compression::Compression, display_bytes, EncodingType};
+
+/// Byte level operations that are not tied to a textual encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ByteTransform {
+    ToHex,
+    FromHex,
+    PrettyJson,
+    MinifyJson,
+    Uppercase,
+    Lowercase,
+    Trim,
+    Reverse,
+}
+
+impl fmt::Display for ByteTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteTransform::ToHex => write!(f, "To Hex"),
+            ByteTransform::FromHex => write!(f, "From Hex"),
+            ByteTransform::PrettyJson => write!(f, "Pretty JSON"),
+            ByteTransform::MinifyJson => write!(f, "Minify JSON"),
+            ByteTransform::Uppercase => write!(f, "Uppercase"),
+            ByteTransform::Lowercase => write!(f, "Lowercase"),
+            ByteTransform::Trim => write!(f, "Trim whitespace"),
+            ByteTransform::Reverse => write!(f, "Reverse bytes"),
+        }
+    }
+}
+
+impl ByteTransform {
+    pub fn variants() -> &'static [ByteTransform] {
+        &[
+            ByteTransform::ToHex,
+            ByteTransform::FromHex,
+            ByteTransform::PrettyJson,
+            ByteTransform::MinifyJson,
+            ByteTransform::Uppercase,
+            ByteTransform::Lowercase,
+            ByteTransform::Trim,
+            ByteTransform::Reverse,
+        ]
+    }
+
+    pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            ByteTransform::ToHex => Ok(hex::encode(input).into_bytes()),
+            ByteTransform::FromHex => {
+                let text = std::str::from_utf8(input)?;
+                let clean: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+                hex::decode(clean).map_err(|e| anyhow!("Invalid hex input: {}", e))
+            }
+            ByteTransform::PrettyJson => {
+                let value: serde_json::Value = serde_json::from_slice(input)?;
+                Ok(serde_json::to_vec_pretty(&value)?)
+            }
+            ByteTransform::MinifyJson => {
+                let value: serde_json::Value = serde_json::from_slice(input)?;
+                Ok(serde_json::to_vec(&value)?)
+            }
+            ByteTransform::Uppercase => Ok(String::from_utf8_lossy(input).to_uppercase().into()),
+            ByteTransform::Lowercase => Ok(String::from_utf8_lossy(input).to_lowercase().into()),
+            ByteTransform::Trim => Ok(input.trim_ascii().to_vec()),
+            ByteTransform::Reverse => Ok(input.iter().rev().copied().collect()),
+        }
+    }
+}
+
+/// A single operation in a recipe. Every step takes bytes and produces bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecipeStep {
+    Encode(EncodingType),
+    Decode(EncodingType),
+    Compress(Compression),
+    /// Decompresses with the given format, or detects it from magic bytes when `None`
+    Decompress(Option<Compression>),
+    Transform(ByteTransform),
+}
+
+impl fmt::Display for RecipeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecipeStep::Encode(encoding) => write!(f, "{} Encode", encoding),
+            RecipeStep::Decode(encoding) => write!(f, "{} Decode", encoding),
+            RecipeStep::Compress(compression) => write!(f, "{} Compress", compression),
+            RecipeStep::Decompress(Some(compression)) => write!(f, "{} Decompress", compression),
+            RecipeStep::Decompress(None) => write!(f, "Auto Decompress"),
+            RecipeStep::Transform(transform) => write!(f, "{}", transform),
+        }
+    }
+}
+
+impl RecipeStep {
+    /// All steps that can be added to a recipe, in the order they are offered in the UI.
+    pub fn catalog() -> Vec<RecipeStep> {
+        let mut steps = Vec::new();
+        for encoding in EncodingType::variants() {
+            steps.push(RecipeStep::Decode(encoding.clone()));
+            steps.push(RecipeStep::Encode(encoding.clone()));
+        }
+        steps.push(RecipeStep::Decompress(None));
+        for compression in Compression::variants() {
+            steps.push(RecipeStep::Decompress(Some(*compression)));
+            steps.push(RecipeStep::Compress(*compression));
+        }
+        steps.extend(
+            ByteTransform::variants()
+                .iter()
+                .map(|t| RecipeStep::Transform(*t)),
+        );
+        steps
+    }
+
+    pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            RecipeStep::Encode(encoding) => Ok(encoding.encode_bytes(input)?.into_bytes()),
+            RecipeStep::Decode(encoding) => {
+                let text = std::str::from_utf8(input)
+                    .map_err(|_| anyhow!("{} input is not valid UTF-8", encoding))?;
+                let text = if encoding.is_escape() {
+                    text
+                } else {
+                    text.trim()
+                };
+                encoding.decode_bytes(text)
+            }
+            RecipeStep::Compress(compression) => compression.compress(input),
+            RecipeStep::Decompress(Some(compression)) => compression.decompress(input),
+            RecipeStep::Decompress(None) => {
+                Compression::decompress_detected(input).map(|(_, output)| output)
+            }
+            RecipeStep::Transform(transform) => transform.apply(input),
+        }
+    }
+}
+
+/// Output of a single step after running a recipe.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StepOutput {
+    pub bytes: Vec<u8>,
+    pub error: Option<String>,
+}
+
+impl StepOutput {
+    /// Renders the output as UTF-8 when possible, falling back to a hex dump for binary data.
+    pub fn as_text(&self) -> String {
+        if let Some(error) = &self.error {
+            return error.clone();
+        }
+        display_bytes(&self.bytes)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recipe {
+    pub name: String,
+    pub steps: Vec<RecipeStep>,
+}
+
+impl Recipe {
+    pub fn new(name: impl Into<String>, steps: Vec<RecipeStep>) -> Self {
+        Self {
+            name: name.into(),
+            steps,
+        }
+    }
+
+    /// Runs every step in order, returning one output per executed step.
+    /// Execution stops at the first failing step, whose output carries the error.
+    pub fn run(&self, input: &[u8]) -> Vec<StepOutput> {
+        let mut outputs = Vec::with_capacity(self.steps.len());
+        let mut current = input.to_vec();
+
+        for (i, step) in self.steps.iter().enumerate() {
+            match step.apply(&current) {
+                Ok(bytes) => {
+                    current = bytes.clone();
+                    outputs.push(StepOutput { bytes, error: None });
+                }
+                Err(e) => {
+                    outputs.push(StepOutput {
+                        bytes: Vec::new(),
+                        error: Some(format!("Step {} ({}) failed: {}", i + 1, step, e)),
+                    });
+                    break;
+                }
+            }
+        }
+
+        outputs
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedRecipes {
+    recipes: Vec<Recipe>,
+}
+
+impl SavedRecipes {
+    pub const FILE_NAME: &'static str = "recipes.json";
+
+    pub fn iter(&self) -> impl Iterator<Item = &Recipe> {
+        self.recipes.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Recipe> {
+        self.recipes.iter().find(|r| r.name == name)
+    }
+
+    /// Adds the recipe, replacing any saved recipe with the same name.
+    pub fn upsert(&mut self, recipe: Recipe) {
+        match self.recipes.iter_mut().find(|r| r.name == recipe.name) {
+            Some(existing) => *existing = recipe,
+            None => self.recipes.push(recipe),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Recipe> {
+        self.recipes
+            .iter()
+            .position(|r| r.name == name)
+            .map(|i| self.recipes.remove(i))
+    }
+
+    /// Loads saved recipes from the json file located at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read(path).context("failed to read saved recipes file")?;
+        serde_json::from_slice(&data).context("failed to deserialize saved recipes file")
+    }
+
+    /// Loads saved recipes from `path`, or returns an empty list if the file doesn't exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load(path)
+    }
+
+    /// Saves the recipes as json file in the provided `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)
+                    .context("failed to create parent directory for recipes file")?;
+            }
+        }
+
+        let data = serde_json::to_vec_pretty(&self).context("failed to serialize recipes")?;
+        fs::write(path, data).context("failed to write saved recipes to a file")
+    }
+
+    /// Returns system directory where saved recipes should be placed joined by the `name` parameter.
+    pub fn dir(name: impl AsRef<str>) -> Option<PathBuf> {
+        let name = name.as_ref();
+        dirs::config_dir()
+            .or_else(dirs::home_dir)
+            .map(|dir| dir.join(name))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecipeProcessor {
+    pub input: String,
+    pub recipe: Recipe,
+    pub outputs: Vec<StepOutput>,
+    pub saved: SavedRecipes,
+    pub live_conversion: bool,
+}
+
+impl RecipeProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn process(&mut self) -> Result<()> {
+        self.outputs = self.recipe.run(self.input.as_bytes());
+        match self.outputs.last().and_then(|o| o.error.clone()) {
+            Some(error) => Err(anyhow!(error)),
+            None => Ok(()),
+        }
+    }
+
+    /// Final output of the recipe, or the input itself when the recipe has no steps.
+    pub fn output_text(&self) -> String {
+        match self.outputs.last() {
+            Some(output) => output.as_text(),
+            None => self.input.clone(),
+        }
+    }
+
+    pub fn add_step(&mut self, step: RecipeStep) {
+        self.recipe.steps.push(step);
+        self.outputs.clear();
+    }
+
+    pub fn remove_step(&mut self, idx: usize) {
+        if idx < self.recipe.steps.len() {
+            self.recipe.steps.remove(idx);
+            self.outputs.clear();
+        }
+    }
+
+    pub fn move_step(&mut self, from: usize, to: usize) {
+        if from < self.recipe.steps.len() && to < self.recipe.steps.len() {
+            self.recipe.steps.swap(from, to);
+            self.outputs.clear();
+        }
+    }
+
+    /// Stores the current recipe under its name and writes all saved recipes to `path`.
+    pub fn save_current(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let name = self.recipe.name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Recipe name is required"));
+        }
+        self.recipe.name = name.to_string();
+        self.saved.upsert(self.recipe.clone());
+        self.saved.save(path)
+    }
+
+    pub fn load_saved(&mut self, name: &str) -> Result<()> {
+        let recipe = self
+            .saved
+            .get(name)
+            .ok_or_else(|| anyhow!("Recipe '{}' not found", name))?;
+        self.recipe = recipe.clone();
+        self.outputs.clear();
+        Ok(())
+    }
+
+    pub fn delete_saved(&mut self, name: &str, path: impl AsRef<Path>) -> Result<()> {
+        self.saved.remove(name);
+        self.saved.save(path)
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.recipe = Recipe::default();
+        self.outputs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layered_payload() {
+        // {"a":1} -> base64 -> url encoded
+        let recipe = Recipe::new(
+            "unwrap",
+            vec![
+                RecipeStep::Decode(EncodingType::UrlEncoding),
+                RecipeStep::Decode(EncodingType::Base64),
+                RecipeStep::Transform(ByteTransform::PrettyJson),
+            ],
+        );
+        let outputs = recipe.run(b"eyJhIjoxfQ%3D%3D");
+
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(outputs[0].as_text(), "eyJhIjoxfQ==");
+        assert_eq!(outputs[1].as_text(), "{\"a\":1}");
+        assert_eq!(outputs[2].as_text(), "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn gunzip_step() {
+        let gzipped = Compression::Gzip.compress(b"{\"a\":1}").unwrap();
+        let encoded = EncodingType::Base64.encode_bytes(&gzipped).unwrap();
+        let recipe = Recipe::new(
+            "gunzip",
+            vec![
+                RecipeStep::Decode(EncodingType::Base64),
+                RecipeStep::Decompress(None),
+            ],
+        );
+        let outputs = recipe.run(encoded.as_bytes());
+
+        assert_eq!(outputs.last().unwrap().as_text(), "{\"a\":1}");
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let recipe = Recipe::new(
+            "broken",
+            vec![
+                RecipeStep::Transform(ByteTransform::FromHex),
+                RecipeStep::Encode(EncodingType::Base64),
+            ],
+        );
+        let outputs = recipe.run(b"not hex");
+
+        assert_eq!(outputs.len(), 1);
+        assert!(outputs[0].error.is_some());
+    }
+
+    #[test]
+    fn binary_output_is_hex_dumped() {
+        let output = StepOutput {
+            bytes: vec![0xff, 0x00, 0x10],
+            error: None,
+        };
+        assert_eq!(output.as_text(), "ff 00 10");
+    }
+
+    #[test]
+    fn save_and_load_recipes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SavedRecipes::FILE_NAME);
+
+        let mut processor = RecipeProcessor::new();
+        processor.recipe = Recipe::new("b64", vec![RecipeStep::Encode(EncodingType::Base64)]);
+        processor.save_current(&path).unwrap();
+
+        let mut loaded = RecipeProcessor::new();
+        loaded.saved = SavedRecipes::load_or_default(&path).unwrap();
+        loaded.load_saved("b64").unwrap();
+        loaded.input = "hi".to_string();
+        loaded.process().unwrap();
+
+        assert_eq!(loaded.output_text(), "aGk=");
+    }
+}
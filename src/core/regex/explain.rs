@@ -0,0 +1,425 @@
+//! Plain-English explanation of a pattern, built from the `regex-syntax` AST.
+
+use std::{fmt, ops::Range};
+
+use regex_syntax::ast::{
+    self, parse::Parser, Assertion, AssertionKind, Ast, ClassBracketed, ClassPerl, ClassPerlKind,
+    ClassSet, ClassSetItem, ClassUnicode, ClassUnicodeKind, Flag, Flags, FlagsItemKind, GroupKind,
+    LiteralKind, Repetition, RepetitionKind, RepetitionRange,
+};
+use regex_syntax::ParserBuilder;
+
+/// One explained part of the pattern, with the byte range it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainNode {
+    pub description: String,
+    pub span: Range<usize>,
+    pub children: Vec<ExplainNode>,
+}
+
+impl ExplainNode {
+    fn leaf(description: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            description: description.into(),
+            span,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Syntax error at a byte range of the pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainError {
+    pub message: String,
+    pub span: Range<usize>,
+    /// Related range, such as the opening parenthesis of an unclosed group
+    pub auxiliary_span: Option<Range<usize>>,
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.span.start)
+    }
+}
+
+impl std::error::Error for ExplainError {}
+
+fn range(span: &ast::Span) -> Range<usize> {
+    span.start.offset..span.end.offset
+}
+
+impl From<regex_syntax::Error> for ExplainError {
+    fn from(error: regex_syntax::Error) -> Self {
+        match error {
+            regex_syntax::Error::Parse(e) => ExplainError {
+                message: e.kind().to_string(),
+                span: range(e.span()),
+                auxiliary_span: e.auxiliary_span().map(range),
+            },
+            regex_syntax::Error::Translate(e) => ExplainError {
+                message: e.kind().to_string(),
+                span: range(e.span()),
+                auxiliary_span: None,
+            },
+            other => ExplainError {
+                message: other.to_string(),
+                span: 0..0,
+                auxiliary_span: None,
+            },
+        }
+    }
+}
+
+/// Explains `pattern` as a list of nodes matched one after the other. The pattern is also
+/// translated, so errors such as an unknown Unicode class are located like syntax errors.
+pub fn explain(pattern: &str) -> Result<Vec<ExplainNode>, ExplainError> {
+    ParserBuilder::new().build().parse(pattern)?;
+    let ast = Parser::new()
+        .parse(pattern)
+        .map_err(regex_syntax::Error::from)?;
+    Ok(match &ast {
+        Ast::Concat(concat) => sequence(&concat.asts),
+        other => vec![node(other)],
+    })
+}
+
+fn node(ast: &Ast) -> ExplainNode {
+    if let Some((singular, _)) = item(ast) {
+        return ExplainNode::leaf(singular, range(ast.span()));
+    }
+    let span = range(ast.span());
+    match ast {
+        Ast::Empty(_) => ExplainNode::leaf("nothing (the empty string)", span),
+        Ast::Flags(set) => ExplainNode::leaf(describe_flags(&set.flags), span),
+        Ast::Assertion(assertion) => ExplainNode::leaf(describe_assertion(assertion), span),
+        Ast::Repetition(repetition) => describe_repetition(repetition),
+        Ast::Group(group) => {
+            let inner = node(&group.ast);
+            let captured = match &group.kind {
+                GroupKind::CaptureIndex(index) => format!("captured as group {}", index),
+                GroupKind::CaptureName { name, .. } => {
+                    format!("captured as `{}` (group {})", name.name, name.index)
+                }
+                GroupKind::NonCapturing(flags) if flags.items.is_empty() => {
+                    return ExplainNode { span, ..inner };
+                }
+                GroupKind::NonCapturing(flags) => format!("with {}", describe_flags(flags)),
+            };
+            if inner.children.is_empty() {
+                ExplainNode::leaf(format!("{}, {}", inner.description, captured), span)
+            } else {
+                ExplainNode {
+                    description: format!("group {}, matching", captured),
+                    span,
+                    children: vec![inner],
+                }
+            }
+        }
+        Ast::Alternation(alternation) => ExplainNode {
+            description: "one of these alternatives".to_string(),
+            span,
+            children: alternation.asts.iter().map(node).collect(),
+        },
+        Ast::Concat(concat) => {
+            let mut children = sequence(&concat.asts);
+            if children.len() == 1 {
+                return children.remove(0);
+            }
+            ExplainNode {
+                description: "in sequence".to_string(),
+                span,
+                children,
+            }
+        }
+        // Literals, dots and classes are handled by `item`
+        _ => ExplainNode::leaf(String::new(), span),
+    }
+}
+
+/// Nodes for consecutive ASTs, with runs of plain literals joined into one text node.
+fn sequence(asts: &[Ast]) -> Vec<ExplainNode> {
+    let mut nodes = Vec::new();
+    let mut i = 0;
+    while i < asts.len() {
+        let run = asts[i..]
+            .iter()
+            .take_while(
+                |ast| matches!(ast, Ast::Literal(literal) if literal.kind == LiteralKind::Verbatim),
+            )
+            .count();
+        if run > 1 {
+            let text: String = asts[i..i + run]
+                .iter()
+                .filter_map(|ast| match ast {
+                    Ast::Literal(literal) => Some(literal.c),
+                    _ => None,
+                })
+                .collect();
+            let span = asts[i].span().start.offset..asts[i + run - 1].span().end.offset;
+            nodes.push(ExplainNode::leaf(format!("the text `{}`", text), span));
+            i += run;
+        } else {
+            nodes.push(node(&asts[i]));
+            i += 1;
+        }
+    }
+    nodes
+}
+
+/// Singular and plural descriptions of a single-character item.
+fn item(ast: &Ast) -> Option<(String, String)> {
+    match ast {
+        Ast::Literal(literal) => {
+            let c = literal.c.escape_debug();
+            Some((
+                format!("the character `{}`", c),
+                format!("`{}` characters", c),
+            ))
+        }
+        Ast::Dot(_) => Some((
+            "any character except a line break (any character with the s flag)".to_string(),
+            "characters other than line breaks (any characters with the s flag)".to_string(),
+        )),
+        Ast::ClassPerl(class) => Some(describe_perl(class)),
+        Ast::ClassUnicode(class) => {
+            let plural = describe_unicode(class);
+            Some((
+                format!("a character {}", plural),
+                format!("characters {}", plural),
+            ))
+        }
+        Ast::ClassBracketed(class) => {
+            let members = describe_bracketed(class);
+            Some((
+                format!("a character {}", members),
+                format!("characters {}", members),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn describe_perl(class: &ClassPerl) -> (String, String) {
+    let (singular, plural) = match (&class.kind, class.negated) {
+        (ClassPerlKind::Digit, false) => ("a digit", "digits"),
+        (ClassPerlKind::Digit, true) => ("a non-digit", "non-digits"),
+        (ClassPerlKind::Space, false) => ("a whitespace character", "whitespace characters"),
+        (ClassPerlKind::Space, true) => ("a non-whitespace character", "non-whitespace characters"),
+        (ClassPerlKind::Word, false) => ("a word character", "word characters"),
+        (ClassPerlKind::Word, true) => ("a non-word character", "non-word characters"),
+    };
+    (singular.to_string(), plural.to_string())
+}
+
+fn describe_unicode(class: &ClassUnicode) -> String {
+    let property = match &class.kind {
+        ClassUnicodeKind::OneLetter(letter) => letter.to_string(),
+        ClassUnicodeKind::Named(name) => name.clone(),
+        ClassUnicodeKind::NamedValue { name, value, .. } => format!("{}={}", name, value),
+    };
+    format!(
+        "{} the Unicode class `{}`",
+        if class.is_negated() { "outside" } else { "in" },
+        property
+    )
+}
+
+fn describe_bracketed(class: &ClassBracketed) -> String {
+    format!(
+        "{} {}",
+        if class.negated { "not in" } else { "in" },
+        describe_set(&class.kind)
+    )
+}
+
+fn describe_set(set: &ClassSet) -> String {
+    match set {
+        ClassSet::Item(item) => describe_set_item(item),
+        ClassSet::BinaryOp(op) => format!(
+            "({} {} {})",
+            describe_set(&op.lhs),
+            match op.kind {
+                ast::ClassSetBinaryOpKind::Intersection => "and also",
+                ast::ClassSetBinaryOpKind::Difference => "but not",
+                ast::ClassSetBinaryOpKind::SymmetricDifference => "or else",
+            },
+            describe_set(&op.rhs)
+        ),
+    }
+}
+
+fn describe_set_item(item: &ClassSetItem) -> String {
+    match item {
+        ClassSetItem::Empty(_) => "nothing".to_string(),
+        ClassSetItem::Literal(literal) => format!("`{}`", literal.c.escape_debug()),
+        ClassSetItem::Range(range) => format!(
+            "`{}`–`{}`",
+            range.start.c.escape_debug(),
+            range.end.c.escape_debug()
+        ),
+        ClassSetItem::Ascii(class) => format!(
+            "{}ASCII {:?}",
+            if class.negated { "non-" } else { "" },
+            class.kind
+        )
+        .to_lowercase(),
+        ClassSetItem::Unicode(class) => format!("characters {}", describe_unicode(class)),
+        ClassSetItem::Perl(class) => describe_perl(class).1,
+        ClassSetItem::Bracketed(class) => format!("characters {}", describe_bracketed(class)),
+        ClassSetItem::Union(union) => union
+            .items
+            .iter()
+            .map(describe_set_item)
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn describe_assertion(assertion: &Assertion) -> &'static str {
+    match assertion.kind {
+        AssertionKind::StartLine => "the start of the text (of a line with the m flag)",
+        AssertionKind::EndLine => "the end of the text (of a line with the m flag)",
+        AssertionKind::StartText => "the start of the text",
+        AssertionKind::EndText => "the end of the text",
+        AssertionKind::WordBoundary => "a word boundary",
+        AssertionKind::NotWordBoundary => "a position that is not a word boundary",
+        AssertionKind::WordBoundaryStart | AssertionKind::WordBoundaryStartAngle => {
+            "the start of a word"
+        }
+        AssertionKind::WordBoundaryEnd | AssertionKind::WordBoundaryEndAngle => "the end of a word",
+        AssertionKind::WordBoundaryStartHalf => "a position not preceded by a word character",
+        AssertionKind::WordBoundaryEndHalf => "a position not followed by a word character",
+    }
+}
+
+fn describe_flags(flags: &Flags) -> String {
+    let mut enabled = true;
+    let mut parts = Vec::new();
+    for item in &flags.items {
+        match item.kind {
+            FlagsItemKind::Negation => enabled = false,
+            FlagsItemKind::Flag(flag) => {
+                let name = match flag {
+                    Flag::CaseInsensitive => "case-insensitive (i)",
+                    Flag::MultiLine => "multi-line (m)",
+                    Flag::DotMatchesNewLine => "dot matches newline (s)",
+                    Flag::SwapGreed => "swap greed (U)",
+                    Flag::Unicode => "Unicode (u)",
+                    Flag::CRLF => "CRLF line breaks (R)",
+                    Flag::IgnoreWhitespace => "ignore whitespace (x)",
+                };
+                parts.push(format!("{} {}", if enabled { "on" } else { "off" }, name));
+            }
+        }
+    }
+    format!("flags {}", parts.join(", "))
+}
+
+fn describe_repetition(repetition: &Repetition) -> ExplainNode {
+    let span = range(&repetition.span);
+    let laziness = if repetition.greedy {
+        ""
+    } else {
+        ", as few as possible"
+    };
+    let (quantifier, single) = match &repetition.op.kind {
+        RepetitionKind::ZeroOrOne => ("optionally".to_string(), true),
+        RepetitionKind::ZeroOrMore => ("zero or more".to_string(), false),
+        RepetitionKind::OneOrMore => ("one or more".to_string(), false),
+        RepetitionKind::Range(RepetitionRange::Exactly(n)) => (format!("exactly {}", n), *n == 1),
+        RepetitionKind::Range(RepetitionRange::AtLeast(n)) => (format!("at least {}", n), false),
+        RepetitionKind::Range(RepetitionRange::Bounded(min, max)) => {
+            (format!("between {} and {}", min, max), false)
+        }
+    };
+
+    if let Some((singular, plural)) = item(&repetition.ast) {
+        let noun = if single { singular } else { plural };
+        return ExplainNode::leaf(format!("{} {}{}", quantifier, noun, laziness), span);
+    }
+
+    let times = if single {
+        quantifier
+    } else {
+        format!("{} times", quantifier)
+    };
+    let inner = node(&repetition.ast);
+    if inner.children.is_empty() {
+        ExplainNode::leaf(
+            format!("{}{}: {}", times, laziness, inner.description),
+            span,
+        )
+    } else {
+        ExplainNode {
+            description: format!("{}{}", times, laziness),
+            span,
+            children: vec![inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptions(nodes: &[ExplainNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.description.as_str()).collect()
+    }
+
+    #[test]
+    fn test_named_group_reads_as_one_node() {
+        let nodes = explain(r"mid(?<postfix>\w+)").unwrap();
+        assert_eq!(
+            descriptions(&nodes),
+            [
+                "the text `mid`",
+                "one or more word characters, captured as `postfix` (group 1)"
+            ]
+        );
+        assert_eq!(nodes[0].span, 0..3);
+        assert_eq!(nodes[1].span, 3..18);
+    }
+
+    #[test]
+    fn test_nested_structure() {
+        let nodes = explain(r"^(?:cat|[a-c0-9]{2,3}?)\b").unwrap();
+        assert_eq!(nodes.len(), 3);
+        let alternation = &nodes[1];
+        assert_eq!(alternation.description, "one of these alternatives");
+        assert_eq!(alternation.span, 1..23);
+        assert_eq!(
+            descriptions(&alternation.children),
+            [
+                "the text `cat`",
+                "between 2 and 3 characters in `a`–`c`, `0`–`9`, as few as possible"
+            ]
+        );
+        assert_eq!(nodes[2].description, "a word boundary");
+    }
+
+    #[test]
+    fn test_repeated_group_keeps_children() {
+        let nodes = explain(r"(\d+-)*").unwrap();
+        assert_eq!(nodes[0].description, "zero or more times");
+        let group = &nodes[0].children[0];
+        assert_eq!(group.description, "group captured as group 1, matching");
+        assert_eq!(
+            descriptions(&group.children[0].children),
+            ["one or more digits", "the character `-`"]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_offset() {
+        let error = explain(r"ab(?<name>c").unwrap_err();
+        assert_eq!(error.message, "unclosed group");
+        assert_eq!(error.span, 2..3);
+
+        let error = explain(r"a{2,1}").unwrap_err();
+        assert_eq!(error.span, 1..6);
+
+        let error = explain(r"ab\p{Bogus}").unwrap_err();
+        assert_eq!(error.message, "Unicode property not found");
+        assert_eq!(error.span, 2..11);
+    }
+}
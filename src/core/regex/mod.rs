@@ -1,13 +1,101 @@
+pub mod explain;
+
+use std::fmt;
+
 use anyhow::{Error, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use explain::{ExplainError, ExplainNode};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegexMode {
+    #[default]
+    Match,
+    Replace,
+    Split,
+}
+
+impl fmt::Display for RegexMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl RegexMode {
+    pub fn variants() -> &'static [RegexMode] {
+        &[RegexMode::Match, RegexMode::Replace, RegexMode::Split]
+    }
+}
+
+/// Location of a byte offset in the text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub byte: usize,
+    pub char: usize,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+}
+
+impl Position {
+    /// Position of `to` in `text`, found by walking forward from `self`.
+    fn advance(self, text: &str, to: usize) -> Position {
+        let mut position = self;
+        for c in text[self.byte..to].chars() {
+            position.char += 1;
+            if c == '\n' {
+                position.line += 1;
+                position.column = 1;
+            } else {
+                position.column += 1;
+            }
+        }
+        position.byte = to;
+        position
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptureGroup {
+    /// Group number, 1 for the first group
+    pub index: usize,
+    pub name: Option<String>,
+    /// Span and text of the group, `None` when it did not participate in the match
+    pub span: Option<Span>,
+    pub text: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegexMatch {
     pub start: usize,
     pub end: usize,
+    pub span: Span,
     pub text: String,
-    pub groups: Vec<String>,
+    pub groups: Vec<CaptureGroup>,
+}
+
+/// One substituted match: the matched text and what replaced it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replacement {
+    pub start: usize,
+    pub end: usize,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplaceResult {
+    /// The text with the replacements applied
+    pub output: String,
+    pub replacements: Vec<Replacement>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,7 +114,16 @@ pub struct RegexProcessor {
     pub case_insensitive: bool,
     pub multiline: bool,
     pub dot_matches_newline: bool,
+    pub mode: RegexMode,
+    /// Substitution template, with `$1`, `${name}` and `$$` references
+    pub replacement: String,
+    /// Replace every match instead of only the first one
+    pub replace_all: bool,
+    /// Maximum number of replacements, or of split fields; 0 means no limit
+    pub limit: usize,
     pub result: Option<RegexMatchResult>,
+    pub replaced: Option<ReplaceResult>,
+    pub fields: Option<Vec<String>>,
 }
 
 impl Default for RegexProcessor {
@@ -37,7 +134,13 @@ impl Default for RegexProcessor {
             case_insensitive: false,
             multiline: false,
             dot_matches_newline: false,
+            mode: RegexMode::Match,
+            replacement: "night${postfix}".to_string(),
+            replace_all: true,
+            limit: 0,
             result: None,
+            replaced: None,
+            fields: None,
         }
     }
 }
@@ -47,7 +150,10 @@ impl RegexProcessor {
         Self::default()
     }
 
+    /// Finds the matches and, in replace or split mode, the replaced text or split fields.
     pub fn process(&mut self) -> Result<()> {
+        self.replaced = None;
+        self.fields = None;
         if self.pattern.is_empty() {
             self.result = Some(RegexMatchResult {
                 pattern: self.pattern.clone(),
@@ -64,6 +170,11 @@ impl RegexProcessor {
         match regex_result {
             Ok(regex) => {
                 let matches = self.find_matches(&regex);
+                match self.mode {
+                    RegexMode::Match => {}
+                    RegexMode::Replace => self.replaced = Some(self.replace_with(&regex)),
+                    RegexMode::Split => self.fields = Some(self.split_with(&regex)),
+                }
                 self.result = Some(RegexMatchResult {
                     pattern: self.pattern.clone(),
                     text: self.text.clone(),
@@ -96,35 +207,220 @@ impl RegexProcessor {
     }
 
     fn find_matches(&self, regex: &Regex) -> Vec<RegexMatch> {
-        let mut matches = Vec::new();
-
-        for mat in regex.find_iter(&self.text) {
-            let mut groups = Vec::new();
+        let text = self.text.as_str();
+        let names: Vec<Option<&str>> = regex.capture_names().collect();
+        let mut position = Position {
+            line: 1,
+            column: 1,
+            ..Default::default()
+        };
 
-            // Find capture groups for this match
-            if let Some(captures) = regex.captures(&self.text[mat.start()..]) {
-                for (i, group) in captures.iter().enumerate() {
-                    if i == 0 {
-                        continue; // Skip the full match
+        regex
+            .captures_iter(text)
+            .filter_map(|captures| {
+                let mat = captures.get(0)?;
+                // Matches are in order, so every start is reached by walking forward
+                position = position.advance(text, mat.start());
+                let span = |start: usize, end: usize| {
+                    let start = position.advance(text, start);
+                    Span {
+                        start,
+                        end: start.advance(text, end),
                     }
-                    groups.push(group.map_or_else(|| String::new(), |m| m.as_str().to_string()));
-                }
-            }
+                };
 
-            matches.push(RegexMatch {
+                let groups = (1..captures.len())
+                    .map(|index| {
+                        let group = captures.get(index);
+                        CaptureGroup {
+                            index,
+                            name: names[index].map(str::to_string),
+                            span: group.map(|g| span(g.start(), g.end())),
+                            text: group.map(|g| g.as_str().to_string()),
+                        }
+                    })
+                    .collect();
+
+                Some(RegexMatch {
+                    start: mat.start(),
+                    end: mat.end(),
+                    span: span(mat.start(), mat.end()),
+                    text: mat.as_str().to_string(),
+                    groups,
+                })
+            })
+            .collect()
+    }
+
+    /// Explains the pattern in plain English, or locates its syntax error.
+    pub fn explain(&self) -> Result<Vec<ExplainNode>, ExplainError> {
+        explain::explain(&self.pattern)
+    }
+
+    /// Replaces the first match, or with `replace_all` up to `limit` matches, by the expanded
+    /// `replacement` template.
+    pub fn replace(&self) -> Result<ReplaceResult> {
+        Ok(self.replace_with(&self.build_regex()?))
+    }
+
+    /// Splits the text at every match, into at most `limit` fields.
+    pub fn split(&self) -> Result<Vec<String>> {
+        Ok(self.split_with(&self.build_regex()?))
+    }
+
+    fn replace_with(&self, regex: &Regex) -> ReplaceResult {
+        let text = self.text.as_str();
+        let count = match (self.replace_all, self.limit) {
+            (false, _) => 1,
+            (true, 0) => usize::MAX,
+            (true, limit) => limit,
+        };
+
+        let mut output = String::with_capacity(text.len());
+        let mut replacements = Vec::new();
+        let mut last = 0;
+        for captures in regex.captures_iter(text).take(count) {
+            let mat = captures.get(0).expect("group 0 is always present");
+            let mut after = String::new();
+            captures.expand(&self.replacement, &mut after);
+
+            output.push_str(&text[last..mat.start()]);
+            output.push_str(&after);
+            last = mat.end();
+            replacements.push(Replacement {
                 start: mat.start(),
                 end: mat.end(),
-                text: mat.as_str().to_string(),
-                groups,
+                before: mat.as_str().to_string(),
+                after,
             });
         }
+        output.push_str(&text[last..]);
+
+        ReplaceResult {
+            output,
+            replacements,
+        }
+    }
 
-        matches
+    fn split_with(&self, regex: &Regex) -> Vec<String> {
+        let text = self.text.as_str();
+        match self.limit {
+            0 => regex.split(text).map(str::to_string).collect(),
+            limit => regex.splitn(text, limit).map(str::to_string).collect(),
+        }
     }
 
     pub fn clear(&mut self) {
         self.pattern.clear();
         self.text.clear();
+        self.replacement.clear();
         self.result = None;
+        self.replaced = None;
+        self.fields = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, text: &str) -> Vec<RegexMatch> {
+        let mut processor = RegexProcessor {
+            pattern: pattern.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        };
+        processor.process().unwrap();
+        processor.result.unwrap().matches
+    }
+
+    #[test]
+    fn test_named_groups_with_spans() {
+        let matches = find(
+            "mid(?<postfix>[a-zA-Z]+)",
+            "aurora midsummer midnight earth",
+        );
+        assert_eq!(matches.len(), 2);
+
+        let group = &matches[1].groups[0];
+        assert_eq!(group.index, 1);
+        assert_eq!(group.name.as_deref(), Some("postfix"));
+        assert_eq!(group.text.as_deref(), Some("night"));
+        let span = group.span.unwrap();
+        assert_eq!((span.start.byte, span.end.byte), (20, 25));
+        assert_eq!((span.start.line, span.start.column), (1, 21));
+    }
+
+    #[test]
+    fn test_groups_belong_to_their_own_match() {
+        // Re-running the regex from the match start used to match `^` and `\b` there again
+        let matches = find(r"(\w)(\w)?", "ab c");
+        let groups: Vec<Vec<Option<&str>>> = matches
+            .iter()
+            .map(|m| m.groups.iter().map(|g| g.text.as_deref()).collect())
+            .collect();
+        assert_eq!(groups, [vec![Some("a"), Some("b")], vec![Some("c"), None]]);
+
+        let matches = find(r"(?m)^(\d+)", "x 12\n34");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].groups[0].text.as_deref(), Some("34"));
+    }
+
+    fn processor(pattern: &str, text: &str, replacement: &str) -> RegexProcessor {
+        RegexProcessor {
+            pattern: pattern.to_string(),
+            text: text.to_string(),
+            replacement: replacement.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_replace_substitutions() {
+        let processor = processor(r"(?<key>\w+)=(\d+)", "a=1, b=2", "${2}:${key} $$");
+        let replaced = processor.replace().unwrap();
+        assert_eq!(replaced.output, "1:a $, 2:b $");
+        assert_eq!(
+            replaced.replacements[1],
+            Replacement {
+                start: 5,
+                end: 8,
+                before: "b=2".to_string(),
+                after: "2:b $".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_replace_first_and_limit() {
+        let mut processor = processor(r"\d", "1 2 3", "#");
+        processor.limit = 2;
+        assert_eq!(processor.replace().unwrap().output, "# # 3");
+
+        processor.replace_all = false;
+        let replaced = processor.replace().unwrap();
+        assert_eq!(replaced.output, "# 2 3");
+        assert_eq!(replaced.replacements.len(), 1);
+    }
+
+    #[test]
+    fn test_split_fields() {
+        let mut processor = processor(r"\s*,\s*", "a , b,c", "");
+        assert_eq!(processor.split().unwrap(), ["a", "b", "c"]);
+
+        processor.limit = 2;
+        processor.mode = RegexMode::Split;
+        processor.process().unwrap();
+        assert_eq!(processor.fields.unwrap(), ["a", "b,c"]);
+    }
+
+    #[test]
+    fn test_char_offsets_and_lines() {
+        let matches = find("(?<word>é+)", "añb\nzéé");
+        let span = matches[0].groups[0].span.unwrap();
+        assert_eq!((span.start.byte, span.start.char), (6, 5));
+        assert_eq!((span.end.byte, span.end.char), (10, 7));
+        assert_eq!((span.start.line, span.start.column), (2, 2));
+        assert_eq!(matches[0].span, span);
     }
 }
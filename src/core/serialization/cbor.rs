@@ -0,0 +1,146 @@
+use anyhow::{anyhow, Result};
+use ciborium::value::{Integer, Value as Cbor};
+use serde_json::{Map, Number, Value};
+
+use super::{
+    annotated_binary, annotated_float, annotation, binary_to_json, check_trailing, float_to_json,
+    key_to_string, BIGINT_KEY, BINARY_KEY, FLOAT_KEY, TAG_KEY,
+};
+
+const VALUE_KEY: &str = "value";
+
+pub fn to_json(bytes: &[u8]) -> Result<Value> {
+    let mut reader = bytes;
+    let value: Cbor =
+        ciborium::de::from_reader(&mut reader).map_err(|e| anyhow!("Invalid CBOR: {}", e))?;
+    check_trailing(bytes, reader)?;
+    Ok(value_to_json(value))
+}
+
+pub fn from_json(json: &Value) -> Result<Vec<u8>> {
+    let value = json_to_value(json)?;
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&value, &mut bytes)
+        .map_err(|e| anyhow!("Failed to encode CBOR: {}", e))?;
+    Ok(bytes)
+}
+
+fn value_to_json(value: Cbor) -> Value {
+    match value {
+        Cbor::Null => Value::Null,
+        Cbor::Bool(b) => Value::Bool(b),
+        Cbor::Integer(i) => integer_to_json(i128::from(i)),
+        Cbor::Float(f) => float_to_json(f),
+        Cbor::Text(s) => Value::String(s),
+        Cbor::Bytes(bytes) => binary_to_json(&bytes),
+        Cbor::Array(items) => Value::Array(items.into_iter().map(value_to_json).collect()),
+        Cbor::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key_to_string(value_to_json(key)), value_to_json(value)))
+                .collect(),
+        ),
+        Cbor::Tag(tag, value) => {
+            let mut map = Map::new();
+            map.insert(TAG_KEY.to_string(), Value::Number(tag.into()));
+            map.insert(VALUE_KEY.to_string(), value_to_json(*value));
+            Value::Object(map)
+        }
+        _ => Value::Null,
+    }
+}
+
+/// CBOR integers range from -2^64 to 2^64 - 1, beyond what JSON numbers keep exact.
+fn integer_to_json(value: i128) -> Value {
+    if let Ok(u) = u64::try_from(value) {
+        Value::Number(u.into())
+    } else if let Ok(i) = i64::try_from(value) {
+        Value::Number(i.into())
+    } else {
+        let mut map = Map::new();
+        map.insert(BIGINT_KEY.to_string(), Value::String(value.to_string()));
+        Value::Object(map)
+    }
+}
+
+fn json_to_value(json: &Value) -> Result<Cbor> {
+    Ok(match json {
+        Value::Null => Cbor::Null,
+        Value::Bool(b) => Cbor::Bool(*b),
+        Value::Number(n) => number_to_value(n),
+        Value::String(s) => Cbor::Text(s.clone()),
+        Value::Array(items) => Cbor::Array(
+            items
+                .iter()
+                .map(json_to_value)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        Value::Object(map) => {
+            if let Some(value) = annotation(map, BINARY_KEY) {
+                return Ok(Cbor::Bytes(annotated_binary(value)?));
+            }
+            if let Some(value) = annotation(map, FLOAT_KEY) {
+                return Ok(Cbor::Float(annotated_float(value)?));
+            }
+            if let Some(value) = annotation(map, BIGINT_KEY) {
+                let integer = value
+                    .as_str()
+                    .and_then(|s| s.parse::<i128>().ok())
+                    .and_then(|i| Integer::try_from(i).ok())
+                    .ok_or_else(|| {
+                        anyhow!("{} must be an integer string within ±2^64", BIGINT_KEY)
+                    })?;
+                return Ok(Cbor::Integer(integer));
+            }
+            if let (Some(tag), Some(value), 2) = (map.get(TAG_KEY), map.get(VALUE_KEY), map.len()) {
+                let tag = tag
+                    .as_u64()
+                    .ok_or_else(|| anyhow!("{} must be an unsigned integer", TAG_KEY))?;
+                return Ok(Cbor::Tag(tag, Box::new(json_to_value(value)?)));
+            }
+            Cbor::Map(
+                map.iter()
+                    .map(|(key, value)| Ok((Cbor::Text(key.clone()), json_to_value(value)?)))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        }
+    })
+}
+
+fn number_to_value(number: &Number) -> Cbor {
+    if let Some(u) = number.as_u64() {
+        Cbor::Integer(u.into())
+    } else if let Some(i) = number.as_i64() {
+        Cbor::Integer(i.into())
+    } else {
+        Cbor::Float(number.as_f64().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn annotates_tags_bytes_and_big_integers() {
+        // [1("2013-03-21"), h'0102', -2^64, {1: "one"}]
+        let bytes =
+            hex::decode("84c16a323031332d30332d32314201023bffffffffffffffffa101636f6e65").unwrap();
+        let json = to_json(&bytes).unwrap();
+        assert_eq!(
+            json,
+            json!([
+                { "$tag": 1, "value": "2013-03-21" },
+                { "$binary": "AQI=" },
+                { "$bigint": "-18446744073709551616" },
+                { "1": "one" }
+            ])
+        );
+
+        // Integer map keys come back as text keys
+        let encoded = from_json(&json).unwrap();
+        assert!(hex::encode(&encoded).ends_with("a16131636f6e65"));
+        assert_eq!(to_json(&encoded).unwrap(), json);
+    }
+}
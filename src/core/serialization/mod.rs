@@ -0,0 +1,279 @@
+pub mod cbor;
+pub mod msgpack;
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine};
+use bson::{Bson, Document};
+use serde_json::{Map, Value};
+use std::{fmt, fs, path::Path};
+
+use crate::core::encoding::input::BinaryInput;
+
+/// Annotation keys used for values JSON has no native type for. A JSON object made of exactly
+/// these keys is turned back into the annotated type when encoding.
+pub const BINARY_KEY: &str = "$binary";
+pub const EXT_KEY: &str = "$ext";
+pub const TAG_KEY: &str = "$tag";
+pub const FLOAT_KEY: &str = "$float";
+pub const BIGINT_KEY: &str = "$bigint";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormat {
+    #[default]
+    MessagePack,
+    Cbor,
+    Bson,
+}
+
+impl fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryFormat::MessagePack => write!(f, "MessagePack"),
+            BinaryFormat::Cbor => write!(f, "CBOR"),
+            BinaryFormat::Bson => write!(f, "BSON"),
+        }
+    }
+}
+
+impl BinaryFormat {
+    pub fn variants() -> &'static [BinaryFormat] {
+        &[
+            BinaryFormat::MessagePack,
+            BinaryFormat::Cbor,
+            BinaryFormat::Bson,
+        ]
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BinaryFormat::MessagePack => "msgpack",
+            BinaryFormat::Cbor => "cbor",
+            BinaryFormat::Bson => "bson",
+        }
+    }
+
+    pub fn to_json(&self, bytes: &[u8]) -> Result<Value> {
+        match self {
+            BinaryFormat::MessagePack => msgpack::to_json(bytes),
+            BinaryFormat::Cbor => cbor::to_json(bytes),
+            BinaryFormat::Bson => bson_to_json(bytes),
+        }
+    }
+
+    pub fn from_json(&self, json: &Value) -> Result<Vec<u8>> {
+        match self {
+            BinaryFormat::MessagePack => msgpack::from_json(json),
+            BinaryFormat::Cbor => cbor::from_json(json),
+            BinaryFormat::Bson => bson_from_json(json),
+        }
+    }
+}
+
+/// BSON uses MongoDB Extended JSON (relaxed mode), so ObjectIds, dates and binary subtypes
+/// show up as `$oid`, `$date` and `$binary` objects.
+fn bson_to_json(bytes: &[u8]) -> Result<Value> {
+    let mut reader = bytes;
+    let document =
+        Document::from_reader(&mut reader).map_err(|e| anyhow!("Invalid BSON: {}", e))?;
+    check_trailing(bytes, reader)?;
+    Ok(Bson::Document(document).into_relaxed_extjson())
+}
+
+fn bson_from_json(json: &Value) -> Result<Vec<u8>> {
+    let document = match Bson::try_from(json.clone()) {
+        Ok(Bson::Document(document)) => document,
+        Ok(_) => return Err(anyhow!("BSON requires a JSON object at the top level")),
+        Err(e) => return Err(anyhow!("Invalid Extended JSON: {}", e)),
+    };
+    let mut bytes = Vec::new();
+    document
+        .to_writer(&mut bytes)
+        .map_err(|e| anyhow!("Failed to encode BSON: {}", e))?;
+    Ok(bytes)
+}
+
+/// Fails when `rest`, the unread tail of `bytes`, is not empty.
+pub(crate) fn check_trailing(bytes: &[u8], rest: &[u8]) -> Result<()> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Unexpected {} trailing bytes at byte {}",
+            rest.len(),
+            bytes.len() - rest.len()
+        ))
+    }
+}
+
+pub(crate) fn binary_to_json(bytes: &[u8]) -> Value {
+    let mut map = Map::new();
+    map.insert(
+        BINARY_KEY.to_string(),
+        Value::String(general_purpose::STANDARD.encode(bytes)),
+    );
+    Value::Object(map)
+}
+
+/// Non-finite floats are not valid JSON numbers.
+pub(crate) fn float_to_json(value: f64) -> Value {
+    match serde_json::Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None => {
+            let mut map = Map::new();
+            map.insert(FLOAT_KEY.to_string(), Value::String(value.to_string()));
+            Value::Object(map)
+        }
+    }
+}
+
+/// Map keys must be strings in JSON, other key types are written as their JSON text.
+pub(crate) fn key_to_string(key: Value) -> String {
+    match key {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Returns the value of `key` when `map` is a single-key annotation object.
+pub(crate) fn annotation<'a>(map: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    map.get(key).filter(|_| map.len() == 1)
+}
+
+pub(crate) fn annotated_binary(value: &Value) -> Result<Vec<u8>> {
+    let encoded = value
+        .as_str()
+        .ok_or_else(|| anyhow!("{} must be a Base64 string", BINARY_KEY))?;
+    general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| anyhow!("Invalid {} payload: {}", BINARY_KEY, e))
+}
+
+pub(crate) fn annotated_float(value: &Value) -> Result<f64> {
+    value
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| anyhow!("{} must be \"NaN\", \"inf\" or \"-inf\"", FLOAT_KEY))
+}
+
+/// Converts between binary serialization formats and JSON.
+#[derive(Debug, Clone, Default)]
+pub struct SerializationConverter {
+    pub format: BinaryFormat,
+    pub input: String,
+    pub input_format: BinaryInput,
+    pub json: String,
+    pub error: Option<String>,
+}
+
+impl SerializationConverter {
+    /// Decodes the binary input into pretty-printed JSON.
+    pub fn decode(&mut self) -> Result<()> {
+        let result = self
+            .input_format
+            .decode(&self.input)
+            .and_then(|bytes| self.format.to_json(&bytes));
+        self.set_json(result)
+    }
+
+    /// Encodes the JSON back into the selected format, written to the input in its current
+    /// representation.
+    pub fn encode(&mut self) -> Result<()> {
+        let result = serde_json::from_str::<Value>(&self.json)
+            .map_err(|e| anyhow!("Invalid JSON: {}", e))
+            .and_then(|json| self.format.from_json(&json));
+        match result {
+            Ok(bytes) => {
+                self.input = match self.input_format {
+                    BinaryInput::Base64 => general_purpose::STANDARD.encode(&bytes),
+                    BinaryInput::Auto | BinaryInput::Hex => hex::encode(&bytes),
+                };
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        self.input = hex::encode(&bytes);
+        self.input_format = BinaryInput::Hex;
+        let result = self.format.to_json(&bytes);
+        self.set_json(result)
+    }
+
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let bytes = self.input_format.decode(&self.input)?;
+        fs::write(path.as_ref(), bytes)
+            .map_err(|e| anyhow!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    fn set_json(&mut self, result: Result<Value>) -> Result<()> {
+        match result {
+            Ok(json) => {
+                self.json = serde_json::to_string_pretty(&json)?;
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.json.clear();
+                self.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.json.clear();
+        self.error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn bson_uses_extended_json() {
+        let oid = bson::oid::ObjectId::parse_str("65a1b2c3d4e5f60718293a4b").unwrap();
+        let document = bson::doc! { "_id": oid, "n": 1 };
+        let mut bytes = Vec::new();
+        document.to_writer(&mut bytes).unwrap();
+
+        let json = BinaryFormat::Bson.to_json(&bytes).unwrap();
+        assert_eq!(
+            json,
+            json!({ "_id": { "$oid": "65a1b2c3d4e5f60718293a4b" }, "n": 1 })
+        );
+        assert_eq!(BinaryFormat::Bson.from_json(&json).unwrap(), bytes);
+        assert!(BinaryFormat::Bson.from_json(&json!([1])).is_err());
+    }
+
+    #[test]
+    fn converter_round_trips_through_json() {
+        let mut converter = SerializationConverter {
+            format: BinaryFormat::Cbor,
+            input: "a2616101616282f5f6".to_string(),
+            ..Default::default()
+        };
+        converter.decode().unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&converter.json).unwrap(),
+            json!({ "a": 1, "b": [true, null] })
+        );
+
+        converter.input.clear();
+        converter.encode().unwrap();
+        assert_eq!(converter.input, "a2616101616282f5f6");
+
+        converter.input = "a16161".to_string();
+        assert!(converter.decode().is_err());
+        assert!(converter.json.is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine};
+use chrono::DateTime;
+use rmpv::Value as MsgPack;
+use serde_json::{Map, Number, Value};
+
+use super::{
+    annotated_binary, annotated_float, annotation, binary_to_json, check_trailing, float_to_json,
+    key_to_string, BINARY_KEY, EXT_KEY, FLOAT_KEY,
+};
+
+const DATA_KEY: &str = "data";
+/// Informational only, the `data` payload is what gets encoded.
+const TIMESTAMP_KEY: &str = "$timestamp";
+const TIMESTAMP_EXT: i8 = -1;
+
+pub fn to_json(bytes: &[u8]) -> Result<Value> {
+    let mut reader = bytes;
+    let value = rmpv::decode::read_value(&mut reader).map_err(|e| {
+        anyhow!(
+            "Invalid MessagePack at byte {}: {}",
+            bytes.len() - reader.len(),
+            e
+        )
+    })?;
+    check_trailing(bytes, reader)?;
+    Ok(value_to_json(value))
+}
+
+pub fn from_json(json: &Value) -> Result<Vec<u8>> {
+    let value = json_to_value(json)?;
+    let mut bytes = Vec::new();
+    rmpv::encode::write_value(&mut bytes, &value)
+        .map_err(|e| anyhow!("Failed to encode MessagePack: {}", e))?;
+    Ok(bytes)
+}
+
+fn value_to_json(value: MsgPack) -> Value {
+    match value {
+        MsgPack::Nil => Value::Null,
+        MsgPack::Boolean(b) => Value::Bool(b),
+        MsgPack::Integer(i) => match (i.as_u64(), i.as_i64()) {
+            (Some(u), _) => Value::Number(u.into()),
+            (None, Some(i)) => Value::Number(i.into()),
+            (None, None) => Value::Null,
+        },
+        MsgPack::F32(f) => float_to_json(f64::from(f)),
+        MsgPack::F64(f) => float_to_json(f),
+        MsgPack::String(s) => match s.as_str() {
+            Some(text) => Value::String(text.to_string()),
+            None => binary_to_json(&s.into_bytes()),
+        },
+        MsgPack::Binary(bytes) => binary_to_json(&bytes),
+        MsgPack::Array(items) => Value::Array(items.into_iter().map(value_to_json).collect()),
+        MsgPack::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key_to_string(value_to_json(key)), value_to_json(value)))
+                .collect(),
+        ),
+        MsgPack::Ext(kind, data) => {
+            let mut map = Map::new();
+            map.insert(EXT_KEY.to_string(), Value::Number(kind.into()));
+            map.insert(
+                DATA_KEY.to_string(),
+                Value::String(general_purpose::STANDARD.encode(&data)),
+            );
+            if kind == TIMESTAMP_EXT {
+                if let Some(timestamp) = decode_timestamp(&data) {
+                    map.insert(TIMESTAMP_KEY.to_string(), Value::String(timestamp));
+                }
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+/// Decodes the timestamp extension in its 32, 64 and 96-bit forms.
+fn decode_timestamp(data: &[u8]) -> Option<String> {
+    let (seconds, nanos) = match data.len() {
+        4 => (i64::from(u32::from_be_bytes(data.try_into().ok()?)), 0),
+        8 => {
+            let raw = u64::from_be_bytes(data.try_into().ok()?);
+            ((raw & 0x3_ffff_ffff) as i64, (raw >> 34) as u32)
+        }
+        12 => (
+            i64::from_be_bytes(data[4..].try_into().ok()?),
+            u32::from_be_bytes(data[..4].try_into().ok()?),
+        ),
+        _ => return None,
+    };
+    DateTime::from_timestamp(seconds, nanos).map(|dt| dt.to_rfc3339())
+}
+
+fn json_to_value(json: &Value) -> Result<MsgPack> {
+    Ok(match json {
+        Value::Null => MsgPack::Nil,
+        Value::Bool(b) => MsgPack::Boolean(*b),
+        Value::Number(n) => number_to_value(n),
+        Value::String(s) => MsgPack::from(s.as_str()),
+        Value::Array(items) => MsgPack::Array(
+            items
+                .iter()
+                .map(json_to_value)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        Value::Object(map) => {
+            if let Some(value) = annotation(map, BINARY_KEY) {
+                return Ok(MsgPack::Binary(annotated_binary(value)?));
+            }
+            if let Some(value) = annotation(map, FLOAT_KEY) {
+                return Ok(MsgPack::F64(annotated_float(value)?));
+            }
+            if let (Some(kind), Some(data)) = (map.get(EXT_KEY), map.get(DATA_KEY)) {
+                let kind = kind
+                    .as_i64()
+                    .and_then(|k| i8::try_from(k).ok())
+                    .ok_or_else(|| anyhow!("{} must be an integer from -128 to 127", EXT_KEY))?;
+                let data = data
+                    .as_str()
+                    .and_then(|d| general_purpose::STANDARD.decode(d).ok())
+                    .ok_or_else(|| anyhow!("Extension data must be a Base64 string"))?;
+                return Ok(MsgPack::Ext(kind, data));
+            }
+            MsgPack::Map(
+                map.iter()
+                    .map(|(key, value)| Ok((MsgPack::from(key.as_str()), json_to_value(value)?)))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        }
+    })
+}
+
+fn number_to_value(number: &Number) -> MsgPack {
+    if let Some(u) = number.as_u64() {
+        MsgPack::from(u)
+    } else if let Some(i) = number.as_i64() {
+        MsgPack::from(i)
+    } else {
+        MsgPack::F64(number.as_f64().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn annotates_binary_and_extensions() {
+        // {"bin": <01 02>, "ts": timestamp32(0), "ext": ext 5 <ff>, "nan": NaN}
+        let bytes = hex::decode(
+            "84a362696ec4020102a27473d6ff00000000a3657874d405ffa36e616ecb7ff8000000000000",
+        )
+        .unwrap();
+        let json = to_json(&bytes).unwrap();
+        assert_eq!(json["bin"], json!({ "$binary": "AQI=" }));
+        assert_eq!(
+            json["ts"],
+            json!({ "$ext": -1, "data": "AAAAAA==", "$timestamp": "1970-01-01T00:00:00+00:00" })
+        );
+        assert_eq!(json["ext"], json!({ "$ext": 5, "data": "/w==" }));
+        assert_eq!(json["nan"], json!({ "$float": "NaN" }));
+
+        let encoded = from_json(&json).unwrap();
+        assert_eq!(to_json(&encoded).unwrap(), json);
+    }
+
+    #[test]
+    fn reports_invalid_input() {
+        assert!(to_json(&[0x92, 0x01]).is_err());
+        assert_eq!(
+            to_json(&[0x01, 0x02]).unwrap_err().to_string(),
+            "Unexpected 1 trailing bytes at byte 1"
+        );
+    }
+}
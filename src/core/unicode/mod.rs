@@ -0,0 +1,472 @@
+use anyhow::{anyhow, Result};
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Characters that reorder surrounding text: LRM/RLM/ALM, the embeddings and overrides,
+/// and the isolates (the "Trojan Source" set).
+const BIDI_CONTROLS: &[char] = &[
+    '\u{061C}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}',
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+];
+
+/// Characters that render as blank but are not classified as format or separator characters.
+const BLANK_LOOKALIKES: &[char] = &['\u{115F}', '\u{1160}', '\u{2800}', '\u{3164}', '\u{FFA0}'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl fmt::Display for NormalizationForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NormalizationForm::Nfc => write!(f, "NFC"),
+            NormalizationForm::Nfd => write!(f, "NFD"),
+            NormalizationForm::Nfkc => write!(f, "NFKC"),
+            NormalizationForm::Nfkd => write!(f, "NFKD"),
+        }
+    }
+}
+
+impl NormalizationForm {
+    pub fn variants() -> &'static [NormalizationForm] {
+        &[
+            NormalizationForm::Nfc,
+            NormalizationForm::Nfd,
+            NormalizationForm::Nfkc,
+            NormalizationForm::Nfkd,
+        ]
+    }
+
+    pub fn apply(&self, input: &str) -> String {
+        match self {
+            NormalizationForm::Nfc => input.nfc().collect(),
+            NormalizationForm::Nfd => input.nfd().collect(),
+            NormalizationForm::Nfkc => input.nfkc().collect(),
+            NormalizationForm::Nfkd => input.nfkd().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl fmt::Display for ByteEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteEncoding::Utf8 => write!(f, "UTF-8"),
+            ByteEncoding::Utf16Le => write!(f, "UTF-16LE"),
+            ByteEncoding::Utf16Be => write!(f, "UTF-16BE"),
+            ByteEncoding::Latin1 => write!(f, "Latin-1"),
+        }
+    }
+}
+
+impl ByteEncoding {
+    pub fn variants() -> &'static [ByteEncoding] {
+        &[
+            ByteEncoding::Utf8,
+            ByteEncoding::Utf16Le,
+            ByteEncoding::Utf16Be,
+            ByteEncoding::Latin1,
+        ]
+    }
+
+    pub fn encode(&self, input: &str) -> Result<Vec<u8>> {
+        match self {
+            ByteEncoding::Utf8 => Ok(input.as_bytes().to_vec()),
+            ByteEncoding::Utf16Le => Ok(input.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            ByteEncoding::Utf16Be => Ok(input.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            ByteEncoding::Latin1 => input
+                .char_indices()
+                .map(|(offset, c)| {
+                    u8::try_from(c).map_err(|_| {
+                        anyhow!(
+                            "U+{:04X} at byte {} cannot be represented in Latin-1",
+                            c as u32,
+                            offset
+                        )
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn decode(&self, input: &[u8]) -> Result<String> {
+        match self {
+            ByteEncoding::Utf8 => String::from_utf8(input.to_vec()).map_err(|e| {
+                anyhow!(
+                    "Invalid UTF-8 sequence at byte {}",
+                    e.utf8_error().valid_up_to()
+                )
+            }),
+            ByteEncoding::Utf16Le | ByteEncoding::Utf16Be => {
+                if !input.len().is_multiple_of(2) {
+                    return Err(anyhow!(
+                        "UTF-16 input must have an even number of bytes, got {}",
+                        input.len()
+                    ));
+                }
+                let units = input.chunks_exact(2).map(|pair| {
+                    let pair = [pair[0], pair[1]];
+                    if *self == ByteEncoding::Utf16Le {
+                        u16::from_le_bytes(pair)
+                    } else {
+                        u16::from_be_bytes(pair)
+                    }
+                });
+
+                let mut output = String::new();
+                let mut offset = 0;
+                for decoded in char::decode_utf16(units) {
+                    let c = decoded.map_err(|e| {
+                        anyhow!(
+                            "Unpaired surrogate 0x{:04X} at byte {}",
+                            e.unpaired_surrogate(),
+                            offset
+                        )
+                    })?;
+                    offset += c.len_utf16() * 2;
+                    output.push(c);
+                }
+                Ok(output)
+            }
+            ByteEncoding::Latin1 => Ok(input.iter().map(|&b| char::from(b)).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharFlag {
+    Invisible,
+    BidiControl,
+    /// The character is confusable with the given prototype from the Unicode confusables table.
+    Confusable(String),
+}
+
+impl fmt::Display for CharFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharFlag::Invisible => write!(f, "invisible"),
+            CharFlag::BidiControl => write!(f, "bidi control"),
+            CharFlag::Confusable(prototype) => write!(f, "looks like \"{}\"", prototype),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CodePointInfo {
+    pub ch: char,
+    pub name: String,
+    pub category: &'static str,
+    pub flags: Vec<CharFlag>,
+}
+
+impl CodePointInfo {
+    pub fn new(ch: char) -> Self {
+        let category = ch.general_category();
+        let name = unicode_names2::name(ch)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| match category {
+                GeneralCategory::Control => "<control>".to_string(),
+                GeneralCategory::PrivateUse => "<private use>".to_string(),
+                _ => "<unnamed>".to_string(),
+            });
+
+        let mut flags = Vec::new();
+        if BIDI_CONTROLS.contains(&ch) {
+            flags.push(CharFlag::BidiControl);
+        } else if is_invisible(ch, category) {
+            flags.push(CharFlag::Invisible);
+        }
+        // ASCII characters are the prototypes that confusables map onto, flagging them would
+        // only produce noise like "0 looks like O". The skeleton is in NFD, so precomposed
+        // letters are compared against their own decomposition.
+        if !ch.is_ascii() {
+            let text = ch.to_string();
+            let prototype: String = unicode_security::skeleton(&text).collect();
+            if prototype != text.nfd().collect::<String>() {
+                flags.push(CharFlag::Confusable(prototype));
+            }
+        }
+
+        Self {
+            ch,
+            name,
+            category: category_abbreviation(category),
+            flags,
+        }
+    }
+
+    pub fn code_point(&self) -> String {
+        format!("U+{:04X}", self.ch as u32)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphemeInfo {
+    pub text: String,
+    pub byte_offset: usize,
+    pub code_points: Vec<CodePointInfo>,
+}
+
+impl GraphemeInfo {
+    pub fn utf8_hex(&self) -> String {
+        format_hex(self.text.as_bytes())
+    }
+
+    pub fn utf16_hex(&self) -> String {
+        self.text
+            .encode_utf16()
+            .map(|unit| format!("{:04X}", unit))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.code_points.iter().any(|cp| !cp.flags.is_empty())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnicodeInspector {
+    pub input: String,
+    pub graphemes: Vec<GraphemeInfo>,
+    pub normalization: NormalizationForm,
+    pub byte_encoding: ByteEncoding,
+    pub bytes: String,
+    pub live_conversion: bool,
+}
+
+impl Default for UnicodeInspector {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            graphemes: Vec::new(),
+            normalization: NormalizationForm::Nfc,
+            byte_encoding: ByteEncoding::Utf8,
+            bytes: String::new(),
+            live_conversion: true,
+        }
+    }
+}
+
+impl UnicodeInspector {
+    pub fn inspect(&mut self) {
+        self.graphemes = self
+            .input
+            .grapheme_indices(true)
+            .map(|(byte_offset, text)| GraphemeInfo {
+                text: text.to_string(),
+                byte_offset,
+                code_points: text.chars().map(CodePointInfo::new).collect(),
+            })
+            .collect();
+    }
+
+    /// Replaces the input with its normalized form.
+    pub fn normalize(&mut self) {
+        self.input = self.normalization.apply(&self.input);
+        self.inspect();
+    }
+
+    pub fn is_normalized(&self) -> bool {
+        self.normalization.apply(&self.input) == self.input
+    }
+
+    /// The confusable skeleton of the whole input. Two strings with the same skeleton are
+    /// visually confusable, e.g. a lookalike domain and the one it imitates.
+    pub fn skeleton(&self) -> String {
+        unicode_security::skeleton(&self.input).collect()
+    }
+
+    pub fn code_point_count(&self) -> usize {
+        self.graphemes.iter().map(|g| g.code_points.len()).sum()
+    }
+
+    pub fn flagged_count(&self) -> usize {
+        self.graphemes
+            .iter()
+            .flat_map(|g| &g.code_points)
+            .filter(|cp| !cp.flags.is_empty())
+            .count()
+    }
+
+    /// Writes the input as hex bytes in the selected encoding.
+    pub fn text_to_bytes(&mut self) -> Result<()> {
+        let bytes = self.byte_encoding.encode(&self.input)?;
+        self.bytes = format_hex(&bytes);
+        Ok(())
+    }
+
+    /// Replaces the input with the hex bytes decoded in the selected encoding.
+    pub fn bytes_to_text(&mut self) -> Result<()> {
+        let digits: String = self.bytes.chars().filter(|c| !c.is_whitespace()).collect();
+        let bytes = hex::decode(&digits).map_err(|e| anyhow!("Invalid hex bytes: {}", e))?;
+        self.input = self.byte_encoding.decode(&bytes)?;
+        self.inspect();
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.bytes.clear();
+        self.graphemes.clear();
+    }
+}
+
+fn is_invisible(ch: char, category: GeneralCategory) -> bool {
+    match category {
+        GeneralCategory::Format => true,
+        GeneralCategory::Control => !matches!(ch, '\t' | '\n' | '\r'),
+        GeneralCategory::SpaceSeparator => ch != ' ',
+        GeneralCategory::LineSeparator | GeneralCategory::ParagraphSeparator => true,
+        _ => BLANK_LOOKALIKES.contains(&ch),
+    }
+}
+
+fn category_abbreviation(category: GeneralCategory) -> &'static str {
+    match category {
+        GeneralCategory::UppercaseLetter => "Lu",
+        GeneralCategory::LowercaseLetter => "Ll",
+        GeneralCategory::TitlecaseLetter => "Lt",
+        GeneralCategory::ModifierLetter => "Lm",
+        GeneralCategory::OtherLetter => "Lo",
+        GeneralCategory::NonspacingMark => "Mn",
+        GeneralCategory::SpacingMark => "Mc",
+        GeneralCategory::EnclosingMark => "Me",
+        GeneralCategory::DecimalNumber => "Nd",
+        GeneralCategory::LetterNumber => "Nl",
+        GeneralCategory::OtherNumber => "No",
+        GeneralCategory::ConnectorPunctuation => "Pc",
+        GeneralCategory::DashPunctuation => "Pd",
+        GeneralCategory::OpenPunctuation => "Ps",
+        GeneralCategory::ClosePunctuation => "Pe",
+        GeneralCategory::InitialPunctuation => "Pi",
+        GeneralCategory::FinalPunctuation => "Pf",
+        GeneralCategory::OtherPunctuation => "Po",
+        GeneralCategory::MathSymbol => "Sm",
+        GeneralCategory::CurrencySymbol => "Sc",
+        GeneralCategory::ModifierSymbol => "Sk",
+        GeneralCategory::OtherSymbol => "So",
+        GeneralCategory::SpaceSeparator => "Zs",
+        GeneralCategory::LineSeparator => "Zl",
+        GeneralCategory::ParagraphSeparator => "Zp",
+        GeneralCategory::Control => "Cc",
+        GeneralCategory::Format => "Cf",
+        GeneralCategory::Surrogate => "Cs",
+        GeneralCategory::PrivateUse => "Co",
+        GeneralCategory::Unassigned => "Cn",
+    }
+}
+
+fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inspect(input: &str) -> UnicodeInspector {
+        let mut inspector = UnicodeInspector {
+            input: input.to_string(),
+            ..Default::default()
+        };
+        inspector.inspect();
+        inspector
+    }
+
+    #[test]
+    fn combining_marks_form_one_grapheme() {
+        let inspector = inspect("e\u{301}x");
+        assert_eq!(inspector.graphemes.len(), 2);
+        let first = &inspector.graphemes[0];
+        assert_eq!(first.code_points.len(), 2);
+        assert_eq!(first.code_points[1].name, "COMBINING ACUTE ACCENT");
+        assert_eq!(first.code_points[1].category, "Mn");
+        assert_eq!(first.utf8_hex(), "65 CC 81");
+        assert_eq!(first.utf16_hex(), "0065 0301");
+        assert_eq!(inspector.graphemes[1].byte_offset, 3);
+    }
+
+    #[test]
+    fn normalization_forms() {
+        assert_eq!(NormalizationForm::Nfc.apply("e\u{301}"), "\u{e9}");
+        assert_eq!(NormalizationForm::Nfd.apply("\u{e9}"), "e\u{301}");
+        assert_eq!(NormalizationForm::Nfkc.apply("\u{FB01}"), "fi");
+        assert_eq!(NormalizationForm::Nfkd.apply("\u{2460}"), "1");
+    }
+
+    #[test]
+    fn flags_invisible_bidi_and_confusables() {
+        let inspector = inspect("p\u{0430}y\u{200B}\u{202E}\u{e9}");
+        let flags: Vec<_> = inspector
+            .graphemes
+            .iter()
+            .flat_map(|g| &g.code_points)
+            .map(|cp| cp.flags.clone())
+            .collect();
+        assert!(flags[0].is_empty());
+        assert_eq!(flags[1], vec![CharFlag::Confusable("a".to_string())]);
+        assert_eq!(flags[3], vec![CharFlag::Invisible]);
+        assert_eq!(flags[4], vec![CharFlag::BidiControl]);
+        assert!(flags[5].is_empty());
+        assert_eq!(inspector.flagged_count(), 3);
+    }
+
+    #[test]
+    fn lookalike_domains_share_a_skeleton() {
+        let spoofed = inspect("\u{0440}\u{0430}ypal.com").skeleton();
+        let genuine = inspect("paypal.com").skeleton();
+        assert_eq!(spoofed, genuine);
+    }
+
+    #[test]
+    fn byte_encodings_round_trip() {
+        let text = "h\u{e9}llo \u{1F600}";
+        for encoding in [
+            ByteEncoding::Utf8,
+            ByteEncoding::Utf16Le,
+            ByteEncoding::Utf16Be,
+        ] {
+            let bytes = encoding.encode(text).unwrap();
+            assert_eq!(encoding.decode(&bytes).unwrap(), text);
+        }
+        assert_eq!(ByteEncoding::Utf16Be.encode("A").unwrap(), vec![0x00, 0x41]);
+        assert_eq!(
+            ByteEncoding::Latin1.encode("h\u{e9}").unwrap(),
+            vec![0x68, 0xE9]
+        );
+        assert!(ByteEncoding::Latin1.encode(text).is_err());
+        assert!(ByteEncoding::Utf16Le.decode(&[0x41]).is_err());
+    }
+
+    #[test]
+    fn reinterprets_mojibake() {
+        let mut inspector = inspect("");
+        inspector.byte_encoding = ByteEncoding::Latin1;
+        inspector.bytes = "C3 A9".to_string();
+        inspector.bytes_to_text().unwrap();
+        assert_eq!(inspector.input, "\u{c3}\u{a9}");
+
+        inspector.text_to_bytes().unwrap();
+        inspector.byte_encoding = ByteEncoding::Utf8;
+        inspector.bytes_to_text().unwrap();
+        assert_eq!(inspector.input, "\u{e9}");
+    }
+}
@@ -3,9 +3,11 @@ use crate::core::color::Color;
 use anyhow::{Error, Result};
 use core_graphics::{
     base::CGFloat,
-    display::{CGDirectDisplayID, CGGetDisplaysWithRect, CGPoint, CGRect, CGSize},
+    display::{CGDirectDisplayID, CGDisplay, CGGetDisplaysWithRect, CGPoint, CGRect, CGSize},
     sys::{CGEventRef, CGEventSourceRef, CGImageRef},
+    window::{kCGNullWindowID, kCGWindowImageNominalResolution, kCGWindowListOptionOnScreenOnly},
 };
+use image::{Rgba, RgbaImage};
 use objc::{class, msg_send, rc::autoreleasepool, runtime::Object, sel, sel_impl};
 use std::ptr::null;
 
@@ -69,4 +71,36 @@ impl DisplayPicker for MacConn {
             Ok(Color::Rgb(Rgb::new(r as f32, g as f32, b as f32)))
         })
     }
+
+    fn capture_region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<RgbaImage> {
+        autoreleasepool(|| {
+            let rect = CGRect::new(
+                &CGPoint::new(x as CGFloat, y as CGFloat),
+                &CGSize::new(width as CGFloat, height as CGFloat),
+            );
+            let image = CGDisplay::screenshot(
+                rect,
+                kCGWindowListOptionOnScreenOnly,
+                kCGNullWindowID,
+                kCGWindowImageNominalResolution,
+            )
+            .ok_or_else(|| Error::msg("failed to acquire image of display"))?;
+            if image.bits_per_pixel() != 32 {
+                return Err(Error::msg("unsupported screen pixel format"));
+            }
+
+            // 32-bit little-endian BGRA rows, possibly padded
+            let stride = image.bytes_per_row();
+            let data = image.data();
+            let bytes = data.bytes();
+            Ok(RgbaImage::from_fn(
+                image.width() as u32,
+                image.height() as u32,
+                |x, y| {
+                    let i = y as usize * stride + x as usize * 4;
+                    Rgba([bytes[i + 2], bytes[i + 1], bytes[i], 255])
+                },
+            ))
+        })
+    }
 }
@@ -18,11 +18,15 @@ pub use x11::DisplayPickerExt;
 pub trait DisplayPickerExt: DisplayPicker {}
 
 use anyhow::Result;
+use image::RgbaImage;
 use std::{fmt::Debug, rc::Rc};
 
 pub trait DisplayPicker: Debug {
     fn get_cursor_pos(&self) -> Result<(i32, i32)>;
     fn get_color_under_cursor(&self) -> Result<Color>;
+
+    /// Captures a region of the screen in root window coordinates.
+    fn capture_region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<RgbaImage>;
 }
 
 pub fn init_display_picker() -> Option<Rc<dyn DisplayPickerExt>> {
@@ -6,14 +6,16 @@ use crate::core::color::Color;
 use crate::platform::display_picker::DisplayPicker;
 use anyhow::{Context, Error, Result};
 use eframe::egui::Color32;
+use image::{Rgba, RgbaImage};
 use windows::{
     core::PCWSTR,
     Win32::{
         Foundation::{HINSTANCE, LPARAM, LRESULT, POINT, WPARAM},
         Graphics::Gdi::{
-            BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, GetDC, GetPixel,
-            Rectangle, ReleaseDC, SelectObject, SetStretchBltMode, StretchBlt, UpdateWindow,
-            CLR_INVALID, COLORONCOLOR, HBITMAP, HDC, SRCCOPY,
+            BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC,
+            GetDIBits, GetPixel, Rectangle, ReleaseDC, SelectObject, SetStretchBltMode, StretchBlt,
+            UpdateWindow, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, CLR_INVALID, COLORONCOLOR,
+            DIB_RGB_COLORS, HBITMAP, HDC, SRCCOPY,
         },
         System::LibraryLoader::GetModuleHandleW,
         UI::WindowsAndMessaging::{
@@ -158,6 +160,45 @@ impl DisplayPicker for WinConn {
 
         Ok(Color::from(Color32::from_rgb(r, g, b)))
     }
+
+    fn capture_region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<RgbaImage> {
+        let bitmap = self.get_screenshot(x, y, width as i32, height as i32)?;
+
+        // Top-down 32-bit BGRA rows
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: bitmap.width,
+                biHeight: -bitmap.height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        let lines = unsafe {
+            GetDIBits(
+                self.device_context,
+                bitmap.hbitmap,
+                0,
+                height,
+                pixels.as_mut_ptr().cast(),
+                &mut info,
+                DIB_RGB_COLORS,
+            )
+        };
+        unsafe { DeleteObject(bitmap.hbitmap) };
+        if lines != height as i32 {
+            return Err(Error::msg("failed to read captured bitmap"));
+        }
+
+        Ok(RgbaImage::from_fn(width, height, |x, y| {
+            let i = (y * width + x) as usize * 4;
+            Rgba([pixels[i + 2], pixels[i + 1], pixels[i], 255])
+        }))
+    }
 }
 
 impl DisplayPickerExt for WinConn {
@@ -3,9 +3,9 @@ use std::borrow::Cow;
 
 use crate::core::color::Color;
 use crate::platform::display_picker::DisplayPicker;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use eframe::egui::Color32;
-use image::{imageops, ImageBuffer, Rgba};
+use image::{imageops, ImageBuffer, Rgba, RgbaImage};
 use x11rb::{
     connection::Connection,
     cursor::Handle as CursorHandle,
@@ -74,6 +74,14 @@ pub fn add_border<'a>(img: &'a Image, color: &Rgba<u8>, width: u32) -> Result<Im
     .context("failed to create a new image with border")
 }
 
+fn pixel_to_rgb(pixel: u32) -> (u8, u8, u8) {
+    let red = (pixel >> 8) & 0xff;
+    let green = (pixel >> 16) & 0xff;
+    let blue = (pixel >> 24) & 0xff;
+
+    (red as u8, green as u8, blue as u8)
+}
+
 pub enum WindowType {
     Desktop,
     Dock,
@@ -185,13 +193,26 @@ impl X11Conn {
 
     pub fn get_color(&self, window: Window, x: i16, y: i16) -> Result<(u8, u8, u8)> {
         let img = self.get_image(window, x, y, 1, 1)?;
-        let pixel = img.get_pixel(0, 0);
+        Ok(pixel_to_rgb(img.get_pixel(0, 0)))
+    }
 
-        let red = (pixel >> 8) & 0xff;
-        let green = (pixel >> 16) & 0xff;
-        let blue = (pixel >> 24) & 0xff;
+    /// Captures a region of the root window, clamped to the screen.
+    pub fn capture_region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<RgbaImage> {
+        let screen = self.screen();
+        let left = x.clamp(0, screen.width_in_pixels as i32);
+        let top = y.clamp(0, screen.height_in_pixels as i32);
+        let right = (x + width as i32).clamp(left, screen.width_in_pixels as i32);
+        let bottom = (y + height as i32).clamp(top, screen.height_in_pixels as i32);
+        let (width, height) = ((right - left) as u16, (bottom - top) as u16);
+        if width == 0 || height == 0 {
+            return Err(anyhow!("Capture region is outside of the screen"));
+        }
 
-        Ok((red as u8, green as u8, blue as u8))
+        let img = self.get_image(screen.root, left as i16, top as i16, width, height)?;
+        Ok(RgbaImage::from_fn(width as u32, height as u32, |x, y| {
+            let (red, green, blue) = pixel_to_rgb(img.get_pixel(x as u16, y as u16));
+            Rgba([red, green, blue, 255])
+        }))
     }
 
     pub fn get_color_for_screen(&self, screen: &Screen) -> Result<(u8, u8, u8)> {
@@ -409,6 +430,10 @@ impl DisplayPicker for X11Conn {
         self.get_color_for_conn()
             .map(|color| Color32::from_rgb(color.0, color.1, color.2).into())
     }
+
+    fn capture_region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<RgbaImage> {
+        self.capture_region(x, y, width, height)
+    }
 }
 
 impl DisplayPickerExt for X11Conn {
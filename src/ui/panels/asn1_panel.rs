@@ -0,0 +1,190 @@
+use eframe::egui::{
+    CollapsingHeader, Color32, ComboBox, CursorIcon, RichText, ScrollArea, TextEdit, Ui,
+};
+
+use crate::{
+    context::FrameCtx,
+    core::{asn1::Asn1Node, encoding::input::BinaryInput},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+pub struct Asn1Panel;
+
+impl UiPanel for Asn1Panel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("ASN.1 Viewer");
+        ui.add_space(DOUBLE_SPACE);
+
+        self.render_input_section(ctx, ui);
+        ui.add_space(SPACE);
+        self.render_action_buttons(ctx, ui);
+        ui.add_space(SPACE);
+        ui.separator();
+        ui.add_space(SPACE);
+        self.render_tree_section(ctx, ui);
+    }
+}
+
+impl Default for Asn1Panel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Asn1Panel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_input_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("PEM, or DER as hex or Base64");
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("asn1_input")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.asn1.input)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(8)
+                        .hint_text("-----BEGIN CERTIFICATE-----"),
+                );
+            });
+    }
+
+    fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("DER input as:");
+            ComboBox::from_id_salt("asn1_input_format")
+                .selected_text(ctx.app.asn1.input_format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in BinaryInput::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.asn1.input_format,
+                            *format,
+                            format.to_string(),
+                        );
+                    }
+                });
+
+            ui.add_space(SPACE);
+
+            if ui
+                .button("🔍 Decode")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.asn1.decode() {
+                    append_global_error(e);
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load file")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .open_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.asn1.load_file(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            let crypto_keys = [
+                ctx.app.crypto.input.public_key.as_deref(),
+                ctx.app.crypto.input.private_key.as_deref(),
+            ];
+            let crypto_keys: Vec<&str> = crypto_keys
+                .into_iter()
+                .flatten()
+                .filter(|key| !key.trim().is_empty())
+                .collect();
+            if !crypto_keys.is_empty()
+                && ui
+                    .button("From Crypto tab")
+                    .on_hover_text("Decode the public and private keys of the Crypto tab")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+            {
+                ctx.app.asn1.input = crypto_keys.join("\n");
+                if let Err(e) = ctx.app.asn1.decode() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.asn1.clear();
+            }
+        });
+    }
+
+    fn render_tree_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        if let Some(error) = &ctx.app.asn1.error {
+            ui.colored_label(Color32::RED, format!("❌ {}", error));
+            return;
+        }
+
+        ScrollArea::vertical().id_salt("asn1_tree").show(ui, |ui| {
+            for (index, document) in ctx.app.asn1.documents.iter().enumerate() {
+                let title = match &document.label {
+                    Some(label) => format!("{} ({} bytes)", label, document.size),
+                    None => format!("DER ({} bytes)", document.size),
+                };
+                ui.label(RichText::new(title).strong());
+                ui.add_space(HALF_SPACE);
+                Self::render_nodes(ui, index, &document.nodes);
+                ui.add_space(SPACE);
+            }
+        });
+    }
+
+    fn render_nodes(ui: &mut Ui, document: usize, nodes: &[Asn1Node]) {
+        for node in nodes {
+            let mut summary = format!(
+                "{} @{} ({}+{})",
+                node.tag,
+                node.offset,
+                node.header_length,
+                if node.indefinite {
+                    format!("{}, indefinite", node.length)
+                } else {
+                    node.length.to_string()
+                }
+            );
+            if node.encapsulated {
+                summary.push_str(" encapsulates");
+            }
+
+            if node.children.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.monospace(summary);
+                    ui.label(RichText::new(&node.value).monospace().strong());
+                });
+            } else {
+                if !node.encapsulated {
+                    summary = format!("{} {}", summary, node.value);
+                }
+                CollapsingHeader::new(RichText::new(summary).monospace())
+                    .id_salt(("asn1_node", document, node.offset))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        Self::render_nodes(ui, document, &node.children);
+                    });
+            }
+        }
+    }
+}
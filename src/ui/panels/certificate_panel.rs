@@ -0,0 +1,254 @@
+use chrono::Utc;
+use eframe::egui::{
+    CollapsingHeader, Color32, ComboBox, CursorIcon, Grid, RichText, ScrollArea, TextEdit, Ui,
+};
+
+use crate::{
+    context::FrameCtx,
+    core::{crypto::x509::CertificateInfo, encoding::input::BinaryInput},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+pub struct CertificatePanel;
+
+impl UiPanel for CertificatePanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("Certificate Inspector");
+        ui.add_space(DOUBLE_SPACE);
+
+        ui.columns(2, |columns| {
+            self.render_bundle_input(ctx, &mut columns[0]);
+            self.render_root_input(ctx, &mut columns[1]);
+        });
+        ui.add_space(SPACE);
+        self.render_action_buttons(ctx, ui);
+        ui.add_space(SPACE);
+        ui.separator();
+        ui.add_space(SPACE);
+
+        if let Some(error) = &ctx.app.certificates.error {
+            ui.colored_label(Color32::RED, format!("❌ {}", error));
+            return;
+        }
+
+        ScrollArea::vertical()
+            .id_salt("certificate_results")
+            .show(ui, |ui| {
+                self.render_chain_section(ctx, ui);
+                for (index, certificate) in ctx.app.certificates.certificates.iter().enumerate() {
+                    Self::render_certificate(ui, index, certificate);
+                }
+            });
+    }
+}
+
+impl Default for CertificatePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CertificatePanel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_bundle_input(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Certificate or chain (leaf first)");
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("certificate_input")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.certificates.input)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(8)
+                        .hint_text("-----BEGIN CERTIFICATE-----"),
+                );
+            });
+    }
+
+    fn render_root_input(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Trusted root (optional)");
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("certificate_root_input")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.certificates.root_input)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(8),
+                );
+            });
+    }
+
+    fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("DER input as:");
+            ComboBox::from_id_salt("certificate_input_format")
+                .selected_text(ctx.app.certificates.input_format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in BinaryInput::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.certificates.input_format,
+                            *format,
+                            format.to_string(),
+                        );
+                    }
+                });
+
+            ui.add_space(SPACE);
+
+            if ui
+                .button("🔍 Inspect")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.certificates.inspect() {
+                    append_global_error(e);
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load file")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .open_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.certificates.load_file(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.certificates.clear();
+            }
+        });
+    }
+
+    fn render_chain_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let inspector = &ctx.app.certificates;
+        if inspector.chain.is_empty() {
+            return;
+        }
+
+        ui.label(RichText::new("Chain").strong());
+        ui.add_space(HALF_SPACE);
+        Grid::new("certificate_chain_grid")
+            .num_columns(2)
+            .striped(true)
+            .spacing([DOUBLE_SPACE, HALF_SPACE])
+            .show(ui, |ui| {
+                for link in &inspector.chain {
+                    ui.label(&link.subject);
+                    let (icon, color) = if link.status.is_ok() {
+                        ("✔", Color32::GREEN)
+                    } else {
+                        ("❌", Color32::RED)
+                    };
+                    ui.colored_label(color, format!("{} {}", icon, link.status));
+                    ui.end_row();
+                }
+            });
+
+        if inspector.root.is_some() {
+            ui.add_space(HALF_SPACE);
+            if inspector.chain_reaches_root() {
+                ui.colored_label(Color32::GREEN, "Chain links up to the provided root");
+            } else {
+                ui.colored_label(Color32::RED, "Chain does not link up to the provided root");
+            }
+        }
+        ui.add_space(SPACE);
+    }
+
+    fn render_certificate(ui: &mut Ui, index: usize, certificate: &CertificateInfo) {
+        CollapsingHeader::new(RichText::new(&certificate.subject).strong())
+            .id_salt(("certificate", index))
+            .default_open(index == 0)
+            .show(ui, |ui| {
+                let now = Utc::now();
+                let expired = now > certificate.not_after || now < certificate.not_before;
+
+                Grid::new(("certificate_grid", index))
+                    .num_columns(2)
+                    .striped(true)
+                    .spacing([DOUBLE_SPACE, HALF_SPACE])
+                    .show(ui, |ui| {
+                        let mut row = |label: &str, value: String| {
+                            ui.label(label);
+                            ui.monospace(value);
+                            ui.end_row();
+                        };
+                        row("Subject", certificate.subject.clone());
+                        row("Issuer", certificate.issuer.clone());
+                        row("Serial", certificate.serial.clone());
+                        row("Version", certificate.version.to_string());
+                        row("Not before", certificate.not_before.to_rfc3339());
+                        row("Not after", certificate.not_after.to_rfc3339());
+                        row(
+                            "Public key",
+                            format!(
+                                "{}, {} bits",
+                                certificate.key_algorithm, certificate.key_size
+                            ),
+                        );
+                        row("Signature", certificate.signature_algorithm.clone());
+                        if !certificate.subject_alt_names.is_empty() {
+                            row("SANs", certificate.subject_alt_names.join("\n"));
+                        }
+                        row("SHA-1", certificate.sha1_fingerprint.clone());
+                        row("SHA-256", certificate.sha256_fingerprint.clone());
+
+                        ui.label("Status");
+                        let color = if expired {
+                            Color32::RED
+                        } else {
+                            Color32::GREEN
+                        };
+                        ui.colored_label(color, certificate.validity_status(now));
+                        ui.end_row();
+                    });
+
+                if certificate.extensions.is_empty() {
+                    return;
+                }
+                ui.add_space(SPACE);
+                ui.label(RichText::new("Extensions").strong());
+                ui.add_space(HALF_SPACE);
+                Grid::new(("certificate_extensions_grid", index))
+                    .num_columns(2)
+                    .striped(true)
+                    .spacing([DOUBLE_SPACE, HALF_SPACE])
+                    .show(ui, |ui| {
+                        for extension in &certificate.extensions {
+                            if extension.critical {
+                                ui.label(format!("{} (critical)", extension.name));
+                            } else {
+                                ui.label(&extension.name);
+                            }
+                            ui.monospace(&extension.value);
+                            ui.end_row();
+                        }
+                    });
+            });
+        ui.add_space(HALF_SPACE);
+    }
+}
@@ -1,15 +1,23 @@
 use crate::{
     context::FrameCtx,
-    core::encoding::EncodingType,
+    core::encoding::{compression::Compression, mime::display_path, EncodingType},
     types::error::append_global_error,
     ui::{
         components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
         traits::UiPanel,
     },
 };
-use eframe::egui::{Align, CursorIcon, Layout, Resize, ScrollArea, Ui};
+use eframe::egui::{
+    self, Align, CollapsingHeader, Color32, ColorImage, ComboBox, CursorIcon, Image, Layout,
+    Resize, ScrollArea, TextEdit, TextureHandle, TextureOptions, Ui,
+};
+
+const PREVIEW_MAX_SIZE: f32 = 256.0;
 
-pub struct EncodingPanel;
+pub struct EncodingPanel {
+    preview: Option<TextureHandle>,
+    preview_error: Option<String>,
+}
 
 impl UiPanel for EncodingPanel {
     fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -18,6 +26,19 @@ impl UiPanel for EncodingPanel {
 
         ui.horizontal(|ui| {
             self.render_main_section(ctx, ui);
+            ui.add_space(DOUBLE_SPACE);
+            ui.vertical(|ui| {
+                CollapsingHeader::new("MIME Parts")
+                    .id_salt("encoding_mime")
+                    .show(ui, |ui| {
+                        self.render_mime_section(ctx, ui);
+                    });
+                CollapsingHeader::new("Data URI")
+                    .id_salt("encoding_data_uri")
+                    .show(ui, |ui| {
+                        self.render_data_uri_section(ctx, ui);
+                    });
+            });
         });
     }
 }
@@ -30,7 +51,10 @@ impl Default for EncodingPanel {
 
 impl EncodingPanel {
     pub fn new() -> Self {
-        Self
+        Self {
+            preview: None,
+            preview_error: None,
+        }
     }
 
     fn render_main_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -38,6 +62,8 @@ impl EncodingPanel {
             self.render_input_section(ctx, ui);
             ui.add_space(HALF_SPACE);
             self.render_encoding_selection(ctx, ui);
+            ui.add_space(HALF_SPACE);
+            self.render_compression_selection(ctx, ui);
             ui.add_space(SPACE);
             self.render_options_section(ctx, ui);
             ui.add_space(SPACE);
@@ -78,48 +104,83 @@ impl EncodingPanel {
     }
 
     fn render_encoding_selection(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        ui.horizontal(|ui| {
+        let mut encoding_changed = false;
+
+        ui.horizontal_wrapped(|ui| {
             ui.label("Encoding Type:");
-            let mut encoding_changed = false;
+            for encoding in EncodingType::variants().iter().filter(|e| !e.is_escape()) {
+                encoding_changed |= ui
+                    .radio_value(
+                        &mut ctx.app.encoding.encoding_type,
+                        encoding.clone(),
+                        encoding.to_string(),
+                    )
+                    .changed();
+            }
+        });
 
-            encoding_changed |= ui
-                .radio_value(
-                    &mut ctx.app.encoding.encoding_type,
-                    EncodingType::Base64,
-                    "Base64",
-                )
-                .changed();
-            encoding_changed |= ui
-                .radio_value(
-                    &mut ctx.app.encoding.encoding_type,
-                    EncodingType::Base64Url,
-                    "Base64 URL",
-                )
-                .changed();
-            encoding_changed |= ui
-                .radio_value(
-                    &mut ctx.app.encoding.encoding_type,
-                    EncodingType::Base64Mime,
-                    "Base64 MIME",
-                )
-                .changed();
-            encoding_changed |= ui
-                .radio_value(
-                    &mut ctx.app.encoding.encoding_type,
-                    EncodingType::Base32,
-                    "Base32",
-                )
-                .changed();
-            encoding_changed |= ui
-                .radio_value(
-                    &mut ctx.app.encoding.encoding_type,
-                    EncodingType::UrlEncoding,
-                    "URL Encoding",
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Escaping:");
+            for encoding in EncodingType::variants().iter().filter(|e| e.is_escape()) {
+                encoding_changed |= ui
+                    .radio_value(
+                        &mut ctx.app.encoding.encoding_type,
+                        encoding.clone(),
+                        encoding.to_string(),
+                    )
+                    .changed();
+            }
+        });
+
+        // Trigger live encoding if enabled and encoding type changed
+        if ctx.app.encoding.live_conversion && encoding_changed {
+            if let Err(e) = ctx.app.encoding.encode() {
+                append_global_error(e);
+            }
+        }
+    }
+
+    fn render_compression_selection(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Compression:");
+            let mut compression_changed = false;
+
+            ComboBox::from_id_salt("encoding_compression")
+                .selected_text(
+                    ctx.app
+                        .encoding
+                        .compression
+                        .map_or("None".to_string(), |c| c.to_string()),
                 )
-                .changed();
+                .show_ui(ui, |ui| {
+                    compression_changed |= ui
+                        .selectable_value(&mut ctx.app.encoding.compression, None, "None")
+                        .changed();
+                    for compression in Compression::variants() {
+                        compression_changed |= ui
+                            .selectable_value(
+                                &mut ctx.app.encoding.compression,
+                                Some(*compression),
+                                compression.to_string(),
+                            )
+                            .changed();
+                    }
+                });
 
-            // Trigger live encoding if enabled and encoding type changed
-            if ctx.app.encoding.live_conversion && encoding_changed {
+            ui.checkbox(
+                &mut ctx.app.encoding.detect_compression,
+                "Auto-detect when decoding",
+            )
+            .on_hover_text(
+                "Detect gzip, zlib and zstd from magic bytes, then try raw deflate and brotli",
+            );
+
+            if let Some(detected) = ctx.app.encoding.detected_compression {
+                ui.label(format!("Detected: {}", detected));
+            }
+
+            // Trigger live encoding if enabled and compression changed
+            if ctx.app.encoding.live_conversion && compression_changed {
                 if let Err(e) = ctx.app.encoding.encode() {
                     append_global_error(e);
                 }
@@ -229,4 +290,216 @@ impl EncodingPanel {
                 });
         });
     }
+
+    fn render_mime_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let mime = &mut ctx.app.encoding.mime;
+
+        ui.label("Raw message");
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("mime_raw")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut mime.raw)
+                        .code_editor()
+                        .hint_text("Paste a raw .eml message"),
+                );
+            });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("📨 Parse")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = mime.parse() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                mime.clear();
+            }
+        });
+        ui.add_space(SPACE);
+
+        let mut clicked = None;
+        for (i, part) in mime.parts.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_space(part.depth as f32 * DOUBLE_SPACE);
+                let mut label = format!(
+                    "{}  {}  [{}]",
+                    display_path(&part.path),
+                    part.content_type,
+                    part.transfer_encoding
+                );
+                if let Some(filename) = &part.filename {
+                    label.push_str(&format!("  {}", filename));
+                }
+                if ui
+                    .selectable_label(mime.selected == Some(i), label)
+                    .on_hover_text("Decode this part")
+                    .clicked()
+                {
+                    clicked = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = clicked {
+            if let Err(e) = mime.select(i) {
+                append_global_error(e);
+            }
+        }
+
+        if mime.selected.is_some() {
+            ui.add_space(SPACE);
+            ui.label("Decoded part");
+            ui.add_space(HALF_SPACE);
+            let mut output = mime.output.as_str();
+            ScrollArea::vertical()
+                .id_salt("mime_output")
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    ui.add(TextEdit::multiline(&mut output).code_editor());
+                });
+        }
+    }
+
+    fn render_data_uri_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("data: URI");
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("data_uri_text")
+            .max_height(150.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.encoding.data_uri.uri)
+                        .code_editor()
+                        .hint_text("data:image/png;base64,..."),
+                );
+            });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load file")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .open_single_file()
+                    .show()
+                {
+                    match ctx.app.encoding.data_uri.load_file(path) {
+                        Ok(_) => self.update_preview(ctx, ui.ctx()),
+                        Err(e) => append_global_error(e),
+                    }
+                }
+            }
+
+            if ui
+                .button("🔍 Parse")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                match ctx.app.encoding.data_uri.parse() {
+                    Ok(_) => self.update_preview(ctx, ui.ctx()),
+                    Err(e) => {
+                        self.preview = None;
+                        self.preview_error = None;
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(data_uri) = &ctx.app.encoding.data_uri.parsed {
+                if ui
+                    .button("💾 Save payload")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                        .set_filename(format!("payload.{}", data_uri.extension()))
+                        .save_single_file()
+                        .show()
+                    {
+                        if let Err(e) = ctx.app.encoding.data_uri.save_payload(path) {
+                            append_global_error(e);
+                        }
+                    }
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.encoding.data_uri.clear();
+                self.preview = None;
+                self.preview_error = None;
+            }
+        });
+
+        let Some(data_uri) = &ctx.app.encoding.data_uri.parsed else {
+            return;
+        };
+
+        ui.add_space(SPACE);
+        ui.label(format!(
+            "{}, {} bytes{}",
+            data_uri.mime,
+            data_uri.payload.len(),
+            if data_uri.base64 { ", base64" } else { "" }
+        ));
+        if let Some(detected) = data_uri.mime_mismatch() {
+            ui.colored_label(
+                Color32::YELLOW,
+                format!("⚠ Payload looks like {}, not {}", detected, data_uri.mime),
+            );
+        }
+
+        if let Some(texture) = &self.preview {
+            let size = texture.size_vec2();
+            let scale = (PREVIEW_MAX_SIZE / size.x.max(size.y)).min(1.0);
+            ui.add_space(HALF_SPACE);
+            ui.add(Image::new(texture).fit_to_exact_size(size * scale));
+        } else if let Some(error) = &self.preview_error {
+            ui.colored_label(Color32::RED, format!("Preview unavailable: {}", error));
+        }
+    }
+
+    fn update_preview(&mut self, ctx: &mut FrameCtx<'_>, egui_ctx: &egui::Context) {
+        self.preview = None;
+        self.preview_error = None;
+
+        let Some(data_uri) = &ctx.app.encoding.data_uri.parsed else {
+            return;
+        };
+        if !data_uri.is_image() {
+            return;
+        }
+
+        match data_uri.decode_image() {
+            Ok(image) => {
+                let size = [image.width() as usize, image.height() as usize];
+                let color_image = ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                self.preview = Some(egui_ctx.load_texture(
+                    "data_uri_preview",
+                    color_image,
+                    TextureOptions::default(),
+                ));
+            }
+            Err(e) => self.preview_error = Some(e.to_string()),
+        }
+    }
 }
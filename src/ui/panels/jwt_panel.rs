@@ -1,7 +1,22 @@
-use eframe::egui::{self, Align, CursorIcon, Layout, Resize, ScrollArea, Ui};
+use eframe::egui::{
+    self, Align, CollapsingHeader, ComboBox, CursorIcon, DragValue, Grid, Layout, Resize, RichText,
+    ScrollArea, Ui,
+};
 use eframe::epaint::Color32;
 
-use crate::core::jwt::Algorithm;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::jwt::audit::AuditState;
+use crate::core::jwt::{
+    claims::{ClaimReport, ClaimStatus},
+    header,
+    jwe::{ContentEncryption, KeyManagement},
+    lint::Severity,
+    secret::SecretEncoding,
+    Algorithm,
+};
 use crate::{
     context::FrameCtx,
     types::error::append_global_error,
@@ -47,8 +62,14 @@ impl JwtPanel {
             self.render_action_buttons(ctx, ui);
             ui.add_space(HALF_SPACE);
             self.render_decoded_section(ctx, ui);
+            ui.add_space(HALF_SPACE);
+            self.render_templates_section(ctx, ui);
             ui.add_space(SPACE);
             self.render_header_section(ctx, ui);
+            ui.add_space(SPACE);
+            self.render_claims_section(ctx, ui);
+            ui.add_space(SPACE);
+            self.render_findings_section(ctx, ui);
         });
     }
 
@@ -72,7 +93,14 @@ impl JwtPanel {
                                 let response = ui.text_edit_multiline(&mut ctx.app.jwt.encoded);
 
                                 if response.changed() {
+                                    ctx.app.jwt.select_algorithm_from_header();
+                                    if let Ok(header) = ctx.app.jwt.get_header() {
+                                        ctx.app.jwt.header = header;
+                                    }
                                     let _ = ctx.app.jwt.verify();
+                                    if ctx.app.jwt.claim_report.is_some() {
+                                        let _ = ctx.app.jwt.validate_claims(&ctx.app.datetime);
+                                    }
 
                                     // Trigger live decoding if enabled
                                     if ctx.app.jwt.live_conversion {
@@ -92,28 +120,27 @@ impl JwtPanel {
             ui.label("Algorithm");
             let mut algorithm_changed = false;
 
-            algorithm_changed |= ui
-                .radio_value(&mut ctx.app.jwt.algorithm, Algorithm::HS256, "HS256")
-                .changed();
-            algorithm_changed |= ui
-                .radio_value(&mut ctx.app.jwt.algorithm, Algorithm::HS384, "HS384")
-                .changed();
-            algorithm_changed |= ui
-                .radio_value(&mut ctx.app.jwt.algorithm, Algorithm::HS512, "HS512")
-                .changed();
-            algorithm_changed |= ui
-                .radio_value(&mut ctx.app.jwt.algorithm, Algorithm::RS256, "RS256")
-                .changed();
-            algorithm_changed |= ui
-                .radio_value(&mut ctx.app.jwt.algorithm, Algorithm::RS384, "RS384")
-                .changed();
-            algorithm_changed |= ui
-                .radio_value(&mut ctx.app.jwt.algorithm, Algorithm::RS512, "RS512")
-                .changed();
+            ComboBox::from_id_salt("jwt_algorithm")
+                .selected_text(ctx.app.jwt.algorithm.to_string())
+                .show_ui(ui, |ui| {
+                    for algorithm in Algorithm::variants() {
+                        algorithm_changed |= ui
+                            .selectable_value(
+                                &mut ctx.app.jwt.algorithm,
+                                algorithm.clone(),
+                                algorithm.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+
+            if algorithm_changed {
+                ctx.app.jwt.sync_header_algorithm();
+            }
 
             // Trigger live encoding if enabled and algorithm changed
             if ctx.app.jwt.live_conversion && algorithm_changed {
-                if let Err(e) = ctx.app.jwt.encode() {
+                if let Err(e) = ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator) {
                     append_global_error(e);
                 }
             }
@@ -132,7 +159,7 @@ impl JwtPanel {
                     .on_hover_cursor(CursorIcon::PointingHand)
                     .clicked()
                 {
-                    match ctx.app.jwt.encode() {
+                    match ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator) {
                         Ok(_) => {}
                         Err(e) => {
                             append_global_error(e);
@@ -146,7 +173,9 @@ impl JwtPanel {
                     .clicked()
                 {
                     match ctx.app.jwt.decode() {
-                        Ok(_) => {}
+                        Ok(_) => {
+                            let _ = ctx.app.jwt.validate_claims(&ctx.app.datetime);
+                        }
                         Err(e) => {
                             append_global_error(e);
                         }
@@ -188,6 +217,9 @@ impl JwtPanel {
                 }
             })),
         );
+        if let Some(kid) = &ctx.app.jwt.verified_kid {
+            ui.label(format!("with kid {}", kid));
+        }
     }
 
     fn render_decoded_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -210,7 +242,9 @@ impl JwtPanel {
                                 let response = ui.text_edit_multiline(&mut ctx.app.jwt.decoded);
 
                                 if ctx.app.jwt.live_conversion && response.changed() {
-                                    if let Err(e) = ctx.app.jwt.encode() {
+                                    if let Err(e) =
+                                        ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator)
+                                    {
                                         append_global_error(e);
                                     }
                                 }
@@ -220,43 +254,562 @@ impl JwtPanel {
             });
     }
 
+    fn render_templates_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        CollapsingHeader::new("Claim templates")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(
+                    RichText::new(
+                        "String values may use {{now}}, {{now+1h}}, {{now-30m}}, {{uuid}} and {{ulid}}, expanded on every encode",
+                    )
+                    .weak(),
+                );
+                ui.add_space(HALF_SPACE);
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut ctx.app.jwt.template_name)
+                            .desired_width(150.0)
+                            .hint_text("template name"),
+                    );
+
+                    if ui
+                        .button("💾 Save")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        match ctx.app.jwt_templates_path() {
+                            Some(path) => {
+                                if let Err(e) = ctx.app.jwt.save_template(path) {
+                                    append_global_error(e);
+                                }
+                            }
+                            None => append_global_error("no directory available to save templates"),
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Saved:");
+                    let mut selected = None;
+                    ComboBox::from_id_salt("jwt_templates")
+                        .selected_text(ctx.app.jwt.template_name.clone())
+                        .show_ui(ui, |ui| {
+                            for template in ctx.app.jwt.templates.iter() {
+                                if ui
+                                    .selectable_label(
+                                        template.name == ctx.app.jwt.template_name,
+                                        &template.name,
+                                    )
+                                    .clicked()
+                                {
+                                    selected = Some(template.name.clone());
+                                }
+                            }
+                        });
+                    if let Some(name) = selected {
+                        if let Err(e) = ctx.app.jwt.load_template(&name) {
+                            append_global_error(e);
+                        }
+                    }
+
+                    let name = ctx.app.jwt.template_name.clone();
+                    if ui
+                        .add_enabled(
+                            ctx.app.jwt.templates.get(&name).is_some(),
+                            egui::Button::new("⬆ Encode"),
+                        )
+                        .on_hover_text("Encode a fresh token from this template")
+                        .clicked()
+                    {
+                        let result = ctx
+                            .app
+                            .jwt
+                            .load_template(&name)
+                            .and_then(|_| ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator));
+                        if let Err(e) = result {
+                            append_global_error(e);
+                        }
+                    }
+
+                    if ui
+                        .add_enabled(
+                            ctx.app.jwt.templates.get(&name).is_some(),
+                            egui::Button::new("Delete"),
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = ctx.app.jwt_templates_path() {
+                            if let Err(e) = ctx.app.jwt.delete_template(&name, path) {
+                                append_global_error(e);
+                            }
+                        }
+                    }
+                });
+            });
+    }
+
     fn render_header_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         ui.vertical(|ui| {
-            ui.label("Header");
+            ui.horizontal(|ui| {
+                ui.label("Header");
+                if ui
+                    .button("⟲ Reset")
+                    .on_hover_text("Restore the default header for the selected algorithm")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    ctx.app.jwt.header = header::default_header(&ctx.app.jwt.algorithm);
+                }
+            });
             ui.add_space(HALF_SPACE);
-            let header = ctx.app.jwt.get_header().unwrap_or_default();
-            ui.text_edit_multiline(&mut header.as_str());
+            let response = ui.add(
+                egui::TextEdit::multiline(&mut ctx.app.jwt.header)
+                    .code_editor()
+                    .desired_rows(4)
+                    .hint_text(r#"{"alg": "HS256", "typ": "JWT", "kid": "..."}"#),
+            );
+
+            if ctx.app.jwt.live_conversion && response.changed() {
+                if let Err(e) = ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator) {
+                    append_global_error(e);
+                }
+            }
+        });
+    }
+
+    fn render_claims_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Claim validation");
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            let expectations = &mut ctx.app.jwt.claim_expectations;
+            ui.label("Issuer");
+            ui.add(
+                egui::TextEdit::singleline(&mut expectations.issuer)
+                    .hint_text("any")
+                    .desired_width(160.0),
+            );
+            ui.label("Audience");
+            ui.add(
+                egui::TextEdit::singleline(&mut expectations.audience)
+                    .hint_text("any")
+                    .desired_width(120.0),
+            );
+            ui.label("Leeway");
+            ui.add(DragValue::new(&mut expectations.leeway).suffix(" s"));
+
+            if ui
+                .button("Validate")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.jwt.validate_claims(&ctx.app.datetime) {
+                    append_global_error(e);
+                }
+            }
         });
+
+        if let Some(report) = &ctx.app.jwt.claim_report {
+            render_claim_report(ui, "jwt_claims", report);
+        }
+    }
+
+    fn render_findings_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Security findings");
+        ui.add_space(HALF_SPACE);
+        let Some(findings) = ctx.app.jwt.current_findings() else {
+            ui.label("Decode a token to review it");
+            return;
+        };
+        if findings.is_empty() {
+            ui.colored_label(Color32::GREEN, "✔ No findings");
+            return;
+        }
+
+        Grid::new("jwt_findings")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for finding in findings {
+                    let color = match finding.severity {
+                        Severity::Critical => Color32::RED,
+                        Severity::High => Color32::ORANGE,
+                        Severity::Medium => Color32::YELLOW,
+                        Severity::Low => Color32::LIGHT_BLUE,
+                    };
+                    ui.colored_label(color, finding.severity.to_string());
+                    ui.label(RichText::new(&finding.title).strong());
+                    ui.label(&finding.detail);
+                    ui.end_row();
+                }
+            });
     }
 
     fn render_key_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        ui.vertical(|ui| match ctx.app.jwt.algorithm {
-            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+        ui.vertical(|ui| {
+            self.render_jwe_section(ctx, ui);
+            ui.add_space(SPACE);
+
+            if ctx.app.jwt.algorithm.is_hmac() {
                 self.render_secret_section(ctx, ui);
-            }
-            Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => {
-                self.render_rsa_keys_section(ctx, ui);
+            } else {
+                self.render_key_pair_section(ctx, ui);
             }
         });
     }
 
+    fn render_jwe_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        CollapsingHeader::new("JWE encryption")
+            .default_open(ctx.app.jwt.current_jwe_header().is_some())
+            .show(ui, |ui| {
+                let settings = &mut ctx.app.jwt.jwe;
+                ui.horizontal(|ui| {
+                    ui.label("Key management");
+                    ComboBox::from_id_salt("jwe_algorithm")
+                        .selected_text(settings.algorithm.to_string())
+                        .show_ui(ui, |ui| {
+                            for algorithm in KeyManagement::variants() {
+                                ui.selectable_value(
+                                    &mut settings.algorithm,
+                                    *algorithm,
+                                    algorithm.to_string(),
+                                );
+                            }
+                        });
+                    ui.label("Content encryption");
+                    ComboBox::from_id_salt("jwe_encryption")
+                        .selected_text(settings.encryption.to_string())
+                        .show_ui(ui, |ui| {
+                            for encryption in ContentEncryption::variants() {
+                                ui.selectable_value(
+                                    &mut settings.encryption,
+                                    *encryption,
+                                    encryption.to_string(),
+                                );
+                            }
+                        });
+                });
+                ui.checkbox(&mut settings.nested, "Sign before encrypting (nested JWT)");
+                ui.add_space(HALF_SPACE);
+
+                ui.label(if settings.algorithm.is_rsa() {
+                    "RSA private key to decrypt, public key to encrypt (PEM or JWK)"
+                } else {
+                    "Shared key (hex, base64url, oct JWK or raw text)"
+                });
+                ScrollArea::vertical()
+                    .id_salt("jwe_key")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut settings.key)
+                                .code_editor()
+                                .desired_rows(4)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                ui.add_space(HALF_SPACE);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("🔒 Encrypt")
+                        .on_hover_text("Encrypt the decoded payload into the encoded field")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        if let Err(e) = ctx.app.jwt.encrypt(&ctx.app.datetime, &ctx.app.generator) {
+                            append_global_error(e);
+                        }
+                    }
+                    if ui
+                        .button("🔓 Decrypt")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        match ctx.app.jwt.decode() {
+                            Ok(_) => {
+                                let _ = ctx.app.jwt.verify();
+                                let _ = ctx.app.jwt.validate_claims(&ctx.app.datetime);
+                            }
+                            Err(e) => append_global_error(e),
+                        }
+                    }
+                });
+
+                let Some(header) = ctx.app.jwt.current_jwe_header() else {
+                    return;
+                };
+                ui.add_space(HALF_SPACE);
+                match ctx.app.jwt.current_jwe() {
+                    Some(decrypted) => ui.colored_label(
+                        Color32::GREEN,
+                        if decrypted.nested.is_some() {
+                            "✔ Decrypted, nested JWS decoded below"
+                        } else {
+                            "✔ Decrypted"
+                        },
+                    ),
+                    None => ui.colored_label(Color32::YELLOW, "Not decrypted"),
+                };
+                ui.label("Protected header");
+                ui.add(
+                    egui::TextEdit::multiline(&mut header.json.as_str())
+                        .code_editor()
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY),
+                );
+            });
+    }
+
     fn render_secret_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        ui.label("Secret");
-        if ui.text_edit_singleline(&mut ctx.app.jwt.secret).changed() {
+        let mut secret_changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Secret");
+            ComboBox::from_id_salt("jwt_secret_encoding")
+                .selected_text(ctx.app.jwt.secret_encoding.to_string())
+                .show_ui(ui, |ui| {
+                    for encoding in SecretEncoding::variants() {
+                        secret_changed |= ui
+                            .selectable_value(
+                                &mut ctx.app.jwt.secret_encoding,
+                                *encoding,
+                                encoding.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load file")
+                .on_hover_text("Use the contents of a key file as the secret")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .open_single_file()
+                    .show()
+                {
+                    match ctx.app.jwt.load_secret_file(path) {
+                        Ok(_) => secret_changed = true,
+                        Err(e) => append_global_error(e),
+                    }
+                }
+            }
+        });
+        secret_changed |= ui.text_edit_singleline(&mut ctx.app.jwt.secret).changed();
+
+        match ctx.app.jwt.secret_bytes() {
+            Ok(bytes) => {
+                if ctx.app.jwt.secret_encoding == SecretEncoding::Utf8
+                    && ctx.app.jwt.secret.trim() != ctx.app.jwt.secret
+                {
+                    ui.colored_label(
+                        Color32::YELLOW,
+                        "⚠ Leading or trailing whitespace is part of the secret",
+                    );
+                } else if ctx.app.jwt.secret_encoding != SecretEncoding::Utf8 {
+                    ui.label(RichText::new(format!("{} byte key", bytes.len())).weak());
+                }
+            }
+            Err(e) if !ctx.app.jwt.secret.is_empty() => {
+                ui.colored_label(Color32::RED, format!("❌ {}", e));
+            }
+            Err(_) => {}
+        }
+
+        if secret_changed {
             let _ = ctx.app.jwt.verify();
 
             // Trigger live encoding if enabled and secret changed
             if ctx.app.jwt.live_conversion {
-                if let Err(e) = ctx.app.jwt.encode() {
+                if let Err(e) = ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator) {
                     append_global_error(e);
                 }
             }
         }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.add_space(SPACE);
+            self.render_secret_audit(ctx, ui);
+        }
     }
 
-    fn render_rsa_keys_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_secret_audit(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ctx.app.jwt.audit.check_progress();
+
+        CollapsingHeader::new("Secret audit (wordlist)")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Tries every line of a local wordlist as the HMAC secret of the token.");
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !ctx.app.jwt.audit.is_running(),
+                            egui::Button::new("📂 Wordlist"),
+                        )
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                            .open_single_file()
+                            .show()
+                        {
+                            ctx.app.jwt.audit.wordlist = Some(path);
+                        }
+                    }
+                    match &ctx.app.jwt.audit.wordlist {
+                        Some(path) => ui.monospace(path.display().to_string()),
+                        None => ui.label("No wordlist selected"),
+                    };
+                });
+
+                ui.horizontal(|ui| {
+                    if ctx.app.jwt.audit.is_running() {
+                        if ui
+                            .button("⏹ Cancel")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            ctx.app.jwt.audit.cancel();
+                        }
+                    } else if ui
+                        .button("▶ Start audit")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        if let Err(e) = ctx.app.jwt.start_secret_audit() {
+                            append_global_error(e);
+                        }
+                    }
+                });
+
+                match &ctx.app.jwt.audit.state {
+                    AuditState::Idle => {}
+                    AuditState::Running { tried, progress } => {
+                        ui.add(
+                            egui::ProgressBar::new(*progress)
+                                .show_percentage()
+                                .text(format!("{} candidates tried", tried)),
+                        );
+                        ui.ctx().request_repaint_after(Duration::from_millis(100));
+                    }
+                    AuditState::Found { secret, tried } => {
+                        let secret = secret.clone();
+                        ui.colored_label(
+                            Color32::RED,
+                            format!("❌ Secret found after {} candidates: {}", tried, secret),
+                        );
+                        if ui
+                            .button("Use this secret")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            ctx.app.jwt.secret = secret;
+                            ctx.app.jwt.secret_encoding = SecretEncoding::Utf8;
+                            let _ = ctx.app.jwt.verify();
+                        }
+                    }
+                    AuditState::NotFound { tried } => {
+                        ui.colored_label(
+                            Color32::GREEN,
+                            format!("✔ None of the {} candidates is the secret", tried),
+                        );
+                    }
+                    AuditState::Cancelled { tried } => {
+                        ui.label(format!("Cancelled after {} candidates", tried));
+                    }
+                    AuditState::Failed(error) => {
+                        ui.colored_label(Color32::RED, format!("❌ {}", error));
+                    }
+                }
+            });
+    }
+
+    fn render_key_tools(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load public key / JWKS")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .open_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.jwt.load_public_key_file(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if let (Some(public_key), Some(private_key)) = (
+                ctx.app.crypto.input.public_key.clone(),
+                ctx.app.crypto.input.private_key.clone(),
+            ) {
+                if ui
+                    .button("From Crypto tab")
+                    .on_hover_text("Use the RSA key pair of the Crypto tab")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    ctx.app.jwt.public_key = public_key;
+                    ctx.app.jwt.private_key = private_key;
+                    let _ = ctx.app.jwt.verify();
+                }
+            }
+
+            if ui
+                .button("Export as JWK")
+                .on_hover_text("Public JWK of the PEM key with its RFC 7638 thumbprint as kid")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.jwt.export_jwk() {
+                    append_global_error(e);
+                }
+            }
+        });
+
+        let Some(export) = &ctx.app.jwt.jwk_export else {
+            return;
+        };
+        ui.add_space(HALF_SPACE);
+        ui.horizontal(|ui| {
+            ui.label("Thumbprint");
+            ui.monospace(&export.thumbprint);
+            if ui
+                .button("📋")
+                .on_hover_text("Copy JWK")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ui.ctx().copy_text(export.jwk.clone());
+            }
+        });
+        ScrollArea::vertical()
+            .id_salt("jwk_export")
+            .max_height(150.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut export.jwk.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            });
+    }
+
+    fn render_key_pair_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let key_type = ctx.app.jwt.algorithm.key_type();
+        self.render_key_tools(ctx, ui);
+        ui.add_space(SPACE);
+
         ui.vertical(|ui| {
-            ui.label("Public Key");
+            ui.label(format!("Public Key ({} PEM, JWK or JWKS)", key_type));
             let scroll_height = ui.available_height() - 30.0;
             ScrollArea::vertical()
                 .id_salt("public_key")
@@ -271,7 +824,9 @@ impl JwtPanel {
 
                         // Trigger live encoding if enabled and public key changed
                         if ctx.app.jwt.live_conversion {
-                            if let Err(e) = ctx.app.jwt.encode() {
+                            if let Err(e) =
+                                ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator)
+                            {
                                 append_global_error(e);
                             }
                         }
@@ -282,7 +837,10 @@ impl JwtPanel {
         ui.add_space(SPACE * 4.);
 
         ui.vertical(|ui| {
-            ui.label("Private Key");
+            ui.label(format!(
+                "Private Key ({} PKCS#8 PEM or private JWK)",
+                key_type
+            ));
             let scroll_height = ui.available_height() - 30.0;
             ScrollArea::vertical()
                 .id_salt("private_key")
@@ -295,7 +853,9 @@ impl JwtPanel {
                     {
                         // Trigger live encoding if enabled and private key changed
                         if ctx.app.jwt.live_conversion {
-                            if let Err(e) = ctx.app.jwt.encode() {
+                            if let Err(e) =
+                                ctx.app.jwt.encode(&ctx.app.datetime, &ctx.app.generator)
+                            {
                                 append_global_error(e);
                             }
                         }
@@ -304,3 +864,36 @@ impl JwtPanel {
         });
     }
 }
+
+/// Claim checks of a report and its verdict, shared with the PASETO panel.
+pub fn render_claim_report(ui: &mut Ui, id_salt: &str, report: &ClaimReport) {
+    ui.add_space(HALF_SPACE);
+    Grid::new(id_salt)
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            for check in &report.checks {
+                let color = match check.status {
+                    ClaimStatus::Pass => Color32::GREEN,
+                    ClaimStatus::Warn => Color32::YELLOW,
+                    ClaimStatus::Fail => Color32::RED,
+                    ClaimStatus::Info => ui.visuals().text_color(),
+                };
+                ui.colored_label(color, check.status.to_string());
+                ui.monospace(check.claim);
+                ui.label(check.value.as_deref().unwrap_or("—"));
+                ui.colored_label(color, &check.message);
+                ui.end_row();
+            }
+        });
+    ui.add_space(HALF_SPACE);
+    ui.label(
+        RichText::new(&report.verdict)
+            .strong()
+            .color(if report.acceptable {
+                Color32::GREEN
+            } else {
+                Color32::RED
+            }),
+    );
+}
@@ -1,3 +1,5 @@
+pub mod asn1_panel;
+pub mod certificate_panel;
 pub mod color_picker_panel;
 pub mod cryptography_panel;
 pub mod datetime_panel;
@@ -5,5 +7,12 @@ pub mod encoding_panel;
 pub mod error_display;
 pub mod generators_panel;
 pub mod jwt_panel;
+pub mod paseto_panel;
+pub mod protobuf_panel;
+pub mod qr_panel;
+pub mod recipe_panel;
 pub mod regex_panel;
+pub mod serialization_panel;
 pub mod top_panel;
+pub mod unicode_panel;
+pub mod url_panel;
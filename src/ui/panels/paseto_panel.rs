@@ -0,0 +1,289 @@
+use eframe::egui::{
+    self, Align, ComboBox, CursorIcon, DragValue, Layout, Resize, RichText, ScrollArea, Ui,
+};
+use eframe::epaint::Color32;
+
+use crate::core::paseto::{Protocol, Purpose};
+use crate::{
+    context::FrameCtx,
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        panels::jwt_panel::render_claim_report,
+        traits::UiPanel,
+    },
+};
+
+pub struct PasetoPanel;
+
+impl UiPanel for PasetoPanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("PASETO Encoder/Decoder");
+        ui.add_space(DOUBLE_SPACE);
+
+        ui.horizontal(|ui| {
+            self.render_main_section(ctx, ui);
+            self.render_key_section(ctx, ui);
+        });
+    }
+}
+
+impl Default for PasetoPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasetoPanel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_main_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            self.render_token_section(ctx, ui);
+            ui.add_space(HALF_SPACE);
+            self.render_protocol_selection(ctx, ui);
+            ui.add_space(SPACE);
+            self.render_action_buttons(ctx, ui);
+            ui.add_space(HALF_SPACE);
+            self.render_payload_section(ctx, ui);
+            ui.add_space(SPACE);
+            self.render_footer_section(ctx, ui);
+            ui.add_space(SPACE);
+            self.render_claims_section(ctx, ui);
+        });
+    }
+
+    fn render_token_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        Resize::default()
+            .id_salt("paseto_token_container")
+            .show(ui, |ui| {
+                ui.set_max_height(ui.available_height() * 1.0);
+                ui.label("Token");
+                ui.add_space(HALF_SPACE);
+                ScrollArea::vertical()
+                    .id_salt("paseto_token")
+                    .stick_to_bottom(false)
+                    .drag_to_scroll(false)
+                    .show(ui, |ui| {
+                        ui.with_layout(
+                            Layout::top_down(Align::Min)
+                                .with_main_justify(true)
+                                .with_cross_justify(true),
+                            |ui| {
+                                if ui.text_edit_multiline(&mut ctx.app.paseto.token).changed() {
+                                    ctx.app.paseto.verified = None;
+                                    ctx.app.paseto.claim_report = None;
+                                }
+                            },
+                        )
+                    });
+            });
+    }
+
+    fn render_protocol_selection(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Protocol");
+            ComboBox::from_id_salt("paseto_protocol")
+                .selected_text(ctx.app.paseto.protocol.to_string())
+                .show_ui(ui, |ui| {
+                    for protocol in Protocol::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.paseto.protocol,
+                            *protocol,
+                            protocol.to_string(),
+                        );
+                    }
+                });
+        });
+    }
+
+    fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui
+                .button("⬆ Encode")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.paseto.encode() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("⬇ Decode")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                match ctx.app.paseto.decode() {
+                    Ok(_) => {
+                        let _ = ctx.app.paseto.validate_claims(&ctx.app.datetime);
+                    }
+                    Err(e) => {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if ui
+                .button("✔ Verify")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                match ctx.app.paseto.verify() {
+                    Ok(_) => {
+                        let _ = ctx.app.paseto.validate_claims(&ctx.app.datetime);
+                    }
+                    Err(e) => {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.paseto.clear();
+            }
+
+            let (text, color) = match ctx.app.paseto.verified {
+                Some(true) => ("Verified ✔", Color32::GREEN),
+                Some(false) => ("Verified ✖", Color32::RED),
+                None => ("Verified", ui.visuals().text_color()),
+            };
+            ui.label(RichText::new(text).color(color));
+        });
+    }
+
+    fn render_payload_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        Resize::default()
+            .id_salt("paseto_payload_container")
+            .show(ui, |ui| {
+                ui.set_max_height(ui.available_height() * 1.0);
+                ui.label("Payload (JSON)");
+                ui.add_space(HALF_SPACE);
+                ScrollArea::vertical()
+                    .id_salt("paseto_payload")
+                    .stick_to_bottom(false)
+                    .drag_to_scroll(false)
+                    .show(ui, |ui| {
+                        ui.with_layout(
+                            Layout::top_down(Align::Min)
+                                .with_main_justify(true)
+                                .with_cross_justify(true),
+                            |ui| {
+                                ui.text_edit_multiline(&mut ctx.app.paseto.payload);
+                            },
+                        )
+                    });
+            });
+    }
+
+    fn render_footer_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        egui::Grid::new("paseto_footer")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Footer");
+                ui.add(
+                    egui::TextEdit::singleline(&mut ctx.app.paseto.footer)
+                        .code_editor()
+                        .hint_text(r#"{"kid": "..."} (authenticated, not encrypted)"#)
+                        .desired_width(360.0),
+                );
+                ui.end_row();
+
+                ui.label("Implicit assertion");
+                ui.add(
+                    egui::TextEdit::singleline(&mut ctx.app.paseto.implicit_assertion)
+                        .code_editor()
+                        .hint_text("authenticated, never included in the token")
+                        .desired_width(360.0),
+                );
+                ui.end_row();
+            });
+    }
+
+    fn render_claims_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Claim validation");
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            let expectations = &mut ctx.app.paseto.claim_expectations;
+            ui.label("Issuer");
+            ui.add(
+                egui::TextEdit::singleline(&mut expectations.issuer)
+                    .hint_text("any")
+                    .desired_width(160.0),
+            );
+            ui.label("Audience");
+            ui.add(
+                egui::TextEdit::singleline(&mut expectations.audience)
+                    .hint_text("any")
+                    .desired_width(120.0),
+            );
+            ui.label("Leeway");
+            ui.add(DragValue::new(&mut expectations.leeway).suffix(" s"));
+
+            if ui
+                .button("Validate")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.paseto.validate_claims(&ctx.app.datetime) {
+                    append_global_error(e);
+                }
+            }
+        });
+
+        if let Some(report) = &ctx.app.paseto.claim_report {
+            render_claim_report(ui, "paseto_claims", report);
+        }
+    }
+
+    fn render_key_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.vertical(|ui| match ctx.app.paseto.protocol.purpose() {
+            Purpose::Local => {
+                ui.label("Key (32 bytes: PASERK k3/k4.local, hex or base64url)");
+                ui.add(
+                    egui::TextEdit::singleline(&mut ctx.app.paseto.key)
+                        .code_editor()
+                        .desired_width(420.0),
+                );
+            }
+            Purpose::Public => {
+                let key_type = match ctx.app.paseto.protocol {
+                    Protocol::V3Public => "P-384",
+                    _ => "Ed25519",
+                };
+                ui.label(format!(
+                    "Public Key ({} PEM, PASERK, hex or base64url)",
+                    key_type
+                ));
+                ScrollArea::vertical()
+                    .id_salt("paseto_public_key")
+                    .max_height(ui.available_height() / 2.0 - 30.0)
+                    .stick_to_bottom(false)
+                    .show(ui, |ui| {
+                        ui.text_edit_multiline(&mut ctx.app.paseto.public_key);
+                    });
+
+                ui.add_space(SPACE * 4.);
+
+                ui.label(format!(
+                    "Secret Key ({} PKCS#8 PEM, PASERK, hex or base64url)",
+                    key_type
+                ));
+                ScrollArea::vertical()
+                    .id_salt("paseto_secret_key")
+                    .max_height(ui.available_height() - 30.0)
+                    .stick_to_bottom(false)
+                    .show(ui, |ui| {
+                        ui.text_edit_multiline(&mut ctx.app.paseto.secret_key);
+                    });
+            }
+        });
+    }
+}
@@ -0,0 +1,170 @@
+use eframe::egui::{
+    Align, CollapsingHeader, Color32, ComboBox, CursorIcon, Layout, Resize, RichText, ScrollArea,
+    Ui,
+};
+
+use crate::{
+    context::FrameCtx,
+    core::{encoding::input::BinaryInput, protobuf::Field},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+pub struct ProtobufPanel;
+
+impl UiPanel for ProtobufPanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("Protobuf Decoder");
+        ui.add_space(DOUBLE_SPACE);
+
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                self.render_input_section(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_action_buttons(ctx, ui);
+            });
+            ui.add_space(DOUBLE_SPACE);
+            ui.vertical(|ui| {
+                self.render_fields_section(ctx, ui);
+            });
+        });
+    }
+}
+
+impl Default for ProtobufPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProtobufPanel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_input_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        Resize::default()
+            .id_salt("protobuf_input_container")
+            .show(ui, |ui| {
+                ui.set_max_height(ui.available_height() * 1.0);
+                ui.label("Encoded message");
+                ui.add_space(HALF_SPACE);
+                ScrollArea::vertical()
+                    .id_salt("protobuf_input")
+                    .stick_to_bottom(false)
+                    .drag_to_scroll(false)
+                    .show(ui, |ui| {
+                        ui.with_layout(
+                            Layout::top_down(Align::Min)
+                                .with_main_justify(true)
+                                .with_cross_justify(true),
+                            |ui| {
+                                ui.text_edit_multiline(&mut ctx.app.protobuf.input);
+                            },
+                        )
+                    });
+            });
+    }
+
+    fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Input as:");
+            ComboBox::from_id_salt("protobuf_input_format")
+                .selected_text(ctx.app.protobuf.input_format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in BinaryInput::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.protobuf.input_format,
+                            *format,
+                            format.to_string(),
+                        );
+                    }
+                });
+        });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("🔍 Decode")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.protobuf.decode() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("From Encoding tab")
+                .on_hover_text(
+                    "Decode the encoded text of the Encoding tab with its current settings",
+                )
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                let result = ctx
+                    .app
+                    .encoding
+                    .decode_to_bytes()
+                    .and_then(|bytes| ctx.app.protobuf.decode_from(bytes));
+                if let Err(e) = result {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.protobuf.clear();
+            }
+        });
+    }
+
+    fn render_fields_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Fields");
+        ui.add_space(HALF_SPACE);
+
+        if let Some(error) = &ctx.app.protobuf.error {
+            ui.colored_label(Color32::RED, format!("❌ {}", error));
+            return;
+        }
+
+        ScrollArea::vertical()
+            .id_salt("protobuf_fields")
+            .show(ui, |ui| {
+                ui.set_min_width(400.0);
+                Self::render_fields(ui, &ctx.app.protobuf.fields);
+            });
+    }
+
+    fn render_fields(ui: &mut Ui, fields: &[Field]) {
+        for field in fields {
+            let summary = format!(
+                "#{} {} @{} ({} bytes)",
+                field.number, field.wire_type, field.offset, field.length
+            );
+
+            match field.children() {
+                Some(children) => {
+                    CollapsingHeader::new(RichText::new(summary).monospace())
+                        .id_salt(("protobuf_field", field.offset))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            Self::render_fields(ui, children);
+                        });
+                }
+                None => {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.monospace(summary);
+                        ui.label(RichText::new(field.value.to_string()).monospace().strong());
+                    });
+                }
+            }
+        }
+    }
+}
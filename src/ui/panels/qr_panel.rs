@@ -0,0 +1,360 @@
+use std::{rc::Rc, time::Duration};
+
+use eframe::egui::{
+    self, Color32, ColorImage, ComboBox, CursorIcon, Image, RichText, ScrollArea, Slider, TextEdit,
+    TextureHandle, TextureOptions, Ui,
+};
+
+use crate::{
+    context::FrameCtx,
+    core::qr::{self, QrErrorCorrection, QrTool},
+    platform::display_picker::{self, DisplayPickerExt},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+const PREVIEW_MAX_SIZE: f32 = 320.0;
+const SCAN_INTERVAL: Duration = Duration::from_millis(250);
+
+pub struct QrPanel {
+    preview: Option<TextureHandle>,
+    display_picker: Option<Rc<dyn DisplayPickerExt>>,
+    scanning: bool,
+    /// Side of the square captured around the cursor while scanning
+    scan_size: u32,
+}
+
+impl UiPanel for QrPanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("QR Code");
+        ui.add_space(DOUBLE_SPACE);
+
+        if self.preview.is_none() && ctx.app.qr.image.is_some() {
+            self.update_preview(&ctx.app.qr, ui.ctx());
+        }
+        if self.scanning {
+            self.scan_around_cursor(ctx, ui.ctx());
+        }
+
+        ui.columns(2, |columns| {
+            self.render_generate_section(ctx, &mut columns[0]);
+            self.render_decode_section(ctx, &mut columns[1]);
+        });
+
+        if let Some(error) = &ctx.app.qr.error {
+            ui.add_space(SPACE);
+            ui.colored_label(Color32::RED, format!("❌ {}", error));
+        }
+    }
+}
+
+impl Default for QrPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QrPanel {
+    pub fn new() -> Self {
+        Self {
+            preview: None,
+            display_picker: display_picker::init_display_picker(),
+            scanning: false,
+            scan_size: 400,
+        }
+    }
+
+    fn render_generate_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label(RichText::new("Generate").strong());
+        ui.add_space(HALF_SPACE);
+
+        let mut changed = false;
+        ScrollArea::vertical()
+            .id_salt("qr_text")
+            .max_height(150.0)
+            .show(ui, |ui| {
+                changed |= ui
+                    .add(
+                        TextEdit::multiline(&mut ctx.app.qr.text)
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(5)
+                            .hint_text("Text or URL to encode"),
+                    )
+                    .changed();
+            });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal_wrapped(|ui| {
+            if ui
+                .button("From Generators")
+                .on_hover_text("Encode the current generator output")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.qr.text = ctx.app.generator.output.trim().to_string();
+                changed = true;
+            }
+            if ui
+                .button("From JWT")
+                .on_hover_text("Encode the token of the JWT tab")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.qr.text = ctx.app.jwt.encoded.trim().to_string();
+                changed = true;
+            }
+        });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            ui.label("Error correction:");
+            ComboBox::from_id_salt("qr_error_correction")
+                .selected_text(ctx.app.qr.error_correction.to_string())
+                .show_ui(ui, |ui| {
+                    for level in QrErrorCorrection::variants() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut ctx.app.qr.error_correction,
+                                *level,
+                                level.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Module size:");
+            changed |= ui
+                .add(Slider::new(&mut ctx.app.qr.module_size, 1..=20).suffix(" px"))
+                .changed();
+        });
+
+        if changed {
+            // Errors are shown below the panel, no need to stack them while typing
+            if ctx.app.qr.generate().is_ok() || ctx.app.qr.image.is_none() {
+                self.update_preview(&ctx.app.qr, ui.ctx());
+            }
+        }
+
+        ui.add_space(SPACE);
+        ui.horizontal(|ui| {
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("💾 Save PNG")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .set_filename("qrcode.png")
+                    .save_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.qr.save_png(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if ui
+                .button("📋 Copy image")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                Self::copy_image(&ctx.app.qr);
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.qr.clear();
+                self.scanning = false;
+                self.update_preview(&ctx.app.qr, ui.ctx());
+            }
+        });
+
+        if let (Some(texture), Some(version)) = (&self.preview, ctx.app.qr.version) {
+            ui.add_space(SPACE);
+            let size = texture.size_vec2();
+            let scale = (PREVIEW_MAX_SIZE / size.x.max(size.y)).min(1.0);
+            ui.add(Image::new(texture).fit_to_exact_size(size * scale));
+            ui.label(format!(
+                "Version {}, {}×{} px",
+                version, size.x as u32, size.y as u32
+            ));
+        }
+    }
+
+    fn render_decode_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label(RichText::new("Decode").strong());
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal_wrapped(|ui| {
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load image")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .add_filter("Images", ["png", "jpg", "jpeg", "gif", "bmp", "webp"])
+                    .open_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.qr.decode_file(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if self.display_picker.is_some() {
+                let label = if self.scanning {
+                    "⏹ Stop scanning"
+                } else {
+                    "🔍 Scan around cursor"
+                };
+                if ui
+                    .button(label)
+                    .on_hover_text("Capture the screen around the cursor until a QR code is found")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    self.scanning = !self.scanning;
+                    if self.scanning {
+                        ctx.app.qr.decoded = None;
+                    }
+                }
+            }
+        });
+
+        if self.display_picker.is_some() {
+            ui.horizontal(|ui| {
+                ui.label("Scan area:");
+                ui.add(Slider::new(&mut self.scan_size, 100..=1000).suffix(" px"));
+            });
+        }
+
+        if self.scanning {
+            ui.add_space(HALF_SPACE);
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Move the cursor over a QR code");
+            });
+        }
+
+        let Some(decoded) = ctx.app.qr.decoded.clone() else {
+            return;
+        };
+        ui.add_space(SPACE);
+        ui.colored_label(
+            Color32::GREEN,
+            format!(
+                "✔ Version {}, error correction {}",
+                decoded.version, decoded.error_correction
+            ),
+        );
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("qr_decoded")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut decoded.text.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(5),
+                );
+            });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("📋 Copy text")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ui.ctx().copy_text(decoded.text.clone());
+            }
+            if ui
+                .button("⬅ Use as input")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.qr.text = decoded.text;
+                if ctx.app.qr.generate().is_ok() {
+                    self.update_preview(&ctx.app.qr, ui.ctx());
+                }
+            }
+        });
+    }
+
+    fn scan_around_cursor(&mut self, ctx: &mut FrameCtx<'_>, egui_ctx: &egui::Context) {
+        let Some(picker) = &self.display_picker else {
+            self.scanning = false;
+            return;
+        };
+
+        let half = self.scan_size as i32 / 2;
+        let capture = picker.get_cursor_pos().and_then(|(x, y)| {
+            picker.capture_region(x - half, y - half, self.scan_size, self.scan_size)
+        });
+        match capture {
+            Ok(image) => {
+                if let Ok(decoded) = qr::decode_image(&image) {
+                    ctx.app.qr.decoded = Some(decoded);
+                    ctx.app.qr.error = None;
+                    self.scanning = false;
+                    return;
+                }
+            }
+            Err(e) => {
+                self.scanning = false;
+                append_global_error(e);
+                return;
+            }
+        }
+        egui_ctx.request_repaint_after(SCAN_INTERVAL);
+    }
+
+    fn update_preview(&mut self, tool: &QrTool, egui_ctx: &egui::Context) {
+        self.preview = tool.image.as_ref().map(|image| {
+            let size = [image.width() as usize, image.height() as usize];
+            egui_ctx.load_texture(
+                "qr_preview",
+                ColorImage::from_rgba_unmultiplied(size, image.as_raw()),
+                TextureOptions::NEAREST,
+            )
+        });
+    }
+
+    fn copy_image(tool: &QrTool) {
+        let Some(image) = &tool.image else {
+            append_global_error("Generate a QR code first");
+            return;
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let data = arboard::ImageData {
+                width: image.width() as usize,
+                height: image.height() as usize,
+                bytes: std::borrow::Cow::Borrowed(image.as_raw()),
+            };
+            if let Err(e) =
+                arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_image(data))
+            {
+                append_global_error(format!("Failed to copy to clipboard: {}", e));
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = image;
+            append_global_error("Clipboard not supported in web version".to_string());
+        }
+    }
+}
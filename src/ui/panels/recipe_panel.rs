@@ -0,0 +1,290 @@
+use eframe::egui::{
+    Align, Color32, ComboBox, CursorIcon, Layout, Resize, RichText, ScrollArea, TextEdit, Ui,
+};
+
+use crate::{
+    context::FrameCtx,
+    core::{encoding::EncodingType, recipe::RecipeStep},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+pub struct RecipePanel {
+    step_to_add: RecipeStep,
+    selected_step: Option<usize>,
+    selected_saved: String,
+}
+
+impl UiPanel for RecipePanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("Recipe Builder");
+        ui.add_space(DOUBLE_SPACE);
+
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                self.render_input_section(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_steps_section(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_action_buttons(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_saved_section(ctx, ui);
+            });
+            ui.add_space(DOUBLE_SPACE);
+            ui.vertical(|ui| {
+                self.render_output_section(ctx, ui);
+            });
+        });
+    }
+}
+
+impl Default for RecipePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecipePanel {
+    pub fn new() -> Self {
+        Self {
+            step_to_add: RecipeStep::Decode(EncodingType::Base64),
+            selected_step: None,
+            selected_saved: String::new(),
+        }
+    }
+
+    fn run(&self, ctx: &mut FrameCtx<'_>) {
+        if let Err(e) = ctx.app.recipe.process() {
+            append_global_error(e);
+        }
+    }
+
+    fn render_input_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        Resize::default()
+            .id_salt("recipe_input_container")
+            .show(ui, |ui| {
+                ui.set_max_height(ui.available_height() * 1.0);
+                ui.label("Input");
+                ui.add_space(HALF_SPACE);
+                ScrollArea::vertical()
+                    .id_salt("recipe_input")
+                    .stick_to_bottom(false)
+                    .drag_to_scroll(false)
+                    .show(ui, |ui| {
+                        ui.with_layout(
+                            Layout::top_down(Align::Min)
+                                .with_main_justify(true)
+                                .with_cross_justify(true),
+                            |ui| {
+                                let response = ui.text_edit_multiline(&mut ctx.app.recipe.input);
+
+                                if ctx.app.recipe.live_conversion && response.changed() {
+                                    let _ = ctx.app.recipe.process();
+                                }
+                            },
+                        )
+                    });
+            });
+    }
+
+    fn render_steps_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("Steps");
+        ui.add_space(HALF_SPACE);
+
+        let mut remove = None;
+        let mut swap = None;
+        let step_count = ctx.app.recipe.recipe.steps.len();
+
+        for (i, step) in ctx.app.recipe.recipe.steps.iter().enumerate() {
+            let failed = ctx
+                .app
+                .recipe
+                .outputs
+                .get(i)
+                .is_some_and(|o| o.error.is_some());
+
+            ui.horizontal(|ui| {
+                let label = RichText::new(format!("{}. {}", i + 1, step));
+                let label = if failed {
+                    label.color(Color32::RED)
+                } else {
+                    label
+                };
+                if ui
+                    .selectable_label(self.selected_step == Some(i), label)
+                    .on_hover_text("Inspect the output of this step")
+                    .clicked()
+                {
+                    self.selected_step = Some(i);
+                }
+                if ui
+                    .add_enabled(i > 0, eframe::egui::Button::new("⬆"))
+                    .clicked()
+                {
+                    swap = Some((i, i - 1));
+                }
+                if ui
+                    .add_enabled(i + 1 < step_count, eframe::egui::Button::new("⬇"))
+                    .clicked()
+                {
+                    swap = Some((i, i + 1));
+                }
+                if ui.button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+
+        if let Some((from, to)) = swap {
+            ctx.app.recipe.move_step(from, to);
+            self.selected_step = Some(to);
+        }
+        if let Some(i) = remove {
+            ctx.app.recipe.remove_step(i);
+            self.selected_step = None;
+        }
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("recipe_step_to_add")
+                .selected_text(self.step_to_add.to_string())
+                .show_ui(ui, |ui| {
+                    for step in RecipeStep::catalog() {
+                        let label = step.to_string();
+                        ui.selectable_value(&mut self.step_to_add, step, label);
+                    }
+                });
+
+            if ui
+                .button("➕ Add step")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.recipe.add_step(self.step_to_add.clone());
+                if ctx.app.recipe.live_conversion {
+                    let _ = ctx.app.recipe.process();
+                }
+            }
+        });
+    }
+
+    fn render_action_buttons(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut ctx.app.recipe.live_conversion, "Live conversion");
+
+            if ui
+                .button("▶ Run")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                self.run(ctx);
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.recipe.clear();
+                self.selected_step = None;
+            }
+        });
+    }
+
+    fn render_saved_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.add(
+                TextEdit::singleline(&mut ctx.app.recipe.recipe.name)
+                    .desired_width(150.0)
+                    .hint_text("recipe name"),
+            );
+
+            if ui
+                .button("💾 Save")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                match ctx.app.recipes_path() {
+                    Some(path) => {
+                        if let Err(e) = ctx.app.recipe.save_current(path) {
+                            append_global_error(e);
+                        }
+                    }
+                    None => append_global_error("no directory available to save recipes"),
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Saved:");
+            ComboBox::from_id_salt("recipe_saved")
+                .selected_text(self.selected_saved.clone())
+                .show_ui(ui, |ui| {
+                    for recipe in ctx.app.recipe.saved.iter() {
+                        ui.selectable_value(
+                            &mut self.selected_saved,
+                            recipe.name.clone(),
+                            &recipe.name,
+                        );
+                    }
+                });
+
+            let has_selection = !self.selected_saved.is_empty();
+            if ui
+                .add_enabled(has_selection, eframe::egui::Button::new("Load"))
+                .clicked()
+            {
+                match ctx.app.recipe.load_saved(&self.selected_saved) {
+                    Ok(_) => {
+                        self.selected_step = None;
+                        self.run(ctx);
+                    }
+                    Err(e) => append_global_error(e),
+                }
+            }
+
+            if ui
+                .add_enabled(has_selection, eframe::egui::Button::new("Delete"))
+                .clicked()
+            {
+                if let Some(path) = ctx.app.recipes_path() {
+                    if let Err(e) = ctx.app.recipe.delete_saved(&self.selected_saved, path) {
+                        append_global_error(e);
+                    }
+                }
+                self.selected_saved.clear();
+            }
+        });
+    }
+
+    fn render_output_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        if let Some(i) = self.selected_step {
+            if let Some(output) = ctx.app.recipe.outputs.get(i) {
+                ui.label(format!("Output of step {}", i + 1));
+                ui.add_space(HALF_SPACE);
+                let mut text = output.as_text();
+                ScrollArea::vertical()
+                    .id_salt("recipe_step_output")
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.add(TextEdit::multiline(&mut text).code_editor());
+                    });
+                ui.add_space(SPACE);
+            }
+        }
+
+        ui.label("Output");
+        ui.add_space(HALF_SPACE);
+        let mut output = ctx.app.recipe.output_text();
+        ScrollArea::vertical()
+            .id_salt("recipe_output")
+            .stick_to_bottom(false)
+            .drag_to_scroll(false)
+            .show(ui, |ui| {
+                ui.add(TextEdit::multiline(&mut output).code_editor());
+            });
+    }
+}
@@ -1,9 +1,17 @@
+use std::ops::Range;
+
 use eframe::egui::{
-    Align, Color32, CursorIcon, Layout, Resize, RichText, ScrollArea, TextEdit, Ui,
+    text::{LayoutJob, TextFormat},
+    Align, CollapsingHeader, Color32, ComboBox, CursorIcon, DragValue, FontSelection, Grid, Layout,
+    Resize, RichText, ScrollArea, Stroke, TextEdit, Ui,
 };
 
 use crate::{
     context::FrameCtx,
+    core::regex::{
+        explain::{self, ExplainError, ExplainNode},
+        RegexMatchResult, RegexMode, Span,
+    },
     types::error::append_global_error,
     ui::{
         components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
@@ -11,7 +19,175 @@ use crate::{
     },
 };
 
-pub struct RegexPanel;
+pub struct RegexPanel {
+    /// Pattern range of the explanation node under the pointer
+    hovered_span: Option<Range<usize>>,
+    explanation: ExplanationCache,
+}
+
+/// Explanation of the last pattern seen, so it is parsed once per edit rather than every frame.
+#[derive(Default)]
+struct ExplanationCache {
+    pattern: String,
+    explanation: Option<Result<Vec<ExplainNode>, ExplainError>>,
+}
+
+impl ExplanationCache {
+    fn get(&mut self, pattern: &str) -> &Result<Vec<ExplainNode>, ExplainError> {
+        if self.explanation.is_none() || self.pattern != pattern {
+            self.pattern = pattern.to_string();
+            self.explanation = Some(explain::explain(pattern));
+        }
+        self.explanation
+            .as_ref()
+            .expect("explanation is computed above")
+    }
+}
+
+/// Background of a whole match in the highlighted text.
+fn match_color() -> Color32 {
+    Color32::from_rgba_unmultiplied(255, 210, 0, 50)
+}
+
+/// Background of capture group `index`, cycling through distinct hues.
+fn group_color(index: usize) -> Color32 {
+    const HUES: [(u8, u8, u8); 6] = [
+        (0, 170, 255),
+        (255, 90, 90),
+        (80, 220, 100),
+        (200, 110, 255),
+        (255, 150, 0),
+        (0, 210, 200),
+    ];
+    let (r, g, b) = HUES[(index - 1) % HUES.len()];
+    Color32::from_rgba_unmultiplied(r, g, b, 90)
+}
+
+/// Byte ranges to highlight and their colors, groups after the match they belong to so that
+/// the innermost highlight wins.
+fn highlight_ranges(result: &RegexMatchResult) -> Vec<(usize, usize, Color32)> {
+    let mut ranges = Vec::new();
+    for regex_match in &result.matches {
+        ranges.push((regex_match.start, regex_match.end, match_color()));
+        for group in &regex_match.groups {
+            if let Some(span) = group.span {
+                ranges.push((span.start.byte, span.end.byte, group_color(group.index)));
+            }
+        }
+    }
+    ranges
+}
+
+fn highlight_job(ui: &Ui, text: &str, ranges: &[(usize, usize, Color32)]) -> LayoutJob {
+    let format = TextFormat {
+        font_id: FontSelection::default().resolve(ui.style()),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+
+    let mut boundaries: Vec<usize> = ranges
+        .iter()
+        .flat_map(|(start, end, _)| [*start, *end])
+        .chain([0, text.len()])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut job = LayoutJob::default();
+    for segment in boundaries.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let background = ranges
+            .iter()
+            .rev()
+            .find(|(from, to, _)| *from <= start && end <= *to)
+            .map_or(Color32::TRANSPARENT, |(_, _, color)| *color);
+        job.append(
+            &text[start..end],
+            0.0,
+            TextFormat {
+                background,
+                ..format.clone()
+            },
+        );
+    }
+    job
+}
+
+/// Range of `text` to mark for `span`, widened to one character when it is empty, as for an
+/// unexpected end of pattern.
+fn visible_range(text: &str, span: &Range<usize>) -> Option<Range<usize>> {
+    if span.end > text.len()
+        || !text.is_char_boundary(span.start)
+        || !text.is_char_boundary(span.end)
+    {
+        return None;
+    }
+    if !span.is_empty() {
+        return Some(span.clone());
+    }
+    match text[span.end..].chars().next() {
+        Some(c) => Some(span.start..span.end + c.len_utf8()),
+        None => text[..span.start]
+            .chars()
+            .next_back()
+            .map(|c| span.start - c.len_utf8()..span.start),
+    }
+}
+
+/// Pattern with the hovered explanation node highlighted and syntax errors underlined.
+fn pattern_job(
+    ui: &Ui,
+    pattern: &str,
+    explanation: &Result<Vec<ExplainNode>, ExplainError>,
+    hovered: Option<&Range<usize>>,
+) -> LayoutJob {
+    let format = TextFormat {
+        font_id: FontSelection::default().resolve(ui.style()),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let hovered = hovered.and_then(|span| visible_range(pattern, span));
+    let errors: Vec<Range<usize>> = match explanation {
+        Ok(_) => Vec::new(),
+        Err(e) => [Some(&e.span), e.auxiliary_span.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|span| visible_range(pattern, span))
+            .collect(),
+    };
+
+    let mut boundaries: Vec<usize> = errors
+        .iter()
+        .chain(&hovered)
+        .flat_map(|span| [span.start, span.end])
+        .chain([0, pattern.len()])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut job = LayoutJob::default();
+    for segment in boundaries.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let covers = |span: &Range<usize>| span.start <= start && end <= span.end;
+        let mut segment_format = format.clone();
+        if hovered.as_ref().is_some_and(covers) {
+            segment_format.background = ui.visuals().selection.bg_fill;
+        }
+        if errors.iter().any(covers) {
+            segment_format.underline = Stroke::new(2.0, Color32::RED);
+        }
+        job.append(&pattern[start..end], 0.0, segment_format);
+    }
+    job
+}
+
+fn describe_span(span: &Span) -> [String; 3] {
+    [
+        format!("{}–{}", span.start.byte, span.end.byte),
+        format!("{}–{}", span.start.char, span.end.char),
+        format!("{}:{}", span.start.line, span.start.column),
+    ]
+}
 
 impl UiPanel for RegexPanel {
     fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -32,15 +208,22 @@ impl Default for RegexPanel {
 
 impl RegexPanel {
     pub fn new() -> Self {
-        Self
+        Self {
+            hovered_span: None,
+            explanation: ExplanationCache::default(),
+        }
     }
 
-    fn render_main_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+    fn render_main_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         ui.vertical(|ui| {
             self.render_pattern_section(ctx, ui);
+            ui.add_space(HALF_SPACE);
+            self.render_explanation_section(ctx, ui);
             ui.add_space(SPACE);
             self.render_options_section(ctx, ui);
             ui.add_space(SPACE);
+            self.render_mode_section(ctx, ui);
+            ui.add_space(SPACE);
             self.render_action_buttons(ctx, ui);
             ui.add_space(DOUBLE_SPACE);
             self.render_text_section(ctx, ui);
@@ -49,7 +232,7 @@ impl RegexPanel {
         });
     }
 
-    fn render_pattern_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+    fn render_pattern_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         Resize::default()
             .id_salt("regex_pattern_container")
             .show(ui, |ui| {
@@ -69,8 +252,21 @@ impl RegexPanel {
                                 .with_main_justify(true)
                                 .with_cross_justify(true),
                             |ui| {
+                                let hovered = self.hovered_span.clone();
+                                let explanation = &mut self.explanation;
+                                let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+                                    let mut job = pattern_job(
+                                        ui,
+                                        text,
+                                        explanation.get(text),
+                                        hovered.as_ref(),
+                                    );
+                                    job.wrap.max_width = wrap_width;
+                                    ui.fonts(|fonts| fonts.layout_job(job))
+                                };
                                 let input_edit = TextEdit::multiline(&mut ctx.app.regex.pattern)
-                                    .desired_width(ui.available_width());
+                                    .desired_width(ui.available_width())
+                                    .layouter(&mut layouter);
 
                                 ui.add(input_edit);
                             },
@@ -79,6 +275,35 @@ impl RegexPanel {
             });
     }
 
+    fn render_explanation_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let pattern = ctx.app.regex.pattern.as_str();
+        let nodes = match self.explanation.get(pattern) {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                ui.colored_label(Color32::RED, format!("❌ Syntax error: {}", e));
+                self.hovered_span = None;
+                return;
+            }
+        };
+
+        let mut hovered = None;
+        CollapsingHeader::new("🧩 Explanation")
+            .default_open(false)
+            .show(ui, |ui| {
+                if nodes.is_empty() {
+                    ui.label(RichText::new("Empty pattern").weak());
+                }
+                for node in nodes {
+                    render_explain_node(ui, pattern, node, 0, &mut hovered);
+                }
+            });
+
+        if hovered != self.hovered_span {
+            self.hovered_span = hovered;
+            ui.ctx().request_repaint();
+        }
+    }
+
     fn render_text_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         Resize::default()
             .id_salt("regex_text_container")
@@ -99,7 +324,29 @@ impl RegexPanel {
                                 .with_main_justify(true)
                                 .with_cross_justify(true),
                             |ui| {
-                                let response = ui.text_edit_multiline(&mut ctx.app.regex.text);
+                                // Highlights only apply to the text they were computed for
+                                let highlighted = ctx
+                                    .app
+                                    .regex
+                                    .result
+                                    .as_ref()
+                                    .filter(|result| result.text == ctx.app.regex.text)
+                                    .map(|result| (result.text.clone(), highlight_ranges(result)));
+                                let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+                                    let ranges = match &highlighted {
+                                        Some((source, ranges)) if source == text => {
+                                            ranges.as_slice()
+                                        }
+                                        _ => &[],
+                                    };
+                                    let mut job = highlight_job(ui, text, ranges);
+                                    job.wrap.max_width = wrap_width;
+                                    ui.fonts(|fonts| fonts.layout_job(job))
+                                };
+                                let response = ui.add(
+                                    TextEdit::multiline(&mut ctx.app.regex.text)
+                                        .layouter(&mut layouter),
+                                );
 
                                 // Auto-process when text changes if pattern is not empty
                                 if response.changed() && !ctx.app.regex.pattern.is_empty() {
@@ -144,6 +391,62 @@ impl RegexPanel {
         });
     }
 
+    fn render_mode_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let regex = &mut ctx.app.regex;
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Mode");
+            ComboBox::from_id_salt("regex_mode")
+                .selected_text(regex.mode.to_string())
+                .show_ui(ui, |ui| {
+                    for mode in RegexMode::variants() {
+                        changed |= ui
+                            .selectable_value(&mut regex.mode, *mode, mode.to_string())
+                            .changed();
+                    }
+                });
+
+            match regex.mode {
+                RegexMode::Match => {}
+                RegexMode::Replace => {
+                    ui.label("Replacement");
+                    changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut regex.replacement)
+                                .code_editor()
+                                .hint_text("$1, ${name}, $$")
+                                .desired_width(240.0),
+                        )
+                        .changed();
+                    changed |= ui.checkbox(&mut regex.replace_all, "Replace all").changed();
+                    ui.add_enabled_ui(regex.replace_all, |ui| {
+                        ui.label("Limit");
+                        changed |= ui
+                            .add(DragValue::new(&mut regex.limit))
+                            .on_hover_text("0 replaces every match")
+                            .changed();
+                    });
+                }
+                RegexMode::Split => {
+                    ui.label("Max fields");
+                    changed |= ui
+                        .add(DragValue::new(&mut regex.limit))
+                        .on_hover_text("0 splits at every match")
+                        .changed();
+                }
+            }
+        });
+
+        // Live preview when the mode or its settings change
+        if changed && !regex.pattern.is_empty() {
+            if let Err(e) = regex.process() {
+                // Error is already stored in the result, no need to display here
+                let _ = e;
+            }
+        }
+    }
+
     fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         ui.horizontal(|ui| {
             if ui
@@ -197,41 +500,188 @@ impl RegexPanel {
                             return;
                         }
                     }
-                    if !result.matches.is_empty() {
-                        for (i, regex_match) in result.matches.iter().enumerate() {
-                            ui.group(|ui| {
-                                // dynamically set width based on content
-                                ui.set_width_range(
-                                    ui.available_width() * 0.4..=ui.available_width() * 0.9,
-                                );
-                                ui.horizontal(|ui| {
-                                    ui.label(format!("Match {}:", i + 1));
-                                    ui.label(format!(
-                                        "Position {}-{}",
-                                        regex_match.start, regex_match.end
-                                    ));
-                                });
+                    match ctx.app.regex.mode {
+                        RegexMode::Match => {}
+                        RegexMode::Replace => {
+                            self.render_replace_results(ctx, ui);
+                            return;
+                        }
+                        RegexMode::Split => {
+                            self.render_split_results(ctx, ui);
+                            return;
+                        }
+                    }
+                    for (i, regex_match) in result.matches.iter().enumerate() {
+                        ui.group(|ui| {
+                            // dynamically set width based on content
+                            ui.set_width_range(
+                                ui.available_width() * 0.4..=ui.available_width() * 0.9,
+                            );
+                            ui.horizontal(|ui| {
+                                ui.label(format!("Match {}:", i + 1));
+                                ui.label(RichText::new(&regex_match.text).code());
+                            });
+
+                            Grid::new(("regex_match_groups", i))
+                                .num_columns(7)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for header in
+                                        ["", "Group", "Name", "Text", "Bytes", "Chars", "Line:Col"]
+                                    {
+                                        ui.label(RichText::new(header).strong());
+                                    }
+                                    ui.end_row();
 
-                                ui.horizontal(|ui| {
-                                    ui.label("Text:");
+                                    ui.colored_label(match_color().to_opaque(), "■");
+                                    ui.label("0");
+                                    ui.label("match");
                                     ui.label(RichText::new(&regex_match.text).code());
-                                });
+                                    for cell in describe_span(&regex_match.span) {
+                                        ui.label(cell);
+                                    }
+                                    ui.end_row();
 
-                                if !regex_match.groups.is_empty() {
-                                    ui.label("Capture Groups:");
-                                    for (group_idx, group) in regex_match.groups.iter().enumerate()
-                                    {
-                                        ui.horizontal(|ui| {
-                                            ui.label(format!("  Group {}:", group_idx + 1));
-                                            ui.label(RichText::new(group).code());
-                                        });
+                                    for group in &regex_match.groups {
+                                        ui.colored_label(group_color(group.index).to_opaque(), "■");
+                                        ui.label(group.index.to_string());
+                                        ui.label(group.name.as_deref().unwrap_or("—"));
+                                        match (&group.text, &group.span) {
+                                            (Some(text), Some(span)) => {
+                                                ui.label(RichText::new(text).code());
+                                                for cell in describe_span(span) {
+                                                    ui.label(cell);
+                                                }
+                                            }
+                                            _ => {
+                                                ui.label(
+                                                    RichText::new("did not participate").weak(),
+                                                );
+                                            }
+                                        }
+                                        ui.end_row();
                                     }
-                                }
-                            });
-                            ui.add_space(HALF_SPACE);
-                        }
+                                });
+                        });
+                        ui.add_space(HALF_SPACE);
+                    }
+                }
+            });
+    }
+
+    fn render_replace_results(&self, ctx: &FrameCtx<'_>, ui: &mut Ui) {
+        let Some(replaced) = &ctx.app.regex.replaced else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Preview");
+            if ui
+                .button("📋 Copy")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ui.ctx().copy_text(replaced.output.clone());
+            }
+        });
+        ui.group(|ui| {
+            ui.set_width(ui.available_width() * 0.9);
+            ui.label(RichText::new(&replaced.output).code());
+        });
+        ui.add_space(SPACE);
+
+        ui.label(format!(
+            "{} replacement{}",
+            replaced.replacements.len(),
+            if replaced.replacements.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ));
+        ui.add_space(HALF_SPACE);
+        Grid::new("regex_replacements")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["#", "Bytes", "Before", "After"] {
+                    ui.label(RichText::new(header).strong());
+                }
+                ui.end_row();
+
+                for (i, replacement) in replaced.replacements.iter().enumerate() {
+                    ui.label((i + 1).to_string());
+                    ui.label(format!("{}–{}", replacement.start, replacement.end));
+                    ui.label(RichText::new(&replacement.before).code());
+                    ui.label(
+                        RichText::new(&replacement.after)
+                            .code()
+                            .color(Color32::GREEN),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn render_split_results(&self, ctx: &FrameCtx<'_>, ui: &mut Ui) {
+        let Some(fields) = &ctx.app.regex.fields else {
+            return;
+        };
+
+        ui.label(format!(
+            "{} field{}",
+            fields.len(),
+            if fields.len() == 1 { "" } else { "s" }
+        ));
+        ui.add_space(HALF_SPACE);
+        Grid::new("regex_split_fields")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (i, field) in fields.iter().enumerate() {
+                    ui.label((i + 1).to_string());
+                    if field.is_empty() {
+                        ui.label(RichText::new("(empty)").weak());
+                    } else {
+                        ui.label(RichText::new(field).code());
                     }
+                    ui.end_row();
                 }
             });
     }
 }
+
+/// Renders `node` and its children, recording the span of the node under the pointer.
+fn render_explain_node(
+    ui: &mut Ui,
+    pattern: &str,
+    node: &ExplainNode,
+    depth: usize,
+    hovered: &mut Option<Range<usize>>,
+) {
+    let source = pattern.get(node.span.clone()).unwrap_or_default();
+    if node.children.is_empty() {
+        let response = ui
+            .horizontal(|ui| {
+                ui.label(RichText::new(source).code());
+                ui.label(&node.description);
+            })
+            .response;
+        if response.hovered() {
+            *hovered = Some(node.span.clone());
+        }
+        return;
+    }
+
+    let response = CollapsingHeader::new(&node.description)
+        .id_salt(("regex_explain", node.span.start, node.span.end, depth))
+        .default_open(true)
+        .show(ui, |ui| {
+            for child in &node.children {
+                render_explain_node(ui, pattern, child, depth + 1, hovered);
+            }
+        });
+    if response.header_response.hovered() {
+        *hovered = Some(node.span.clone());
+    }
+}
@@ -0,0 +1,178 @@
+use eframe::egui::{Color32, ComboBox, CursorIcon, ScrollArea, TextEdit, Ui};
+
+use crate::{
+    context::FrameCtx,
+    core::{encoding::input::BinaryInput, serialization::BinaryFormat},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+pub struct SerializationPanel;
+
+impl UiPanel for SerializationPanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("Binary Serialization");
+        ui.add_space(DOUBLE_SPACE);
+
+        self.render_options(ctx, ui);
+        ui.add_space(SPACE);
+
+        ui.columns(2, |columns| {
+            self.render_binary_section(ctx, &mut columns[0]);
+            self.render_json_section(ctx, &mut columns[1]);
+        });
+
+        if let Some(error) = &ctx.app.serialization.error {
+            ui.add_space(SPACE);
+            ui.colored_label(Color32::RED, format!("❌ {}", error));
+        }
+    }
+}
+
+impl Default for SerializationPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SerializationPanel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_options(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Format:");
+            ComboBox::from_id_salt("serialization_format")
+                .selected_text(ctx.app.serialization.format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in BinaryFormat::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.serialization.format,
+                            *format,
+                            format.to_string(),
+                        );
+                    }
+                });
+
+            ui.add_space(SPACE);
+            ui.label("Input as:");
+            ComboBox::from_id_salt("serialization_input_format")
+                .selected_text(ctx.app.serialization.input_format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in BinaryInput::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.serialization.input_format,
+                            *format,
+                            format.to_string(),
+                        );
+                    }
+                });
+        });
+    }
+
+    fn render_binary_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label(format!("{} (hex or Base64)", ctx.app.serialization.format));
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("serialization_input")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.serialization.input)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(12),
+                );
+            });
+        ui.add_space(HALF_SPACE);
+
+        ui.horizontal_wrapped(|ui| {
+            if ui
+                .button("Decode ➡")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.serialization.decode() {
+                    append_global_error(e);
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("📂 Load file")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .open_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.serialization.load_file(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("💾 Save file")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Ok(Some(path)) = native_dialog::DialogBuilder::file()
+                    .set_filename(format!("data.{}", ctx.app.serialization.format.extension()))
+                    .save_single_file()
+                    .show()
+                {
+                    if let Err(e) = ctx.app.serialization.save_file(path) {
+                        append_global_error(e);
+                    }
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.serialization.clear();
+            }
+        });
+    }
+
+    fn render_json_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("JSON")
+            .on_hover_text(
+                "Values without a JSON type are annotated: {\"$binary\": base64}, \
+                 {\"$ext\": type, \"data\": base64}, {\"$tag\": n, \"value\": ...}, \
+                 {\"$bigint\": \"...\"} and {\"$float\": \"NaN\"}. BSON uses MongoDB Extended JSON.",
+            );
+        ui.add_space(HALF_SPACE);
+        ScrollArea::vertical()
+            .id_salt("serialization_json")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.serialization.json)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(12),
+                );
+            });
+        ui.add_space(HALF_SPACE);
+
+        if ui
+            .button("⬅ Encode")
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .clicked()
+        {
+            if let Err(e) = ctx.app.serialization.encode() {
+                append_global_error(e);
+            }
+        }
+    }
+}
@@ -78,6 +78,14 @@ impl TopPanel {
                 ctx.app.central_panel_tab = CentralPanelTab::Jwt;
             }
         );
+        add_button_if!(
+            "PASETO",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Paseto),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Paseto;
+                ctx.app.sidepanel.show = false;
+            }
+        );
         add_button_if!(
             "Encoding",
             matches!(ctx.app.central_panel_tab, CentralPanelTab::Encoding),
@@ -86,6 +94,38 @@ impl TopPanel {
                 ctx.app.sidepanel.show = false;
             }
         );
+        add_button_if!(
+            "URL",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Url),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Url;
+                ctx.app.sidepanel.show = false;
+            }
+        );
+        add_button_if!(
+            "Protobuf",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Protobuf),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Protobuf;
+                ctx.app.sidepanel.show = false;
+            }
+        );
+        add_button_if!(
+            "MsgPack/CBOR",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Serialization),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Serialization;
+                ctx.app.sidepanel.show = false;
+            }
+        );
+        add_button_if!(
+            "Recipes",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Recipe),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Recipe;
+                ctx.app.sidepanel.show = false;
+            }
+        );
         add_button_if!(
             "Regex",
             matches!(ctx.app.central_panel_tab, CentralPanelTab::Regex),
@@ -94,6 +134,14 @@ impl TopPanel {
                 ctx.app.sidepanel.show = false;
             }
         );
+        add_button_if!(
+            "Unicode",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Unicode),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Unicode;
+                ctx.app.sidepanel.show = false;
+            }
+        );
         add_button_if!(
             "Generators",
             matches!(ctx.app.central_panel_tab, CentralPanelTab::Generators),
@@ -102,6 +150,14 @@ impl TopPanel {
                 ctx.app.sidepanel.show = false;
             }
         );
+        add_button_if!(
+            "QR Code",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Qr),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Qr;
+                ctx.app.sidepanel.show = false;
+            }
+        );
         add_button_if!(
             "Cryptography",
             matches!(ctx.app.central_panel_tab, CentralPanelTab::Cryptography),
@@ -110,6 +166,22 @@ impl TopPanel {
                 ctx.app.sidepanel.show = false;
             }
         );
+        add_button_if!(
+            "ASN.1",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Asn1),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Asn1;
+                ctx.app.sidepanel.show = false;
+            }
+        );
+        add_button_if!(
+            "Certificates",
+            matches!(ctx.app.central_panel_tab, CentralPanelTab::Certificates),
+            {
+                ctx.app.central_panel_tab = CentralPanelTab::Certificates;
+                ctx.app.sidepanel.show = false;
+            }
+        );
     }
 
     pub fn render_right_side_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) -> bool {
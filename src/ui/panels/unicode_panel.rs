@@ -0,0 +1,278 @@
+use eframe::egui::{
+    Align, Button, Color32, ComboBox, CursorIcon, Grid, Layout, Resize, RichText, ScrollArea,
+    TextEdit, Ui,
+};
+
+use crate::{
+    context::FrameCtx,
+    core::unicode::{ByteEncoding, NormalizationForm},
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+pub struct UnicodePanel;
+
+impl UiPanel for UnicodePanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("Unicode Inspector");
+        ui.add_space(DOUBLE_SPACE);
+
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                self.render_input_section(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_action_buttons(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_normalization_section(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_bytes_section(ctx, ui);
+            });
+            ui.add_space(DOUBLE_SPACE);
+            ui.vertical(|ui| {
+                self.render_summary_section(ctx, ui);
+                ui.add_space(SPACE);
+                self.render_graphemes_table(ctx, ui);
+            });
+        });
+    }
+}
+
+impl Default for UnicodePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnicodePanel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_input_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        Resize::default()
+            .id_salt("unicode_input_container")
+            .show(ui, |ui| {
+                ui.set_max_height(ui.available_height() * 1.0);
+                ui.label("Text");
+                ui.add_space(HALF_SPACE);
+                ScrollArea::vertical()
+                    .id_salt("unicode_input")
+                    .stick_to_bottom(false)
+                    .drag_to_scroll(false)
+                    .show(ui, |ui| {
+                        ui.with_layout(
+                            Layout::top_down(Align::Min)
+                                .with_main_justify(true)
+                                .with_cross_justify(true),
+                            |ui| {
+                                let response = ui.text_edit_multiline(&mut ctx.app.unicode.input);
+
+                                if ctx.app.unicode.live_conversion && response.changed() {
+                                    ctx.app.unicode.inspect();
+                                }
+                            },
+                        )
+                    });
+            });
+    }
+
+    fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut ctx.app.unicode.live_conversion, "Live inspection");
+
+            if ui
+                .button("🔍 Inspect")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.unicode.inspect();
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.unicode.clear();
+            }
+        });
+    }
+
+    fn render_normalization_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Normalization:");
+            ComboBox::from_id_salt("unicode_normalization")
+                .selected_text(ctx.app.unicode.normalization.to_string())
+                .show_ui(ui, |ui| {
+                    for form in NormalizationForm::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.unicode.normalization,
+                            *form,
+                            form.to_string(),
+                        );
+                    }
+                });
+
+            if ui
+                .button("Normalize")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.unicode.normalize();
+            }
+
+            if !ctx.app.unicode.input.is_empty() {
+                if ctx.app.unicode.is_normalized() {
+                    ui.label(RichText::new("already normalized").color(Color32::GREEN));
+                } else {
+                    ui.label(RichText::new("not normalized").color(Color32::YELLOW));
+                }
+            }
+        });
+    }
+
+    fn render_bytes_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Bytes as:");
+            ComboBox::from_id_salt("unicode_byte_encoding")
+                .selected_text(ctx.app.unicode.byte_encoding.to_string())
+                .show_ui(ui, |ui| {
+                    for encoding in ByteEncoding::variants() {
+                        ui.selectable_value(
+                            &mut ctx.app.unicode.byte_encoding,
+                            *encoding,
+                            encoding.to_string(),
+                        );
+                    }
+                });
+
+            if ui
+                .button("Text → Bytes")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.unicode.text_to_bytes() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("Bytes → Text")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.unicode.bytes_to_text() {
+                    append_global_error(e);
+                }
+            }
+        });
+        ui.add_space(HALF_SPACE);
+
+        ScrollArea::vertical()
+            .id_salt("unicode_bytes")
+            .max_height(150.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut ctx.app.unicode.bytes)
+                        .code_editor()
+                        .hint_text("hex bytes, e.g. C3 A9"),
+                );
+            });
+    }
+
+    fn render_summary_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let unicode = &ctx.app.unicode;
+        ui.label(format!(
+            "{} graphemes, {} code points, {} UTF-8 bytes, {} UTF-16 units",
+            unicode.graphemes.len(),
+            unicode.code_point_count(),
+            unicode.input.len(),
+            unicode.input.encode_utf16().count()
+        ));
+
+        let flagged = unicode.flagged_count();
+        if flagged > 0 {
+            ui.label(
+                RichText::new(format!("⚠ {} suspicious code points", flagged))
+                    .color(Color32::YELLOW),
+            );
+        }
+
+        if !unicode.input.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Skeleton:")
+                    .on_hover_text("Visually confusable strings share the same skeleton");
+                ui.monospace(unicode.skeleton());
+            });
+        }
+    }
+
+    fn render_graphemes_table(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ScrollArea::both()
+            .id_salt("unicode_graphemes")
+            .show(ui, |ui| {
+                Grid::new("unicode_graphemes_grid")
+                    .striped(true)
+                    .spacing([DOUBLE_SPACE, HALF_SPACE])
+                    .show(ui, |ui| {
+                        for header in [
+                            "Offset",
+                            "Grapheme",
+                            "UTF-8",
+                            "UTF-16",
+                            "Code point",
+                            "Cat",
+                            "Name",
+                            "Flags",
+                        ] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for grapheme in &ctx.app.unicode.graphemes {
+                            let color = if grapheme.is_flagged() {
+                                Color32::YELLOW
+                            } else {
+                                ui.visuals().text_color()
+                            };
+
+                            for (i, cp) in grapheme.code_points.iter().enumerate() {
+                                if i == 0 {
+                                    ui.monospace(grapheme.byte_offset.to_string());
+                                    if ui
+                                        .add(Button::new(
+                                            RichText::new(&grapheme.text).monospace().color(color),
+                                        ))
+                                        .on_hover_text("Copy grapheme")
+                                        .clicked()
+                                    {
+                                        ui.ctx().copy_text(grapheme.text.clone());
+                                    }
+                                    ui.monospace(grapheme.utf8_hex());
+                                    ui.monospace(grapheme.utf16_hex());
+                                } else {
+                                    for _ in 0..4 {
+                                        ui.label("");
+                                    }
+                                }
+
+                                ui.monospace(cp.code_point());
+                                ui.monospace(cp.category);
+                                ui.label(&cp.name);
+                                let flags = cp
+                                    .flags
+                                    .iter()
+                                    .map(|flag| flag.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+                                ui.label(RichText::new(flags).color(Color32::YELLOW));
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,280 @@
+use eframe::egui::{Color32, CursorIcon, Grid, RichText, ScrollArea, TextEdit, Ui};
+
+use crate::{
+    context::FrameCtx,
+    types::error::append_global_error,
+    ui::{
+        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        traits::UiPanel,
+    },
+};
+
+const FIELD_WIDTH: f32 = 320.0;
+
+pub struct UrlPanel;
+
+impl UiPanel for UrlPanel {
+    fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.heading("URL Parser");
+        ui.add_space(DOUBLE_SPACE);
+
+        self.render_input_section(ctx, ui);
+        ui.add_space(SPACE);
+        self.render_action_buttons(ctx, ui);
+        ui.add_space(SPACE);
+
+        if let Some(error) = &ctx.app.url.error {
+            ui.colored_label(Color32::RED, format!("❌ {}", error));
+            ui.add_space(SPACE);
+        }
+
+        ScrollArea::vertical()
+            .id_salt("url_components_scroll")
+            .show(ui, |ui| {
+                let mut changed = self.render_components_section(ctx, ui);
+                ui.add_space(SPACE);
+                changed |= self.render_path_section(ctx, ui);
+                ui.add_space(SPACE);
+                changed |= self.render_query_section(ctx, ui);
+
+                if changed && ctx.app.url.live_conversion {
+                    let _ = ctx.app.url.build();
+                }
+            });
+    }
+}
+
+impl Default for UrlPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UrlPanel {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_input_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.label("URL");
+        ui.add_space(HALF_SPACE);
+        let response = ui.add(
+            TextEdit::singleline(&mut ctx.app.url.input)
+                .desired_width(ui.available_width())
+                .hint_text("https://example.com/path?key=value#fragment"),
+        );
+
+        if ctx.app.url.live_conversion && response.changed() {
+            let _ = ctx.app.url.parse();
+        }
+    }
+
+    fn render_action_buttons(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut ctx.app.url.live_conversion, "Live conversion");
+
+            if ui
+                .button("🔍 Parse")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.url.parse() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("🔨 Build")
+                .on_hover_text("Rebuild the URL from the components below")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                if let Err(e) = ctx.app.url.build() {
+                    append_global_error(e);
+                }
+            }
+
+            if ui
+                .button("⟲  Clear")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.url.clear();
+            }
+        });
+    }
+
+    fn render_components_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) -> bool {
+        let c = &mut ctx.app.url.components;
+        let mut changed = false;
+
+        Grid::new("url_components_grid")
+            .num_columns(2)
+            .spacing([DOUBLE_SPACE, HALF_SPACE])
+            .show(ui, |ui| {
+                ui.label("Scheme:");
+                changed |= ui
+                    .add(TextEdit::singleline(&mut c.scheme).desired_width(FIELD_WIDTH))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Username:");
+                changed |= ui
+                    .add(TextEdit::singleline(&mut c.username).desired_width(FIELD_WIDTH))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Password:");
+                changed |= ui
+                    .add(TextEdit::singleline(&mut c.password).desired_width(FIELD_WIDTH))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Host:");
+                changed |= ui
+                    .add(
+                        TextEdit::singleline(&mut c.host)
+                            .desired_width(FIELD_WIDTH)
+                            .hint_text("unicode or punycode"),
+                    )
+                    .changed();
+                ui.end_row();
+
+                if c.host_ascii != c.host_unicode {
+                    ui.label("");
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Punycode:");
+                            ui.monospace(&c.host_ascii);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Unicode:");
+                            ui.monospace(&c.host_unicode);
+                        });
+                    });
+                    ui.end_row();
+                }
+
+                ui.label("Port:");
+                let hint = c
+                    .default_port
+                    .map(|p| format!("default {}", p))
+                    .unwrap_or_default();
+                changed |= ui
+                    .add(
+                        TextEdit::singleline(&mut c.port)
+                            .desired_width(FIELD_WIDTH)
+                            .hint_text(hint),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Fragment:");
+                let response =
+                    ui.add(TextEdit::singleline(&mut c.fragment).desired_width(FIELD_WIDTH));
+                if response.changed() {
+                    c.has_fragment = !c.fragment.is_empty();
+                    changed = true;
+                }
+                ui.end_row();
+            });
+
+        changed
+    }
+
+    fn render_path_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let mut remove = None;
+
+        if ctx.app.url.components.opaque_path {
+            ui.label("Path (opaque)");
+        } else {
+            ui.label("Path Segments");
+        }
+        ui.add_space(HALF_SPACE);
+
+        let opaque = ctx.app.url.components.opaque_path;
+        for (i, segment) in ctx.app.url.components.path_segments.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .add(TextEdit::singleline(segment).desired_width(FIELD_WIDTH))
+                    .changed();
+                if !opaque && ui.button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = remove {
+            ctx.app.url.remove_path_segment(i);
+            changed = true;
+        }
+
+        if !opaque
+            && ui
+                .button("➕ Add segment")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+        {
+            ctx.app.url.add_path_segment();
+        }
+
+        changed
+    }
+
+    fn render_query_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let mut remove = None;
+
+        ui.label("Query Parameters");
+        ui.add_space(HALF_SPACE);
+
+        Grid::new("url_query_grid")
+            .num_columns(3)
+            .striped(true)
+            .spacing([SPACE, HALF_SPACE])
+            .show(ui, |ui| {
+                ui.strong("Key");
+                ui.strong("Value");
+                ui.label("");
+                ui.end_row();
+
+                for (i, param) in ctx.app.url.components.query.iter_mut().enumerate() {
+                    changed |= ui
+                        .add(TextEdit::singleline(&mut param.key).desired_width(150.0))
+                        .changed();
+                    changed |= ui
+                        .add(TextEdit::singleline(&mut param.value).desired_width(FIELD_WIDTH))
+                        .changed();
+                    if ui.button("✖").clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(i) = remove {
+            ctx.app.url.remove_query_param(i);
+            changed = true;
+        }
+
+        if ui
+            .button("➕ Add parameter")
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .clicked()
+        {
+            ctx.app.url.add_query_param();
+        }
+
+        let query = ctx.app.url.query_string();
+        if !query.is_empty() {
+            ui.add_space(HALF_SPACE);
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Encoded:");
+                ui.monospace(RichText::new(query));
+            });
+        }
+
+        changed
+    }
+}